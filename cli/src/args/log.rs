@@ -1,9 +1,13 @@
 use std::fmt::Arguments;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
 
 use clap::Parser;
 use fern::colors::{Color, ColoredLevelConfig};
 use fern::FormatCallback;
-use indicatif::{ProgressBar, ProgressState, ProgressStyle};
+use indicatif::{HumanBytes, ProgressBar, ProgressState, ProgressStyle};
 use log::{LevelFilter, Record};
 
 #[derive(Parser)]
@@ -45,13 +49,28 @@ impl LogArgs {
             .expect("The global logger was already initialized");
     }
 
-    pub fn init_progress_bar(&self, total: u64) -> ProgressBar {
+    /// Create the download progress bar. Speed and ETA are calculated from the moving average
+    /// `bytes_per_sec` instead of the position updates of the bar
+    pub fn init_progress_bar(&self, total: u64, bytes_per_sec: Arc<AtomicU64>) -> ProgressBar {
         let pb = ProgressBar::new(total);
+        let eta_bytes_per_sec = bytes_per_sec.clone();
 
         pb.set_style(
-            ProgressStyle::with_template("{msg}\n\n{spinner:.blue} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({eta})")
+            ProgressStyle::with_template("{msg}\n\n{spinner:.blue} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta})")
                 .unwrap()
-                .with_key("eta", |state: &ProgressState, w: &mut dyn std::fmt::Write| write!(w, "{:.1}s", state.eta().as_secs_f64()).unwrap())
+                .with_key("bytes_per_sec", move |_state: &ProgressState, w: &mut dyn std::fmt::Write| {
+                    write!(w, "{}/s", HumanBytes(bytes_per_sec.load(Ordering::Relaxed))).unwrap()
+                })
+                .with_key("eta", move |state: &ProgressState, w: &mut dyn std::fmt::Write| {
+                    let speed = eta_bytes_per_sec.load(Ordering::Relaxed);
+                    let remaining = state.len().unwrap_or(0).saturating_sub(state.pos());
+
+                    if speed == 0 {
+                        write!(w, "-").unwrap()
+                    } else {
+                        write!(w, "{:.1}s", remaining as f64 / speed as f64).unwrap()
+                    }
+                })
                 .progress_chars("█░░")
                 .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏")
         );
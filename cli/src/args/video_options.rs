@@ -28,6 +28,17 @@ pub struct VideoOptionsArgs {
     default_value = "highest",
     )]
     pub quality: Option<Quality>,
+
+    /// Download the format with this itag, see `--list-formats` for the available itags
+    #[clap(
+        long,
+        conflicts_with_all(& ["only_audio", "only_video", "quality"])
+    )]
+    pub itag: Option<u64>,
+
+    /// Prefer formats of this codec family over higher qualities of other codecs, e.g. `avc1` for H.264
+    #[clap(long, conflicts_with_all(& ["itag"]))]
+    pub prefer_codec: Option<String>,
 }
 
 fn parse_from_str(s: &str) -> anyhow::Result<Quality> {
@@ -33,15 +33,23 @@ pub struct DownloadArgs {
     )]
     pub path: Option<PathBuf>,
 
-    /// The filename of the video file [default: <VIDEO_ID>.mp3]
+    /// The filename of the video file [default: <VIDEO_ID>.<CONTAINER>]
     ///
     /// If the file already exists, it will be removed, even if the download fails!
     #[clap(
         short,
         long = "filename",
-        help = "The filename of the video file [default: <VIDEO_ID>.mp3]",
+        help = "The filename of the video file [default: <VIDEO_ID>.<CONTAINER>]",
         num_args = 1,
         required = false
     )]
     pub filename: Option<PathBuf>,
+
+    /// Print the available formats of the video and exit
+    #[clap(
+        short = 'F',
+        long = "list-formats",
+        help = "Print the available formats of the video and exit"
+    )]
+    pub list_formats: bool,
 }
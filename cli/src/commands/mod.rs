@@ -1,8 +1,9 @@
 pub mod download;
+pub mod playlist;
 
 use clap::Parser;
 
-use self::download::DownloadArgs;
+use self::{download::DownloadArgs, playlist::PlaylistArgs};
 
 #[derive(Parser)]
 #[clap(
@@ -19,4 +20,8 @@ pub enum Commands {
     Download the video to spesific folder or stdout
     ")]
     Download(DownloadArgs),
+    #[clap(about = "\
+    Download all videos of the playlist to spesific folder
+    ")]
+    Playlist(PlaylistArgs),
 }
@@ -0,0 +1,51 @@
+use clap::Parser;
+use std::path::PathBuf;
+
+use crate::args::{log::LogArgs, video_options::VideoOptionsArgs};
+
+#[derive(Parser)]
+pub struct PlaylistArgs {
+    #[clap(
+        short = 'u',
+        long = "url",
+        help = "Playlist URL",
+        num_args = 1,
+        required = true
+    )]
+    pub url: String,
+
+    #[clap(flatten)]
+    pub video_options: VideoOptionsArgs,
+
+    #[clap(flatten)]
+    pub log: LogArgs,
+
+    /// Where to download the videos to [default: **./**]
+    #[clap(
+        short = 'p',
+        long = "path",
+        help = "Location folder to download [default: ./]",
+        num_args = 1,
+        required = false
+    )]
+    pub path: Option<PathBuf>,
+
+    /// How many videos are downloaded at the same time
+    #[clap(
+        short = 'c',
+        long = "concurrency",
+        help = "How many videos are downloaded at the same time",
+        default_value_t = 3,
+        value_parser = clap::value_parser!(u16).range(1..)
+    )]
+    pub concurrency: u16,
+
+    /// Maximum number of videos to download, all videos of the playlist are downloaded if not set
+    #[clap(
+        long = "limit",
+        help = "Maximum number of videos to download [default: all]",
+        num_args = 1,
+        required = false
+    )]
+    pub limit: Option<u64>,
+}
@@ -5,18 +5,34 @@ pub mod utils;
 use std::{
     fs::File,
     path::{Path, PathBuf},
-    pin::Pin,
+    sync::Arc,
+    time::Duration,
 };
 
 use anyhow::{Error, Result};
 use clap::Parser;
 use colored::Colorize;
-use rusty_ytdl::{Video, VideoOptions, VideoSearchOptions};
-use tokio::io::{self, AsyncWriteExt};
+use indicatif::{MultiProgress, ProgressBar};
+use rusty_ytdl::{
+    choose_format,
+    search::{Playlist, PlaylistSearchOptions},
+    stream::Stream,
+    RequestOptions, Video, VideoFormat, VideoOptions, VideoQuality, VideoSearchOptions,
+};
+use tokio::{
+    io::{self, AsyncWriteExt},
+    sync::Semaphore,
+    task::JoinSet,
+};
+
+use args::video_options::{Quality, VideoOptionsArgs};
+use commands::{download::DownloadArgs, playlist::PlaylistArgs, Commands};
+use utils::{
+    format_table::print_formats, result_serializer::ResultSerializer, speed::TransferSpeed,
+};
 
-use args::video_options::Quality;
-use commands::{download::DownloadArgs, Commands};
-use utils::result_serializer::ResultSerializer;
+/// Extension of the downloaded file if the container of the format is unknown
+const DEFAULT_EXTENSION: &str = "mp4";
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -24,6 +40,7 @@ async fn main() -> Result<()> {
 
     let res = match commands {
         Commands::Download(args) => download(args).await,
+        Commands::Playlist(args) => playlist(args).await,
     };
 
     if let Err(ref err) = res {
@@ -46,33 +63,9 @@ async fn download(args: DownloadArgs) -> Result<()> {
     args.log.init_logger();
 
     let video_identifier = args.id;
-    let download_path = args.path.unwrap_or(PathBuf::new().join("."));
-
-    if !download_path.exists() {
-        return Err(Error::msg("Folder path not found!"));
-    } else if !download_path.is_dir() {
-        return Err(Error::msg("Output path must be a directory!"));
-    }
-
-    let filter = if args.video_options.only_audio {
-        VideoSearchOptions::Audio
-    } else if args.video_options.only_video {
-        VideoSearchOptions::Video
-    } else {
-        VideoSearchOptions::VideoAudio
-    };
+    let download_path = get_download_path(args.path)?;
 
-    let quality = args
-        .video_options
-        .quality
-        .unwrap_or(Quality::Highest)
-        .into();
-
-    let download_options = VideoOptions {
-        quality,
-        filter,
-        ..Default::default()
-    };
+    let download_options = get_video_options(&args.video_options);
 
     let video = Video::new_with_options(&video_identifier, download_options.clone());
 
@@ -88,7 +81,13 @@ async fn download(args: DownloadArgs) -> Result<()> {
     }
 
     let video_info = video_info.unwrap();
-    let stream = video.stream().await;
+
+    if args.list_formats {
+        print_formats(&video_info.formats);
+        return Ok(());
+    }
+
+    let stream = video.stream_from_info(&video_info).await;
 
     if let Err(err) = stream {
         return Err(Error::msg(err.to_string()));
@@ -96,12 +95,18 @@ async fn download(args: DownloadArgs) -> Result<()> {
 
     let stream = stream.unwrap();
 
+    let extension = choose_format(&video_info.formats, &download_options)
+        .map(|format| format_extension(&format))
+        .unwrap_or(DEFAULT_EXTENSION);
+
     let download_file = args
         .filename
-        .unwrap_or(format!("{}.mp3", video_info.video_details.video_id).into());
-    let video_size = stream.content_length();
+        .unwrap_or(format!("{}.{extension}", video_info.video_details.video_id).into());
 
-    let pb = args.log.init_progress_bar(video_size as u64);
+    let speed = TransferSpeed::new();
+    let pb = args
+        .log
+        .init_progress_bar(stream.content_length() as u64, speed.handle());
 
     pb.set_message(format!(
         "{} {}",
@@ -109,78 +114,19 @@ async fn download(args: DownloadArgs) -> Result<()> {
         "is downloading...".white().bold(),
     ));
 
-    let mut downloaded: u64 = 0_u64;
-
-    let pb_clone = pb.clone();
-
-    let path_package_clone = download_path.clone();
-    let download_file_clone = download_file.clone();
-
-    let future = async move {
-        if matches!(
-            args.output.output_format,
-            args::output::OutputFormat::Stdout
-        ) {
-            let mut stdout = io::stdout();
-            let mut stdout = Pin::new(&mut stdout);
-
-            while let Some(bytes) = stream.chunk().await.unwrap() {
-                if let Err(err) = stdout.write_all(&bytes).await {
-                    pb_clone.finish_and_clear();
-                    return Err(Error::msg(err.to_string()));
-                }
-
-                let new = std::cmp::min(downloaded + bytes.len() as u64, video_size as u64);
-                downloaded = new;
-                pb_clone.set_position(new);
-            }
-        } else {
-            let path_values = Path::new(&path_package_clone);
-
-            let file = File::create(path_values.join(Path::new(&download_file_clone)));
-
-            if let Err(err) = file.as_ref() {
-                pb_clone.finish_and_clear();
-                return Err(Error::msg(err.to_string()));
-            }
-
-            let mut file = file.unwrap();
-
-            while let Some(bytes) = stream.chunk().await.unwrap() {
-                use std::io::Write;
-
-                if let Err(err) = file.write_all(&bytes) {
-                    pb_clone.finish_and_clear();
-                    return Err(Error::msg(err.to_string()));
-                }
-
-                let new = std::cmp::min(downloaded + bytes.len() as u64, video_size as u64);
-                downloaded = new;
-                pb_clone.set_position(new);
-            }
-        }
-
-        Ok(())
+    let output_path = if matches!(
+        args.output.output_format,
+        args::output::OutputFormat::Stdout
+    ) {
+        None
+    } else {
+        Some(download_path.join(Path::new(&download_file)))
     };
 
-    let download_thread = tokio::spawn(future);
-
-    let pb_clone_clone = pb.clone();
-    let progress_tick = tokio::spawn(async move {
-        loop {
-            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-            pb_clone_clone.tick();
-        }
-    });
-
-    let finished = download_thread.is_finished();
-    let _ = download_thread.await?;
-
-    if finished {
-        progress_tick.abort()
-    }
+    let res = write_stream(stream, output_path.as_deref(), &pb, speed).await;
 
     pb.finish_and_clear();
+    res?;
 
     // Display successfuly download message than exit with success
     println!(
@@ -206,3 +152,226 @@ async fn download(args: DownloadArgs) -> Result<()> {
 
     Ok(())
 }
+
+async fn playlist(args: PlaylistArgs) -> Result<()> {
+    args.log.init_logger();
+
+    let download_path = get_download_path(args.path)?;
+    let download_options = get_video_options(&args.video_options);
+
+    let playlist = Playlist::get(
+        &args.url,
+        Some(&PlaylistSearchOptions {
+            limit: args.limit.unwrap_or(u64::MAX),
+            request_options: Some(download_options.request_options.clone()),
+            fetch_all: args.limit.is_none(),
+        }),
+    )
+    .await
+    .map_err(|err| Error::msg(err.to_string()))?;
+
+    let total = playlist.videos.len();
+    let index_width = total.to_string().len();
+
+    let log_args = Arc::new(args.log);
+    let multi_progress = MultiProgress::new();
+    let semaphore = Arc::new(Semaphore::new(args.concurrency as usize));
+    let mut downloads = JoinSet::new();
+
+    for (index, playlist_video) in playlist.videos.into_iter().enumerate() {
+        let log_args = log_args.clone();
+        let multi_progress = multi_progress.clone();
+        let semaphore = semaphore.clone();
+        let download_options = download_options.clone();
+        let download_path = download_path.clone();
+
+        downloads.spawn(async move {
+            let _permit = semaphore.acquire_owned().await?;
+
+            let video = Video::new_with_options(&playlist_video.id, download_options.clone())
+                .map_err(|err| Error::msg(format!("{}: {err}", playlist_video.title)))?;
+
+            let video_info = video
+                .get_info()
+                .await
+                .map_err(|err| Error::msg(format!("{}: {err}", playlist_video.title)))?;
+
+            let extension = choose_format(&video_info.formats, &download_options)
+                .map(|format| format_extension(&format))
+                .unwrap_or(DEFAULT_EXTENSION);
+
+            let download_file = format!(
+                "{:0index_width$} - {}.{extension}",
+                index + 1,
+                sanitize_filename(&playlist_video.title)
+            );
+            let output_path = download_path.join(&download_file);
+
+            let stream = video
+                .stream_from_info(&video_info)
+                .await
+                .map_err(|err| Error::msg(format!("{}: {err}", playlist_video.title)))?;
+
+            let speed = TransferSpeed::new();
+            let pb = multi_progress
+                .add(log_args.init_progress_bar(stream.content_length() as u64, speed.handle()));
+
+            pb.set_message(format!(
+                "{} {}",
+                playlist_video.title.cyan(),
+                "is downloading...".white().bold(),
+            ));
+
+            let res = write_stream(stream, Some(&output_path), &pb, speed).await;
+
+            pb.finish_and_clear();
+            multi_progress.remove(&pb);
+
+            res.map(|_| download_file)
+                .map_err(|err| Error::msg(format!("{}: {err}", playlist_video.title)))
+        });
+    }
+
+    let mut failed = 0;
+
+    while let Some(res) = downloads.join_next().await {
+        match res? {
+            Ok(download_file) => {
+                let _ = multi_progress.println(format!(
+                    "{} {}",
+                    "Downloaded".white().bold(),
+                    download_file.green()
+                ));
+            }
+            Err(err) => {
+                failed += 1;
+                let _ = multi_progress.println(format!("{} {err}", "Failed".red().bold()));
+            }
+        }
+    }
+
+    println!(
+        "\n{} {}\n",
+        format!(
+            "{} of {total} videos successfully downloaded to",
+            total - failed
+        )
+        .white()
+        .bold(),
+        String::from(download_path.to_string_lossy())
+            .as_str()
+            .green()
+            .underline()
+    );
+
+    if failed > 0 {
+        return Err(Error::msg(format!(
+            "{failed} videos could not be downloaded"
+        )));
+    }
+
+    Ok(())
+}
+
+fn get_download_path(path: Option<PathBuf>) -> Result<PathBuf> {
+    let download_path = path.unwrap_or(PathBuf::new().join("."));
+
+    if !download_path.exists() {
+        return Err(Error::msg("Folder path not found!"));
+    } else if !download_path.is_dir() {
+        return Err(Error::msg("Output path must be a directory!"));
+    }
+
+    Ok(download_path)
+}
+
+fn get_video_options(args: &VideoOptionsArgs) -> VideoOptions {
+    let filter = if args.only_audio {
+        VideoSearchOptions::Audio
+    } else if args.only_video {
+        VideoSearchOptions::Video
+    } else {
+        VideoSearchOptions::VideoAudio
+    };
+
+    let quality = if let Some(itag) = args.itag {
+        VideoQuality::Itag(itag)
+    } else {
+        args.quality.clone().unwrap_or(Quality::Highest).into()
+    };
+
+    VideoOptions {
+        quality,
+        filter,
+        prefer_codec: args.prefer_codec.clone(),
+        request_options: RequestOptions {
+            // Reuse the deciphered player functions of previous runs
+            player_cache_path: Some(std::env::temp_dir().join("rusty_ytdl_player_functions.json")),
+            // Info fetched to choose the file extension is reused by the stream
+            info_cache_ttl: Some(Duration::from_secs(600)),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+/// Write the stream to the file, or to stdout if `path` is `None`, while updating the progress bar
+async fn write_stream(
+    stream: Box<dyn Stream + Send + Sync>,
+    path: Option<&Path>,
+    pb: &ProgressBar,
+    mut speed: TransferSpeed,
+) -> Result<()> {
+    let video_size = stream.content_length() as u64;
+    let mut downloaded: u64 = 0_u64;
+
+    let mut file = path.map(File::create).transpose()?;
+    let mut stdout = io::stdout();
+
+    pb.enable_steady_tick(Duration::from_millis(100));
+
+    while let Some(bytes) = stream
+        .chunk()
+        .await
+        .map_err(|err| Error::msg(err.to_string()))?
+    {
+        if let Some(file) = file.as_mut() {
+            use std::io::Write;
+
+            file.write_all(&bytes)?;
+        } else {
+            stdout.write_all(&bytes).await?;
+        }
+
+        let new = std::cmp::min(downloaded + bytes.len() as u64, video_size);
+        downloaded = new;
+        speed.record(new);
+        pb.set_position(new);
+    }
+
+    Ok(())
+}
+
+/// File extension of the format derived from its container. Audio only `mp4` is named `m4a`
+fn format_extension(format: &VideoFormat) -> &'static str {
+    match format.mime_type.container.as_str() {
+        "mp4" if !format.has_video => "m4a",
+        "mp4" => "mp4",
+        "webm" => "webm",
+        "3gpp" => "3gp",
+        _ => DEFAULT_EXTENSION,
+    }
+}
+
+/// Replace characters which are not allowed in file names
+fn sanitize_filename(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
@@ -0,0 +1,80 @@
+use colored::Colorize;
+use indicatif::HumanBytes;
+use rusty_ytdl::VideoFormat;
+
+const HEADERS: [&str; 8] = [
+    "ITAG",
+    "EXT",
+    "RESOLUTION",
+    "FPS",
+    "VIDEO CODEC",
+    "AUDIO CODEC",
+    "BITRATE",
+    "SIZE",
+];
+
+/// Print the formats as a table like `yt-dlp -F`
+pub fn print_formats(formats: &[VideoFormat]) {
+    let rows = formats.iter().map(format_row).collect::<Vec<_>>();
+
+    let widths = HEADERS
+        .iter()
+        .enumerate()
+        .map(|(index, header)| {
+            rows.iter()
+                .map(|row| row[index].chars().count())
+                .chain([header.len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+
+    let line = |columns: &[String]| {
+        columns
+            .iter()
+            .zip(widths.iter().copied())
+            .map(|(column, width)| format!("{column:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{}", line(&HEADERS.map(|x| x.to_string())).white().bold());
+
+    for row in rows {
+        println!("{}", line(&row));
+    }
+}
+
+fn format_row(format: &VideoFormat) -> [String; 8] {
+    let or_dash = |value: Option<String>| value.unwrap_or("-".to_string());
+
+    let resolution = if format.has_video {
+        match (format.width, format.height) {
+            (Some(width), Some(height)) => format!("{width}x{height}"),
+            _ => or_dash(format.quality_label.clone()),
+        }
+    } else {
+        "audio only".to_string()
+    };
+
+    [
+        format.itag.to_string(),
+        or_dash(Some(format.mime_type.container.clone()).filter(|x| !x.is_empty())),
+        resolution,
+        or_dash(format.fps.map(|x| x.to_string())),
+        or_dash(format.mime_type.video_codec.clone()),
+        or_dash(format.mime_type.audio_codec.clone()),
+        format!("{}k", format.bitrate / 1000),
+        or_dash(format.estimated_file_size(None).map(|size| {
+            // Estimated from the bitrate if the content length is unknown
+            let prefix = if format.content_length.is_some() {
+                ""
+            } else {
+                "~"
+            };
+            format!("{prefix}{}", HumanBytes(size))
+        })),
+    ]
+}
@@ -1 +1,3 @@
+pub mod format_table;
 pub mod result_serializer;
+pub mod speed;
@@ -0,0 +1,66 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// Length of the moving average window
+const WINDOW: Duration = Duration::from_secs(5);
+
+/// Moving average of the download speed calculated from the position of every chunk
+#[derive(Debug, Clone)]
+pub struct TransferSpeed {
+    samples: VecDeque<(Instant, u64)>,
+    bytes_per_sec: Arc<AtomicU64>,
+}
+
+impl TransferSpeed {
+    pub fn new() -> Self {
+        Self {
+            samples: VecDeque::new(),
+            bytes_per_sec: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Shared handle of the current speed, read by the progress bar
+    pub fn handle(&self) -> Arc<AtomicU64> {
+        self.bytes_per_sec.clone()
+    }
+
+    /// Record the downloaded position after a chunk
+    pub fn record(&mut self, position: u64) {
+        let now = Instant::now();
+
+        self.samples.push_back((now, position));
+
+        // Keep at least two samples to calculate the speed
+        while self.samples.len() > 2
+            && self
+                .samples
+                .front()
+                .is_some_and(|(time, _)| now.duration_since(*time) > WINDOW)
+        {
+            self.samples.pop_front();
+        }
+
+        if let (Some((first_time, first_position)), Some((last_time, last_position))) =
+            (self.samples.front(), self.samples.back())
+        {
+            let elapsed = last_time.duration_since(*first_time).as_secs_f64();
+
+            if elapsed > 0.0 {
+                let speed = last_position.saturating_sub(*first_position) as f64 / elapsed;
+                self.bytes_per_sec.store(speed as u64, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+impl Default for TransferSpeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
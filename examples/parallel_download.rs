@@ -0,0 +1,35 @@
+use rusty_ytdl::*;
+
+// Compare download time of the same source with single and multiple connections
+#[tokio::main]
+async fn main() {
+    let video_url = "https://www.youtube.com/watch?v=FZ8BxMU3BYc";
+
+    for parallel_connections in [1, 4] {
+        let video_options = VideoOptions {
+            quality: VideoQuality::Highest,
+            filter: VideoSearchOptions::Audio,
+            download_options: DownloadOptions {
+                parallel_connections: Some(parallel_connections),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let video = Video::new_with_options(video_url, video_options).unwrap();
+
+        let stream = video.stream().await.unwrap();
+
+        let instant = std::time::Instant::now();
+        let mut downloaded = 0;
+
+        while let Some(chunk) = stream.chunk().await.unwrap() {
+            downloaded += chunk.len();
+        }
+
+        println!(
+            "{parallel_connections} connection(s): {downloaded} bytes in {:?}",
+            instant.elapsed()
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use rusty_ytdl::sponsorblock::SegmentCategory;
+use rusty_ytdl::Video;
+
+#[tokio::main]
+async fn main() {
+    let url = "FZ8BxMU3BYc";
+
+    let video = Video::new(url).unwrap();
+
+    let segments = video
+        .get_skip_segments(SegmentCategory::ALL)
+        .await
+        .unwrap();
+
+    for segment in segments {
+        println!(
+            "{:?}: {:.2}s - {:.2}s",
+            segment.category, segment.start, segment.end
+        );
+    }
+}
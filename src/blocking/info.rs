@@ -1,12 +1,16 @@
 use std::path::Path;
 use std::borrow::Cow;
 
+use bytes::Bytes;
+
 use crate::block_async;
 #[cfg(feature = "live")]
 use crate::blocking::stream::LiveStream;
 use crate::blocking::stream::NonLiveStream;
-use crate::constants::DEFAULT_DL_CHUNK_SIZE;
-use crate::structs::{VideoError, VideoInfo, VideoOptions};
+use crate::structs::{
+    Availability, CaptionFormat, Comment, CommentOptions, DecipherDiagnostics, RelatedVideo,
+    ThumbnailOptions, TranscriptSegment, VideoError, VideoFormat, VideoInfo, VideoOptions,
+};
 use crate::utils::choose_format;
 use crate::Video as AsyncVideo;
 
@@ -14,6 +18,10 @@ use crate::Video as AsyncVideo;
 use super::stream::LiveStreamOptions;
 use super::stream::{NonLiveStreamOptions, Stream};
 
+#[cfg(feature = "ffmpeg")]
+use crate::blocking::stream::MergedStream;
+#[cfg(feature = "sponsorblock")]
+use crate::sponsorblock::{Segment, SegmentCategory};
 #[cfg(feature = "ffmpeg")]
 use crate::structs::FFmpegArgs;
 
@@ -39,18 +47,77 @@ impl<'opts> Video<'opts> {
         Ok(Self(AsyncVideo::new_with_options(url_or_id, options)?))
     }
 
+    /// Crate [`Video`] struct with custom [`VideoOptions`] using an already built client, see [`AsyncVideo::new_with_client`]
+    pub fn new_with_client(
+        url_or_id: impl Into<String>,
+        options: impl Into<Cow<'opts, VideoOptions>>,
+        client: reqwest_middleware::ClientWithMiddleware,
+    ) -> Result<Self, VideoError> {
+        Ok(Self(AsyncVideo::new_with_client(
+            url_or_id, options, client,
+        )?))
+    }
+
+    /// Crate [`Video`] struct of the parent video of a clip with custom [`VideoOptions`], see [`AsyncVideo::from_clip`]
+    pub fn from_clip(
+        clip_url: impl Into<String>,
+        options: impl Into<Cow<'opts, VideoOptions>>,
+    ) -> Result<Self, VideoError> {
+        Ok(Self(block_async!(AsyncVideo::from_clip(
+            clip_url, options
+        ))?))
+    }
+
     /// Try to get basic information about video
     /// - `HLS` and `DashMPD` formats excluded!
     pub fn get_basic_info(&self) -> Result<VideoInfo, VideoError> {
         Ok(block_async!(self.0.get_basic_info())?)
     }
 
+    /// Try to get metadata of the video without resolving the download URLs, see [`AsyncVideo::get_metadata`]
+    pub fn get_metadata(&self) -> Result<VideoInfo, VideoError> {
+        Ok(block_async!(self.0.get_metadata())?)
+    }
+
     /// Try to get full information about video
     /// - `HLS` and `DashMPD` formats included!
     pub fn get_info(&self) -> Result<VideoInfo, VideoError> {
         Ok(block_async!(self.0.get_info())?)
     }
 
+    /// Get the formats matching the options of the video ordered from the best candidate, see [`AsyncVideo::formats`]
+    pub fn formats(&self) -> Result<Vec<VideoFormat>, VideoError> {
+        Ok(block_async!(self.0.formats())?)
+    }
+
+    /// Get the format by exact itag number, see [`AsyncVideo::get_format_by_itag`]
+    pub fn get_format_by_itag(&self, itag: u64) -> Result<Option<VideoFormat>, VideoError> {
+        Ok(block_async!(self.0.get_format_by_itag(itag))?)
+    }
+
+    /// Get the untouched JSON of the innertube `player` endpoint, see [`AsyncVideo::get_raw_player_response`]
+    pub fn get_raw_player_response(&self) -> Result<serde_json::Value, VideoError> {
+        Ok(block_async!(self.0.get_raw_player_response())?)
+    }
+
+    /// Get the DASH manifest URL of the video, see [`AsyncVideo::get_dash_manifest_url`]
+    pub fn get_dash_manifest_url(&self) -> Result<Option<String>, VideoError> {
+        Ok(block_async!(self.0.get_dash_manifest_url())?)
+    }
+
+    /// Get the HLS manifest URL of the video, see [`AsyncVideo::get_hls_manifest_url`]
+    pub fn get_hls_manifest_url(&self) -> Result<Option<String>, VideoError> {
+        Ok(block_async!(self.0.get_hls_manifest_url())?)
+    }
+
+    /// Same as [`Video::get_info`] but also returns the functions the formats are deciphered with,
+    /// see [`AsyncVideo::get_info_with_diagnostics`]
+    pub fn get_info_with_diagnostics(
+        &self,
+    ) -> Result<(VideoInfo, Option<DecipherDiagnostics>), VideoError> {
+        Ok(block_async!(self.0.get_info_with_diagnostics())?)
+    }
+
     /// Try to turn [`Stream`] implemented [`LiveStream`] or [`NonLiveStream`] depend on the video.
     /// If function successfully return can download video chunk by chunk
     /// # Example
@@ -66,15 +133,25 @@ impl<'opts> Video<'opts> {
     ///     }
     /// ```
     pub fn stream(&self) -> Result<Box<dyn Stream + Send + Sync>, VideoError> {
+        let info = block_async!(self.0.get_info())?;
+
+        self.stream_from_info(&info)
+    }
+
+    /// Turn already fetched [`VideoInfo`] into [`Stream`] without fetching the info again, see [`AsyncVideo::stream_from_info`]
+    pub fn stream_from_info(
+        &self,
+        info: &VideoInfo,
+    ) -> Result<Box<dyn Stream + Send + Sync>, VideoError> {
         let client = self.0.get_client();
 
         let options = self.0.get_options();
 
-        let info = block_async!(self.0.get_info())?;
         let format = choose_format(&info.formats, &options)
             .map_err(|_op| VideoError::VideoSourceNotFound)?;
 
         let link = format.url;
+        let content_type = format.mime_type.mime.essence_str().to_string();
 
         if link.is_empty() {
             return Err(VideoError::VideoSourceNotFound);
@@ -97,12 +174,9 @@ impl<'opts> Video<'opts> {
             }
         }
 
-        let dl_chunk_size = options
-            .download_options
-            .dl_chunk_size
-            .unwrap_or(DEFAULT_DL_CHUNK_SIZE);
+        let dl_chunk_size = options.download_options.chunk_size();
 
-        let start = 0;
+        let start = options.download_options.start_offset.unwrap_or(0);
         let end = start + dl_chunk_size;
 
         let mut content_length = format
@@ -128,6 +202,13 @@ impl<'opts> Video<'opts> {
             dl_chunk_size,
             start,
             end,
+            on_progress: options.download_options.on_progress.clone(),
+            refresh_link: self.0.refresh_link(format.itag),
+            parallel_connections: options.download_options.parallel_connections,
+            max_bytes_per_second: options.download_options.max_bytes_per_second,
+            cancellation: options.download_options.cancellation.clone(),
+            verify_length: options.download_options.verify_length,
+            content_type: Some(content_type),
             #[cfg(feature = "ffmpeg")]
             ffmpeg_args: None,
         })?;
@@ -148,6 +229,7 @@ impl<'opts> Video<'opts> {
     ///            format: Some("mp3".to_string()),
     ///            audio_filter: Some("aresample=48000,asetrate=48000*0.8".to_string()),
     ///            video_filter: Some("eq=brightness=150:saturation=2".to_string()),
+    ///            ..Default::default()
     ///        })).unwrap();
     ///
     ///     while let Some(chunk) = stream.chunk().unwrap() {
@@ -167,6 +249,7 @@ impl<'opts> Video<'opts> {
             .map_err(|_op| VideoError::VideoSourceNotFound)?;
 
         let link = format.url;
+        let content_type = format.mime_type.mime.essence_str().to_string();
 
         if link.is_empty() {
             return Err(VideoError::VideoSourceNotFound);
@@ -189,12 +272,9 @@ impl<'opts> Video<'opts> {
             }
         }
 
-        let dl_chunk_size = options
-            .download_options
-            .dl_chunk_size
-            .unwrap_or(DEFAULT_DL_CHUNK_SIZE);
+        let dl_chunk_size = options.download_options.chunk_size();
 
-        let start = 0;
+        let start = options.download_options.start_offset.unwrap_or(0);
         let end = start + dl_chunk_size;
 
         let mut content_length = format
@@ -223,17 +303,42 @@ impl<'opts> Video<'opts> {
             dl_chunk_size,
             start,
             end,
+            on_progress: options.download_options.on_progress.clone(),
+            refresh_link: self.0.refresh_link(format.itag),
+            parallel_connections: options.download_options.parallel_connections,
+            max_bytes_per_second: options.download_options.max_bytes_per_second,
+            cancellation: options.download_options.cancellation.clone(),
+            verify_length: options.download_options.verify_length,
+            content_type: Some(content_type),
             ffmpeg_args,
         })?;
 
         Ok(Box::new(stream))
     }
 
-    /// Download video directly to the file
+    #[cfg(feature = "ffmpeg")]
+    /// Download separate video and audio formats muxed by FFmpeg into a single stream, see [`AsyncVideo::stream_merged`]
+    pub fn stream_merged(
+        &self,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<Box<dyn Stream + Send + Sync>, VideoError> {
+        let stream = block_async!(self.0.stream_merged(timeout))?;
+
+        Ok(Box::new(MergedStream(stream)))
+    }
+
+    /// Download video directly to the file.
+    /// If the path has no extension, it is inferred from the chosen format's container (e.g. `mp4`, `webm`).
+    /// With [`crate::DownloadOptions::start_offset`] the existing file is resumed, see [`AsyncVideo::download`]
     pub fn download<P: AsRef<Path>>(&self, path: P) -> Result<(), VideoError> {
         Ok(block_async!(self.0.download(path))?)
     }
 
+    /// Download the best Opus audio format remuxed to Ogg Opus without FFmpeg, see [`AsyncVideo::download_opus`]
+    pub fn download_opus<P: AsRef<Path>>(&self, path: P) -> Result<(), VideoError> {
+        Ok(block_async!(self.0.download_opus(path))?)
+    }
+
     #[cfg(feature = "ffmpeg")]
     /// Download video with ffmpeg args directly to the file
     pub async fn download_with_ffmpeg<P: AsRef<Path>>(
@@ -246,6 +351,58 @@ impl<'opts> Video<'opts> {
             .download_with_ffmpeg(path, ffmpeg_args))?)
     }
 
+    /// Download caption track of the video by language code in the given [`CaptionFormat`].
+    /// Manually created tracks are preferred over auto generated ones
+    pub fn download_caption(
+        &self,
+        lang: &str,
+        format: CaptionFormat,
+    ) -> Result<String, VideoError> {
+        Ok(block_async!(self.0.download_caption(lang, format))?)
+    }
+
+    /// Download thumbnail of the video selected by [`ThumbnailOptions`]
+    pub fn download_thumbnail(&self, options: ThumbnailOptions) -> Result<Bytes, VideoError> {
+        Ok(block_async!(self.0.download_thumbnail(options))?)
+    }
+
+    /// Fetch top level comments of the video according to the [`CommentOptions`]
+    pub fn get_comments(&self, options: CommentOptions) -> Result<Vec<Comment>, VideoError> {
+        Ok(block_async!(self.0.get_comments(options))?)
+    }
+
+    /// Get the transcript of the video, see [`AsyncVideo::get_transcript`]
+    pub fn get_transcript(&self, lang: Option<&str>) -> Result<Vec<TranscriptSegment>, VideoError> {
+        Ok(block_async!(self.0.get_transcript(lang))?)
+    }
+
+    /// Check the availability of the video with a single player request, see [`AsyncVideo::check_availability`]
+    pub fn check_availability(&self) -> Result<Availability, VideoError> {
+        Ok(block_async!(self.0.check_availability())?)
+    }
+
+    /// Fetch only the related videos of the watch page, see [`AsyncVideo::get_related_videos`]
+    pub fn get_related_videos(&self) -> Result<Vec<RelatedVideo>, VideoError> {
+        Ok(block_async!(self.0.get_related_videos())?)
+    }
+
+    /// Fetch the next page of the related videos of the [`VideoInfo`], see [`AsyncVideo::next_related_videos`]
+    pub fn next_related_videos(
+        &self,
+        info: &mut VideoInfo,
+    ) -> Result<Vec<RelatedVideo>, VideoError> {
+        Ok(block_async!(self.0.next_related_videos(info))?)
+    }
+
+    #[cfg(feature = "sponsorblock")]
+    /// Get SponsorBlock skip segments of the video, see [`AsyncVideo::get_skip_segments`]
+    pub fn get_skip_segments(
+        &self,
+        categories: &[SegmentCategory],
+    ) -> Result<Vec<Segment>, VideoError> {
+        Ok(block_async!(self.0.get_skip_segments(categories))?)
+    }
+
     /// Get video URL
     pub fn get_video_url(&self) -> String {
         self.0.get_video_url()
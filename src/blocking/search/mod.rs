@@ -1,6 +1,7 @@
 mod youtube;
 
 pub use youtube::{
-    Channel, EmbedOptions, Playlist, PlaylistSearchOptions, RequestOptions, SearchOptions,
-    SearchResult, SearchType, Video, YouTube,
+    Channel, ChannelSearchOptions, ChannelVideos, DurationFilter, EmbedOptions, Playlist,
+    PlaylistSearchOptions, RequestOptions, SearchOptions, SearchResult, SearchType, SortBy,
+    UploadDate, Video, YouTube,
 };
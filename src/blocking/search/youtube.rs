@@ -1,8 +1,11 @@
 pub use crate::search::{
-    Channel, EmbedOptions, LanguageTags, PlaylistSearchOptions, RequestOptions, SearchOptions,
-    SearchResult, SearchType, Video,
+    Channel, ChannelSearchOptions, DurationFilter, EmbedOptions, LanguageTags,
+    PlaylistSearchOptions, RequestOptions, SearchOptions, SearchPage, SearchResult, SearchType,
+    SortBy, SuggestionOptions, UploadDate, Video,
+};
+use crate::search::{
+    ChannelVideos as AsyncChannelVideos, Playlist as AsyncPlaylist, YouTube as AsyncYouTube,
 };
-use crate::search::{Playlist as AsyncPlaylist, YouTube as AsyncYouTube};
 use crate::{block_async, VideoError};
 use serde::Serialize;
 
@@ -39,6 +42,26 @@ impl YouTube {
         Ok(block_async!(self.0.search(query, search_options))?)
     }
 
+    /// Search and return only the first page of results, see [`AsyncYouTube::search_page`]
+    pub fn search_page(
+        &self,
+        query: impl Into<String>,
+        search_options: Option<&SearchOptions>,
+    ) -> Result<SearchPage, VideoError> {
+        Ok(block_async!(self.0.search_page(query, search_options))?)
+    }
+
+    /// Fetch the next page of a search, see [`AsyncYouTube::search_continued`]
+    pub fn search_continued(
+        &self,
+        continuation: impl Into<String>,
+        search_options: Option<&SearchOptions>,
+    ) -> Result<SearchPage, VideoError> {
+        Ok(block_async!(self
+            .0
+            .search_continued(continuation, search_options))?)
+    }
+
     /// Classic search function but only get first [`SearchResult`] item. `SearchOptions.limit` not use in request its will be always `1`
     pub fn search_one(
         &self,
@@ -65,6 +88,31 @@ impl YouTube {
     ) -> Result<Vec<String>, VideoError> {
         Ok(block_async!(self.0.suggestion(query, language))?)
     }
+
+    /// Fetch search suggestion in the language and region of [`SuggestionOptions`], see [`AsyncYouTube::suggestion_with_options`]
+    pub fn suggestion_with_options(
+        &self,
+        query: impl Into<String>,
+        options: &SuggestionOptions,
+    ) -> Result<Vec<String>, VideoError> {
+        Ok(block_async!(self
+            .0
+            .suggestion_with_options(query, options))?)
+    }
+
+    /// Fetch channel details (subscriber count, description, banner and verification)
+    /// with channel id, handle (`@name`) or channel URL
+    /// # Example
+    /// ```ignore
+    /// let youtube = YouTube::new().unwrap();
+    ///
+    /// let channel = youtube.channel("@YouTube");
+    ///
+    /// println!("{channel:#?}");
+    /// ```
+    pub fn channel(&self, url_or_id: impl Into<String>) -> Result<Channel, VideoError> {
+        Ok(block_async!(self.0.channel(url_or_id))?)
+    }
 }
 
 impl std::ops::Deref for YouTube {
@@ -88,6 +136,7 @@ pub struct Playlist(pub(super) AsyncPlaylist);
 
 impl Playlist {
     /// Try to get [`Playlist`] than fetch videos according to the [`PlaylistSearchOptions`]
+    /// - YouTube Mix (`RD...`) playlists are infinite, `fetch_all` is ignored and `limit` is always applied
     pub fn get(
         url: impl Into<String>,
         options: Option<&PlaylistSearchOptions>,
@@ -95,6 +144,16 @@ impl Playlist {
         Ok(Self(block_async!(AsyncPlaylist::get(url, options))?))
     }
 
+    /// Get the video at the `index` query param of the playlist URL, see [`AsyncPlaylist::get_video_at_index`]
+    pub fn get_video_at_index(
+        url: impl Into<String>,
+        options: Option<&PlaylistSearchOptions>,
+    ) -> Result<Video, VideoError> {
+        Ok(block_async!(AsyncPlaylist::get_video_at_index(
+            url, options
+        ))?)
+    }
+
     /// Get next chunk of videos from playlist and return fetched [`Video`] array.
     /// - If limit is [`None`] it will be [`u64::MAX`]
     /// - If [`Playlist`] is coming from [`SearchResult`] this function always return empty [`Vec<Video>`]!
@@ -172,6 +231,17 @@ impl Playlist {
     pub fn get_playlist_url(url_or_id: impl Into<String>) -> Option<String> {
         AsyncPlaylist::get_playlist_url(url_or_id)
     }
+
+    /// Get id of the YouTube Mix (`RD...`) playlist. Albums are not counted as mix
+    pub fn get_mix_id(url_or_id: impl Into<String>) -> Option<String> {
+        AsyncPlaylist::get_mix_id(url_or_id)
+    }
+}
+
+impl From<AsyncPlaylist> for Playlist {
+    fn from(playlist: AsyncPlaylist) -> Self {
+        Self(playlist)
+    }
 }
 
 impl std::ops::Deref for Playlist {
@@ -187,3 +257,65 @@ impl std::ops::DerefMut for Playlist {
         &mut self.0
     }
 }
+
+#[derive(Clone, derivative::Derivative, Serialize)]
+#[derivative(Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelVideos(pub(super) AsyncChannelVideos);
+
+impl ChannelVideos {
+    /// Try to get [`Channel`] than fetch uploaded videos according to the [`ChannelSearchOptions`], see [`Channel::get`]
+    /// - `url_or_id` can be channel id, handle (`@name`) or channel URL
+    pub fn get(
+        url_or_id: impl Into<String>,
+        options: Option<&ChannelSearchOptions>,
+    ) -> Result<Self, VideoError> {
+        Ok(Self(block_async!(Channel::get(url_or_id, options))?))
+    }
+
+    /// Fetch uploaded videos of the channel (e.g. coming from [`SearchResult`]), see [`Channel::videos`]
+    pub fn from_channel(
+        channel: &Channel,
+        options: Option<&ChannelSearchOptions>,
+    ) -> Result<Self, VideoError> {
+        Ok(Self(block_async!(channel.videos(options))?))
+    }
+
+    /// Get next chunk of uploaded videos from channel and return fetched [`Video`] array.
+    /// - If limit is [`None`] it will be [`u64::MAX`]
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut uploads = ChannelVideos::get("@YouTube", None).unwrap();
+    ///
+    /// while let Ok(videos) = uploads.next(Some(50)) {
+    ///     if videos.is_empty() {
+    ///         break;
+    ///     }
+    /// }
+    /// ```
+    pub fn next(&mut self, limit: Option<u64>) -> Result<Vec<Video>, VideoError> {
+        Ok(block_async!(self.0.next(limit))?)
+    }
+}
+
+impl From<AsyncChannelVideos> for ChannelVideos {
+    fn from(channel_videos: AsyncChannelVideos) -> Self {
+        Self(channel_videos)
+    }
+}
+
+impl std::ops::Deref for ChannelVideos {
+    type Target = AsyncChannelVideos;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for ChannelVideos {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
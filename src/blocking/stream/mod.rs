@@ -1,5 +1,7 @@
 mod streams;
 
+#[cfg(feature = "ffmpeg")]
+pub(crate) use streams::MergedStream;
 #[cfg(feature = "live")]
 pub use streams::{LiveStream, LiveStreamOptions};
 pub use streams::{NonLiveStream, NonLiveStreamOptions, Stream};
@@ -1,4 +1,5 @@
 use bytes::Bytes;
+use std::{path::Path, time::Duration};
 
 use crate::blocking::stream::Stream;
 use crate::stream::{LiveStream as AsyncLiveStream, LiveStreamOptions};
@@ -10,6 +11,20 @@ impl LiveStream {
     pub fn new(options: LiveStreamOptions) -> Result<Self, VideoError> {
         Ok(Self(AsyncLiveStream::new(options)?))
     }
+
+    /// Record the live stream to the file until it ends or the `duration` elapses, see [`AsyncLiveStream::record_to_file`]
+    pub fn record_to_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        duration: Option<Duration>,
+    ) -> Result<(), VideoError> {
+        Ok(block_async!(self.0.record_to_file(path, duration))?)
+    }
+
+    /// Start the stream `offset` back from the live edge, see [`AsyncLiveStream::segments_from`]
+    pub fn segments_from(&self, offset: Duration) -> Result<Duration, VideoError> {
+        Ok(block_async!(self.0.segments_from(offset))?)
+    }
 }
 
 impl Stream for LiveStream {
@@ -17,6 +32,15 @@ impl Stream for LiveStream {
         use crate::stream::Stream;
         Ok(block_async!(self.0.chunk())?)
     }
+
+    fn content_type(&self) -> Option<&str> {
+        use crate::stream::Stream;
+        self.0.content_type()
+    }
+
+    fn is_live(&self) -> bool {
+        true
+    }
 }
 
 impl std::ops::Deref for LiveStream {
@@ -0,0 +1,19 @@
+use bytes::Bytes;
+
+use crate::blocking::stream::Stream;
+use crate::{block_async, VideoError};
+
+/// Video and audio formats muxed by FFmpeg, see [`crate::Video::stream_merged`]
+pub(crate) struct MergedStream(pub(crate) Box<dyn crate::stream::Stream + Send + Sync>);
+
+impl Stream for MergedStream {
+    fn chunk(&self) -> Result<Option<Bytes>, VideoError> {
+        use crate::stream::Stream;
+        Ok(block_async!(self.0.chunk())?)
+    }
+
+    fn content_length(&self) -> usize {
+        use crate::stream::Stream;
+        self.0.content_length()
+    }
+}
@@ -8,10 +8,14 @@ use crate::VideoError;
 
 #[cfg(feature = "live")]
 mod live;
+#[cfg(feature = "ffmpeg")]
+mod merged;
 mod non_live;
 
 #[cfg(feature = "live")]
 pub use live::LiveStream;
+#[cfg(feature = "ffmpeg")]
+pub(crate) use merged::MergedStream;
 pub use non_live::NonLiveStream;
 
 pub trait Stream {
@@ -26,4 +30,33 @@ pub trait Stream {
     fn content_length(&self) -> usize {
         0
     }
+
+    /// Mime type of the stream without codecs (e.g. `audio/webm`), see [`crate::stream::Stream::content_type`]
+    fn content_type(&self) -> Option<&str> {
+        None
+    }
+
+    /// Whether the stream is a [`LiveStream`], which has no end
+    fn is_live(&self) -> bool {
+        false
+    }
+
+    /// Download the whole stream into memory and return all the [`Bytes`]
+    ///
+    /// Live streams are refused because they have no end, use [`Stream::chunk`] instead
+    fn download_to_end(&self) -> Result<Bytes, VideoError> {
+        if self.is_live() {
+            return Err(VideoError::DownloadError(
+                "Stream has unbounded length (live stream), use chunk instead".to_string(),
+            ));
+        }
+
+        let mut buffer = bytes::BytesMut::with_capacity(self.content_length());
+
+        while let Some(chunk) = self.chunk()? {
+            buffer.extend_from_slice(&chunk);
+        }
+
+        Ok(buffer.freeze())
+    }
 }
@@ -10,6 +10,11 @@ impl NonLiveStream {
     pub fn new(options: NonLiveStreamOptions) -> Result<Self, VideoError> {
         Ok(Self(AsyncNonLiveStream::new(options)?))
     }
+
+    /// New stream of only the `start..=end` bytes of the same source, see [`AsyncNonLiveStream::range`]
+    pub fn range(&self, start: u64, end: Option<u64>) -> Result<Self, VideoError> {
+        Ok(Self(block_async!(self.0.range(start, end))?))
+    }
 }
 
 impl Stream for NonLiveStream {
@@ -21,6 +26,10 @@ impl Stream for NonLiveStream {
     fn content_length(&self) -> usize {
         self.0.content_length() as usize
     }
+
+    fn content_type(&self) -> Option<&str> {
+        self.0.content_type()
+    }
 }
 
 impl std::ops::Deref for NonLiveStream {
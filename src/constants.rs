@@ -49,11 +49,26 @@ pub(crate) static PARSE_INT_REGEX: Lazy<Regex> =
 // 10485760 -> Default is 10MB to avoid Youtube throttle (Bigger than this value can be throttle by Youtube)
 pub(crate) const DEFAULT_DL_CHUNK_SIZE: u64 = 10485760;
 
+/// Smaller chunk sizes are raised to this value (64KB), `0` would never advance the requested range
+pub(crate) const MIN_DL_CHUNK_SIZE: u64 = 65536;
+
+/// Bigger chunk sizes are lowered to this value, Youtube throttles ranges above 10MB
+pub(crate) const MAX_DL_CHUNK_SIZE: u64 = DEFAULT_DL_CHUNK_SIZE;
+
 /// Default max number of retries for a web reqwest.
 pub(crate) const DEFAULT_MAX_RETRIES: u32 = 3;
 
-pub static INNERTUBE_CLIENT: Lazy<HashMap<&str, (&str, &str, &str)>> =
-    // (clientVersion, clientName, json value)
+/// Max delay waited for the `Retry-After` header of a `429`/`503` response before retrying
+pub(crate) const MAX_RETRY_AFTER: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Max iterations of a single loop in the player script functions, bounds a pathological script instead of spinning forever
+pub(crate) const JS_LOOP_ITERATION_LIMIT: u64 = 1_000_000;
+
+/// Max recursion depth in the player script functions
+pub(crate) const JS_RECURSION_LIMIT: usize = 1024;
+
+pub static INNERTUBE_CLIENT: Lazy<HashMap<&str, (&str, &str, &str, &str)>> =
+    // (clientVersion, clientName, json value, user agent of the player and videoplayback requests)
     Lazy::new(|| {
         HashMap::from([
             (
@@ -68,6 +83,7 @@ pub static INNERTUBE_CLIENT: Lazy<HashMap<&str, (&str, &str, &str)>> =
                             "hl": "en"
                         }
                     },"#,
+                    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/87.0.4280.101 Safari/537.36",
                 ),
             ),
             (
@@ -87,6 +103,7 @@ pub static INNERTUBE_CLIENT: Lazy<HashMap<&str, (&str, &str, &str)>> =
                             "hl": "en"
                         }
                     },"#,
+                    "com.google.ios.youtube/19.29.1 (iPhone16,2; U; CPU iOS 17_5_1 like Mac OS X;)",
                 ),
             ),
             (
@@ -107,6 +124,41 @@ pub static INNERTUBE_CLIENT: Lazy<HashMap<&str, (&str, &str, &str)>> =
                             "embedUrl": "https://google.com"
                         }
                     },"#,
+                    "Mozilla/5.0 (ChromiumStylePlatform) Cobalt/Version",
+                ),
+            ),
+            (
+                "android",
+                (
+                    "19.29.37",
+                    "3",
+                    r#""context": {
+                        "client": {
+                            "clientName": "ANDROID",
+                            "clientVersion": "19.29.37",
+                            "androidSdkVersion": 30,
+                            "userAgent": "com.google.android.youtube/19.29.37 (Linux; U; Android 11) gzip",
+                            "osName": "Android",
+                            "osVersion": "11",
+                            "hl": "en"
+                        }
+                    },"#,
+                    "com.google.android.youtube/19.29.37 (Linux; U; Android 11) gzip",
+                ),
+            ),
+            (
+                "web_creator",
+                (
+                    "1.20240723.03.00",
+                    "62",
+                    r#""context": {
+                        "client": {
+                            "clientName": "WEB_CREATOR",
+                            "clientVersion": "1.20240723.03.00",
+                            "hl": "en"
+                        }
+                    },"#,
+                    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/87.0.4280.101 Safari/537.36",
                 ),
             ),
         ])
@@ -1298,5 +1350,133 @@ pub static FORMATS: Lazy<HashMap<&str, StaticFormat>> = Lazy::new(|| {
                 audio_bitrate: None,
             },
         ),
+        (
+            "394",
+            StaticFormat {
+                mime_type: MimeType {
+                    mime: Mime::from_str("video/mp4; codecs=\"av01.0.00M.08\"")
+                        .expect("Static mime error: itag 394"),
+                    container: "mp4".to_string(),
+                    codecs: vec!["av01.0.00M.08".to_string()],
+                    video_codec: Some("av01.0.00M.08".to_string()),
+                    audio_codec: None,
+                },
+                quality_label: Some("144p".to_string()),
+                bitrate: Some(80000),
+                audio_bitrate: None,
+            },
+        ),
+        (
+            "395",
+            StaticFormat {
+                mime_type: MimeType {
+                    mime: Mime::from_str("video/mp4; codecs=\"av01.0.00M.08\"")
+                        .expect("Static mime error: itag 395"),
+                    container: "mp4".to_string(),
+                    codecs: vec!["av01.0.00M.08".to_string()],
+                    video_codec: Some("av01.0.00M.08".to_string()),
+                    audio_codec: None,
+                },
+                quality_label: Some("240p".to_string()),
+                bitrate: Some(160000),
+                audio_bitrate: None,
+            },
+        ),
+        (
+            "396",
+            StaticFormat {
+                mime_type: MimeType {
+                    mime: Mime::from_str("video/mp4; codecs=\"av01.0.01M.08\"")
+                        .expect("Static mime error: itag 396"),
+                    container: "mp4".to_string(),
+                    codecs: vec!["av01.0.01M.08".to_string()],
+                    video_codec: Some("av01.0.01M.08".to_string()),
+                    audio_codec: None,
+                },
+                quality_label: Some("360p".to_string()),
+                bitrate: Some(350000),
+                audio_bitrate: None,
+            },
+        ),
+        (
+            "397",
+            StaticFormat {
+                mime_type: MimeType {
+                    mime: Mime::from_str("video/mp4; codecs=\"av01.0.04M.08\"")
+                        .expect("Static mime error: itag 397"),
+                    container: "mp4".to_string(),
+                    codecs: vec!["av01.0.04M.08".to_string()],
+                    video_codec: Some("av01.0.04M.08".to_string()),
+                    audio_codec: None,
+                },
+                quality_label: Some("480p".to_string()),
+                bitrate: Some(650000),
+                audio_bitrate: None,
+            },
+        ),
+        (
+            "398",
+            StaticFormat {
+                mime_type: MimeType {
+                    mime: Mime::from_str("video/mp4; codecs=\"av01.0.05M.08\"")
+                        .expect("Static mime error: itag 398"),
+                    container: "mp4".to_string(),
+                    codecs: vec!["av01.0.05M.08".to_string()],
+                    video_codec: Some("av01.0.05M.08".to_string()),
+                    audio_codec: None,
+                },
+                quality_label: Some("720p".to_string()),
+                bitrate: Some(1300000),
+                audio_bitrate: None,
+            },
+        ),
+        (
+            "399",
+            StaticFormat {
+                mime_type: MimeType {
+                    mime: Mime::from_str("video/mp4; codecs=\"av01.0.08M.08\"")
+                        .expect("Static mime error: itag 399"),
+                    container: "mp4".to_string(),
+                    codecs: vec!["av01.0.08M.08".to_string()],
+                    video_codec: Some("av01.0.08M.08".to_string()),
+                    audio_codec: None,
+                },
+                quality_label: Some("1080p".to_string()),
+                bitrate: Some(2500000),
+                audio_bitrate: None,
+            },
+        ),
+        (
+            "400",
+            StaticFormat {
+                mime_type: MimeType {
+                    mime: Mime::from_str("video/mp4; codecs=\"av01.0.12M.08\"")
+                        .expect("Static mime error: itag 400"),
+                    container: "mp4".to_string(),
+                    codecs: vec!["av01.0.12M.08".to_string()],
+                    video_codec: Some("av01.0.12M.08".to_string()),
+                    audio_codec: None,
+                },
+                quality_label: Some("1440p".to_string()),
+                bitrate: Some(6500000),
+                audio_bitrate: None,
+            },
+        ),
+        (
+            "401",
+            StaticFormat {
+                mime_type: MimeType {
+                    mime: Mime::from_str("video/mp4; codecs=\"av01.0.12M.08\"")
+                        .expect("Static mime error: itag 401"),
+                    container: "mp4".to_string(),
+                    codecs: vec!["av01.0.12M.08".to_string()],
+                    video_codec: Some("av01.0.12M.08".to_string()),
+                    audio_codec: None,
+                },
+                quality_label: Some("2160p".to_string()),
+                bitrate: Some(13000000),
+                audio_bitrate: None,
+            },
+        ),
     ])
 });
@@ -1,32 +1,57 @@
+use bytes::Bytes;
+use m3u8_rs::parse_master_playlist;
 use once_cell::sync::Lazy;
-use reqwest::{
-    header::{HeaderMap, HeaderName, HeaderValue, COOKIE},
-    Client,
-};
-use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
-use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest_middleware::ClientWithMiddleware;
 use scraper::{Html, Selector};
 use serde_json::json;
-use std::{borrow::{Borrow, Cow}, path::Path, time::Duration};
+use std::{
+    borrow::{Borrow, Cow},
+    future::Future,
+    path::Path,
+    pin::Pin,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::RwLock;
 use url::Url;
 
+#[cfg(feature = "sponsorblock")]
+use crate::sponsorblock::{fetch_skip_segments, Segment, SegmentCategory};
+#[cfg(feature = "ffmpeg")]
+use crate::stream::{FFmpegAudioInput, FFmpegStreamOptions, MergedStream};
 #[cfg(feature = "live")]
 use crate::stream::{LiveStream, LiveStreamOptions};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::stream::{NonLiveStream, NonLiveStreamOptions, RefreshLink, Stream};
 #[cfg(feature = "ffmpeg")]
-use crate::structs::FFmpegArgs;
+use crate::structs::{FFmpegArgs, VideoQuality, VideoSearchOptions};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::std::{Instant, SystemTime, UNIX_EPOCH};
 
 use crate::{
-    constants::{BASE_URL, DEFAULT_DL_CHUNK_SIZE, DEFAULT_MAX_RETRIES, INNERTUBE_CLIENT},
-    info_extras::{get_media, get_related_videos},
-    stream::{NonLiveStream, NonLiveStreamOptions, Stream},
+    constants::{BASE_URL, INNERTUBE_CLIENT},
+    info_extras::{
+        get_captions, get_comments, get_comments_continuation, get_comments_sort_continuation,
+        get_heatmap, get_media, get_related_videos, get_related_videos_continuation,
+        get_related_videos_page, get_transcript_languages, get_transcript_params,
+        get_transcript_segments,
+    },
+    remux::webm_opus_to_ogg,
     structs::{
-        CustomRetryableStrategy, PlayerResponse, VideoError, VideoInfo, VideoOptions, YTConfig,
+        Availability, CaptionFormat, Clip, Comment, CommentOptions, DecipherDiagnostics,
+        HlsVariant, InnerTubeClient, PlayerResponse, RelatedVideo, ThumbnailOptions,
+        TranscriptSegment, VideoError, VideoFormat, VideoInfo, VideoOptions, YTConfig,
     },
     utils::{
-        between, choose_format, clean_video_details, get_functions, get_html,
-        get_html5player, get_random_v6_ip, get_video_id, get_ytconfig, is_age_restricted_from_html,
-        is_live, is_not_yet_broadcasted, is_play_error, is_player_response_error, is_private_video,
-        is_rental, parse_live_video_formats, parse_video_formats, sort_formats,
+        between, candidate_formats, choose_format, clean_video_details, get_availability, get_clip,
+        get_clip_id, get_functions, get_html, get_html5player, get_playability_error,
+        get_player_url, get_video_id, get_visitor_data, get_ytconfig, has_streaming_formats,
+        is_age_gated, is_age_restricted_from_html, is_live, is_not_yet_broadcasted, is_play_error,
+        is_player_response_error, is_private_video, is_rental, parse_dash_video_formats,
+        parse_live_video_formats, parse_video_formats, sort_formats, vtt_to_srt,
     },
 };
 
@@ -39,30 +64,40 @@ pub struct Video<'opts> {
     options: Cow<'opts, VideoOptions>,
     #[derivative(PartialEq = "ignore")]
     client: ClientWithMiddleware,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    info_cache: Arc<InfoCache>,
+    /// Set if the video is created from a clip, returned on [`crate::VideoDetails::clip`]
+    clip: Option<Clip>,
 }
 
+/// Cached results of [`Video::get_basic_info`] and [`Video::get_info`], used if [`crate::RequestOptions::info_cache_ttl`] is set
+#[derive(Default)]
+struct InfoCache {
+    basic: RwLock<Option<(Instant, FetchedInfo)>>,
+    full: RwLock<Option<(Instant, FetchedInfo)>>,
+}
+
+/// Fetched info and the player functions its formats are deciphered with, `None` if nothing is deciphered
+type FetchedInfo = (VideoInfo, Option<DecipherDiagnostics>);
+
+/// Cached info is refetched if any format URL expires within this margin
+const INFO_CACHE_EXPIRE_MARGIN: Duration = Duration::from_secs(300);
+
 impl Video<'static> {
     /// Crate [`Video`] struct to get info or download with default [`VideoOptions`]
     #[cfg_attr(feature = "performance_analysis", flamer::flame)]
     pub fn new(url_or_id: impl Into<String>) -> Result<Self, VideoError> {
         let video_id = get_video_id(&url_or_id.into()).ok_or(VideoError::VideoNotFound)?;
 
-        let client = Client::builder().build().map_err(VideoError::Reqwest)?;
-
-        let retry_policy = ExponentialBackoff::builder()
-            .retry_bounds(Duration::from_millis(1000), Duration::from_millis(30000))
-            .build_with_max_retries(DEFAULT_MAX_RETRIES);
-        let client = ClientBuilder::new(client)
-            .with(RetryTransientMiddleware::new_with_policy_and_strategy(
-                retry_policy,
-                CustomRetryableStrategy,
-            ))
-            .build();
+        let options = VideoOptions::default();
+        let client = options.request_options.build_client()?;
 
         Ok(Self {
             video_id,
-            options: Cow::Owned(VideoOptions::default()),
+            options: Cow::Owned(options),
             client,
+            info_cache: Arc::new(InfoCache::default()),
+            clip: None,
         })
     }
 }
@@ -78,53 +113,85 @@ impl<'opts> Video<'opts> {
         let options = options.into();
         let video_id = get_video_id(&url_or_id.into()).ok_or(VideoError::VideoNotFound)?;
 
-        let client = match options.request_options.client.clone() {
-            Some(client) => client,
-            None => {
-                let mut client_builder = Client::builder();
+        let client = options.request_options.build_client()?;
 
-                if let Some(proxy) = &options.request_options.proxy {
-                    client_builder = client_builder.proxy(proxy.clone());
-                }
+        Ok(Self {
+            video_id,
+            options,
+            client,
+            info_cache: Arc::new(InfoCache::default()),
+            clip: None,
+        })
+    }
 
-                if let Some(ipv6_block) = &options.request_options.ipv6_block {
-                    let ipv6 = get_random_v6_ip(ipv6_block)?;
-                    client_builder = client_builder.local_address(ipv6);
-                }
+    /// Crate [`Video`] struct with custom [`VideoOptions`] using an already built client for info and download requests.
+    /// Useful to share one connection pool and custom middlewares (tracing, caching, auth) across many videos.
+    /// [`crate::RequestOptions`] client related fields (`client`, `proxy`, `cookies`, `ipv6_block`, `max_retries`) are ignored
+    ///
+    /// # Example
+    /// ```ignore
+    ///     let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    ///
+    ///     let video = Video::new_with_client(video_url, VideoOptions::default(), client.clone()).unwrap();
+    /// ```
+    pub fn new_with_client(
+        url_or_id: impl Into<String>,
+        options: impl Into<Cow<'opts, VideoOptions>>,
+        client: ClientWithMiddleware,
+    ) -> Result<Self, VideoError> {
+        let video_id = get_video_id(&url_or_id.into()).ok_or(VideoError::VideoNotFound)?;
 
-                if let Some(cookie) = &options.request_options.cookies {
-                    let mut headers = HeaderMap::new();
-                    headers.insert(
-                        COOKIE,
-                        HeaderValue::from_str(cookie).map_err(|_x| VideoError::CookieError)?,
-                    );
+        Ok(Self {
+            video_id,
+            options: options.into(),
+            client,
+            info_cache: Arc::new(InfoCache::default()),
+            clip: None,
+        })
+    }
 
-                    client_builder = client_builder.default_headers(headers)
-                }
+    /// Crate [`Video`] struct of the parent video of a clip (`https://www.youtube.com/clip/...`) with custom [`VideoOptions`].
+    /// Clip page is requested to resolve the video, its portion is returned on [`crate::VideoDetails::clip`]
+    ///
+    /// # Example
+    /// ```ignore
+    ///     let video = Video::from_clip(clip_url, VideoOptions::default()).await.unwrap();
+    ///
+    ///     let info = video.get_info().await.unwrap();
+    ///     let clip = info.video_details.clip.unwrap();
+    ///
+    ///     // Download only the clipped portion
+    ///     video.download_with_ffmpeg("clip.mp4", Some(FFmpegArgs {
+    ///         start: Some(clip.start()),
+    ///         end: Some(clip.end()),
+    ///         ..Default::default()
+    ///     })).await.unwrap();
+    /// ```
+    pub async fn from_clip(
+        clip_url: impl Into<String>,
+        options: impl Into<Cow<'opts, VideoOptions>>,
+    ) -> Result<Self, VideoError> {
+        let clip_id = get_clip_id(&clip_url.into()).ok_or(VideoError::VideoNotFound)?;
+        let options = options.into();
 
-                client_builder.build().map_err(VideoError::Reqwest)?
-            }
-        };
+        let client = options.request_options.build_client()?;
 
-        let max_retries = options
-            .request_options
-            .max_retries
-            .unwrap_or(DEFAULT_MAX_RETRIES);
-
-        let retry_policy = ExponentialBackoff::builder()
-            .retry_bounds(Duration::from_millis(1000), Duration::from_millis(30000))
-            .build_with_max_retries(max_retries);
-        let client = ClientBuilder::new(client)
-            .with(RetryTransientMiddleware::new_with_policy_and_strategy(
-                retry_policy,
-                CustomRetryableStrategy,
-            ))
-            .build();
+        let url_parsed = Url::parse_with_params(
+            format!("https://www.youtube.com/clip/{clip_id}").as_str(),
+            &[("hl", "en")],
+        )
+        .map_err(VideoError::URLParseError)?;
+
+        let initial_data = fetch_initial_data(&client, url_parsed.as_str()).await?;
+        let (video_id, clip) =
+            get_clip(&initial_data, &clip_id).ok_or(VideoError::VideoNotFound)?;
 
         Ok(Self {
             video_id,
             options,
             client,
+            info_cache: Arc::new(InfoCache::default()),
+            clip: Some(clip),
         })
     }
 
@@ -132,6 +199,59 @@ impl<'opts> Video<'opts> {
     /// - `HLS` and `DashMPD` formats excluded!
     #[cfg_attr(feature = "performance_analysis", flamer::flame)]
     pub async fn get_basic_info(&self) -> Result<VideoInfo, VideoError> {
+        self.cached_info(&self.info_cache.basic, self.fetch_basic_info(true))
+            .await
+            .map(|(info, _)| info)
+    }
+
+    /// Try to get metadata (title, author, duration, thumbnails, ...) of the video without resolving the download URLs.
+    /// Much faster than [`Video::get_basic_info`] because the player script is not deciphered and
+    /// no other innertube clients are requested
+    /// - [`VideoFormat::url`] is the raw URL or signature cipher of the web player response, not downloadable!
+    #[cfg_attr(feature = "performance_analysis", flamer::flame)]
+    pub async fn get_metadata(&self) -> Result<VideoInfo, VideoError> {
+        self.fetch_basic_info(false).await.map(|(info, _)| info)
+    }
+
+    /// Get the untouched JSON of the innertube `player` endpoint, including the fields [`VideoInfo`] doesn't model.
+    /// Requested with [`crate::RequestOptions::innertube_client`] if set, otherwise with the `web` client.
+    /// Useful to attach to bug reports when YouTube changes the response
+    /// # Example
+    /// ```ignore
+    ///     let video = Video::new("https://www.youtube.com/watch?v=FZ8BxMU3BYc").unwrap();
+    ///
+    ///     let player_response = video.get_raw_player_response().await.unwrap();
+    ///
+    ///     println!("{}", player_response["playabilityStatus"]["status"]);
+    /// ```
+    pub async fn get_raw_player_response(&self) -> Result<serde_json::Value, VideoError> {
+        let url_parsed = Url::parse_with_params(self.get_video_url().as_str(), &[("hl", "en")])
+            .map_err(VideoError::URLParseError)?;
+
+        let response = get_html(&self.client, url_parsed.as_str(), None).await?;
+
+        let client_name = self
+            .options
+            .request_options
+            .innertube_client
+            .map(|x| x.key())
+            .unwrap_or("web");
+
+        let player_response = self
+            .get_player_ytconfig(
+                &response,
+                INNERTUBE_CLIENT
+                    .get(client_name)
+                    .cloned()
+                    .unwrap_or_default(),
+                self.options.request_options.po_token.as_ref(),
+            )
+            .await?;
+
+        serde_json::from_str(&player_response).map_err(|_| VideoError::BodyCannotParsed)
+    }
+
+    async fn fetch_basic_info(&self, resolve_urls: bool) -> Result<FetchedInfo, VideoError> {
         let client = &self.client;
 
         let url_parsed = Url::parse_with_params(self.get_video_url().as_str(), &[("hl", "en")])
@@ -174,6 +294,10 @@ impl<'opts> Video<'opts> {
             (player_response, initial_response)
         };
 
+        if let Some(err) = get_playability_error(&player_response) {
+            return Err(err);
+        }
+
         if is_play_error(&player_response, ["ERROR"].to_vec()) {
             return Err(VideoError::VideoNotFound);
         }
@@ -182,29 +306,89 @@ impl<'opts> Video<'opts> {
             return Err(VideoError::VideoPlayerResponseError(reason));
         }
 
-        let is_age_restricted = is_age_restricted_from_html(&player_response, &response);
+        let is_age_restricted = is_age_restricted_from_html(&player_response, &response)
+            || is_age_gated(&player_response);
+        let is_login_required = is_private_video(&player_response);
 
-        if is_private_video(&player_response) && !is_age_restricted {
+        // Login required videos are retried with the `tv_embedded` client below before giving up
+        if is_login_required
+            && !is_age_restricted
+            && (!resolve_urls || self.options.request_options.innertube_client.is_some())
+        {
             return Err(VideoError::VideoIsPrivate);
         }
 
-        // POToken experiment detected fallback to ios client (Webpage contains broken formats)
-        if !is_live(&player_response) {
-            let ios_ytconfig = self
+        // Manifest URLs of the watch page, other clients do not always expose them
+        let watch_manifest_urls = player_response
+            .streaming_data
+            .as_ref()
+            .map(|x| (x.dash_manifest_url.clone(), x.hls_manifest_url.clone()))
+            .unwrap_or_default();
+
+        let mut innertube_client = InnerTubeClient::Web;
+        let pinned_client = self.options.request_options.innertube_client;
+
+        // Explicitly chosen client is the only one used for the player request
+        // (formats of the watch page are enough when download URLs are not resolved)
+        if let Some(pinned_client) = pinned_client.filter(|_| resolve_urls) {
+            let ytconfig = self
                 .get_player_ytconfig(
                     &response,
-                    INNERTUBE_CLIENT.get("ios").cloned().unwrap_or_default(),
-                    self.options.request_options.po_token.as_ref()
+                    INNERTUBE_CLIENT
+                        .get(pinned_client.key())
+                        .cloned()
+                        .unwrap_or_default(),
+                    self.options.request_options.po_token.as_ref(),
                 )
                 .await?;
 
             let player_response_new =
-                serde_json::from_str::<PlayerResponse>(&ios_ytconfig).unwrap_or_default();
+                serde_json::from_str::<PlayerResponse>(&ytconfig).unwrap_or_default();
 
             player_response.streaming_data = player_response_new.streaming_data;
+            innertube_client = pinned_client;
+        }
+        // POToken experiment detected fallback to other clients (Webpage contains broken formats)
+        // Clients are tried in sequence until one of them returns playable streaming data
+        else if resolve_urls && !is_live(&player_response) {
+            for fallback_client in [
+                InnerTubeClient::Ios,
+                InnerTubeClient::Android,
+                InnerTubeClient::WebCreator,
+            ] {
+                let client_name = fallback_client.key();
+
+                let Ok(ytconfig) = self
+                    .get_player_ytconfig(
+                        &response,
+                        INNERTUBE_CLIENT
+                            .get(client_name)
+                            .cloned()
+                            .unwrap_or_default(),
+                        self.options.request_options.po_token.as_ref(),
+                    )
+                    .await
+                else {
+                    trace_debug!(client = client_name, "Player request failed");
+                    continue;
+                };
+
+                let player_response_new =
+                    serde_json::from_str::<PlayerResponse>(&ytconfig).unwrap_or_default();
+
+                if has_streaming_formats(&player_response_new) {
+                    player_response.streaming_data = player_response_new.streaming_data;
+                    innertube_client = fallback_client;
+                    break;
+                }
+
+                trace_debug!(client = client_name, "No playable streaming data");
+            }
         }
 
-        if is_age_restricted {
+        let mut age_restricted_bypass = false;
+
+        if resolve_urls && (is_age_restricted || is_login_required) && pinned_client.is_none() {
             let embed_ytconfig = self
                 .get_player_ytconfig(
                     &response,
@@ -219,68 +403,212 @@ impl<'opts> Video<'opts> {
             let player_response_new =
                 serde_json::from_str::<PlayerResponse>(&embed_ytconfig).unwrap_or_default();
 
+            if is_login_required
+                && !is_age_restricted
+                && !has_streaming_formats(&player_response_new)
+            {
+                return Err(VideoError::VideoIsPrivate);
+            }
+
+            trace_debug!("Age restriction bypassed with the tv_embedded client");
+            age_restricted_bypass = true;
             player_response.streaming_data = player_response_new.streaming_data;
             player_response.storyboards = player_response_new.storyboards;
+            innertube_client = InnerTubeClient::TvEmbedded;
         }
 
+        trace_debug!(
+            client = innertube_client.key(),
+            "Innertube client of the streaming data"
+        );
+
         if is_rental(&player_response) || is_not_yet_broadcasted(&player_response) {
             return Err(VideoError::VideoSourceNotFound);
         }
 
-        let video_details = clean_video_details(
+        let mut video_details = clean_video_details(
             &initial_response,
             &player_response,
             get_media(&initial_response).unwrap_or_default(),
             self.video_id.clone(),
         );
+        video_details.clip = self.clip.clone();
 
         let dash_manifest_url = player_response
             .streaming_data
             .as_ref()
-            .and_then(|x| x.dash_manifest_url.clone());
+            .and_then(|x| x.dash_manifest_url.clone())
+            .or(watch_manifest_urls.0);
 
         let hls_manifest_url = player_response
             .streaming_data
             .as_ref()
-            .and_then(|x| x.hls_manifest_url.clone());
+            .and_then(|x| x.hls_manifest_url.clone())
+            .or(watch_manifest_urls.1);
 
-        Ok(VideoInfo {
+        let (format_functions, diagnostics) =
+            if resolve_urls && !self.options.request_options.skip_url_decryption {
+                let html5player = get_html5player(response.as_str()).unwrap_or_default();
+
+                let functions = get_functions(
+                    html5player.as_str(),
+                    client,
+                    self.options.request_options.player_cache_path.as_deref(),
+                )
+                .await?;
+
+                let diagnostics = DecipherDiagnostics {
+                    player_url: get_player_url(&html5player),
+                    functions: functions.clone(),
+                };
+
+                (Some(functions), Some(diagnostics))
+            } else {
+                (None, None)
+            };
+
+        let mut formats =
+            parse_video_formats(&player_response, format_functions).unwrap_or_default();
+
+        // PO token also required on the videoplayback URLs
+        if let Some(po_token) = self.options.request_options.po_token.as_ref() {
+            for format in formats.iter_mut().filter(|x| !x.is_hls) {
+                if let Ok(mut url) = Url::parse(&format.url) {
+                    url.query_pairs_mut().append_pair("pot", po_token);
+                    format.url = url.to_string();
+                }
+            }
+        }
+
+        let info = VideoInfo {
             dash_manifest_url,
             hls_manifest_url,
-            formats: {
-                parse_video_formats(
-                    &player_response,
-                    get_functions(
-                        get_html5player(response.as_str()).unwrap_or_default(),
-                        client,
-                    )
-                    .await?,
-                )
-                .unwrap_or_default()
-            },
+            formats,
             related_videos: { get_related_videos(&initial_response).unwrap_or_default() },
+            related_videos_continuation: get_related_videos_continuation(&initial_response),
             video_details,
-        })
+            captions: get_captions(&player_response).unwrap_or_default(),
+            innertube_client: Some(innertube_client),
+            heatmap: get_heatmap(&initial_response).unwrap_or_default(),
+            age_restricted_bypass,
+        };
+
+        Ok((info, diagnostics))
     }
 
     /// Try to get full information about video
     /// - `HLS` and `DashMPD` formats included!
     #[cfg_attr(feature = "performance_analysis", flamer::flame)]
     pub async fn get_info(&self) -> Result<VideoInfo, VideoError> {
-        let mut info = self.get_basic_info().await?;
+        self.cached_info(&self.info_cache.full, self.fetch_info())
+            .await
+            .map(|(info, _)| info)
+    }
+
+    /// Return the cached info of the slot if it is younger than [`crate::RequestOptions::info_cache_ttl`]
+    /// and its format URLs are not about to expire, otherwise fetch and cache it
+    async fn cached_info(
+        &self,
+        slot: &RwLock<Option<(Instant, FetchedInfo)>>,
+        fetch: impl Future<Output = Result<FetchedInfo, VideoError>>,
+    ) -> Result<FetchedInfo, VideoError> {
+        let Some(ttl) = self.options.request_options.info_cache_ttl else {
+            return fetch.await;
+        };
+
+        if let Some((fetched_at, fetched)) = &*slot.read().await {
+            if fetched_at.elapsed() < ttl && !is_info_expiring(&fetched.0) {
+                return Ok(fetched.clone());
+            }
+        }
+
+        let fetched = fetch.await?;
+
+        *slot.write().await = Some((Instant::now(), fetched.clone()));
+
+        Ok(fetched)
+    }
+
+    async fn fetch_info(&self) -> Result<FetchedInfo, VideoError> {
+        let (mut info, diagnostics) = self
+            .cached_info(&self.info_cache.basic, self.fetch_basic_info(true))
+            .await?;
+
+        if let Some(url) = &info.dash_manifest_url {
+            if let Ok(body) = get_html(&self.client, url, None).await {
+                // Skip formats already exposed by streaming data
+                let dash_formats: Vec<VideoFormat> = parse_dash_video_formats(&body)
+                    .into_iter()
+                    .filter(|x| !info.formats.iter().any(|format| format.itag == x.itag))
+                    .collect();
+
+                info.formats.extend(dash_formats);
+            }
+        }
 
         if let Some(url) = &info.hls_manifest_url {
-            if let Ok(unformated_formats) = get_m3u8(url, &self.client).await {
-                info.formats
-                    .extend(parse_live_video_formats(unformated_formats));
+            if let Ok(variants) = get_m3u8(url, &self.client).await {
+                info.formats.extend(parse_live_video_formats(variants));
             }
         }
 
         // Last sort formats
         info.formats.sort_by(sort_formats);
-        Ok(info)
+        Ok((info, diagnostics))
+    }
+
+    /// Get the formats matching the [`VideoOptions`] of the video, ordered from the best candidate.
+    /// The first one is the format [`Video::stream`] downloads. Useful for format picker UIs
+    /// # Example
+    /// ```ignore
+    ///     let video = Video::new("https://www.youtube.com/watch?v=FZ8BxMU3BYc").unwrap();
+    ///
+    ///     for format in video.formats().await.unwrap() {
+    ///         println!("{} {:?}", format.itag, format.quality_label);
+    ///     }
+    /// ```
+    pub async fn formats(&self) -> Result<Vec<VideoFormat>, VideoError> {
+        let info = self.get_info().await?;
+
+        Ok(candidate_formats(&info.formats, &self.options))
+    }
+
+    /// Get the format by exact itag number, see [`VideoInfo::get_format_by_itag`]
+    /// # Example
+    /// ```ignore
+    ///     let video = Video::new("https://www.youtube.com/watch?v=FZ8BxMU3BYc").unwrap();
+    ///
+    ///     if let Some(format) = video.get_format_by_itag(140).await.unwrap() {
+    ///         println!("{}", format.url);
+    ///     }
+    /// ```
+    pub async fn get_format_by_itag(&self, itag: u64) -> Result<Option<VideoFormat>, VideoError> {
+        Ok(self.get_info().await?.get_format_by_itag(itag))
+    }
+
+    /// Get the DASH manifest URL of the video, which can be handed to external players (mpv, ffmpeg).
+    /// Usually only available for live streams and premieres
+    pub async fn get_dash_manifest_url(&self) -> Result<Option<String>, VideoError> {
+        Ok(self.get_basic_info().await?.dash_manifest_url)
     }
 
+    /// Get the HLS manifest URL of the video, which can be handed to external players (mpv, ffmpeg).
+    /// Usually only available for live streams
+    pub async fn get_hls_manifest_url(&self) -> Result<Option<String>, VideoError> {
+        Ok(self.get_basic_info().await?.hls_manifest_url)
+    }
+
+    /// Same as [`Video::get_info`] but also returns the decipher and n transform functions the formats are deciphered with.
+    /// Useful to investigate `403` errors on downloads
+    /// - Diagnostics are [`None`] if nothing is deciphered (e.g. [`crate::RequestOptions::skip_url_decryption`] is set)
+    pub async fn get_info_with_diagnostics(
+        &self,
+    ) -> Result<(VideoInfo, Option<DecipherDiagnostics>), VideoError> {
+        self.cached_info(&self.info_cache.full, self.fetch_info())
+            .await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
     /// Try to turn [`Stream`] implemented [`LiveStream`] or [`NonLiveStream`] depend on the video.
     /// If function successfully return can download video chunk by chunk
     /// # Example
@@ -296,13 +624,43 @@ impl<'opts> Video<'opts> {
     ///     }
     /// ```
     pub async fn stream(&self) -> Result<Box<dyn Stream + Send + Sync>, VideoError> {
-        let client = &self.client;
-
         let info = self.get_info().await?;
+
+        self.stream_from_info(&info).await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Turn already fetched [`VideoInfo`] (e.g. cached by the application) into [`Stream`] without fetching the info again.
+    /// The format is chosen with the options of the video. Format URLs of the info expire after about 6 hours
+    /// # Example
+    /// ```ignore
+    ///     let video = Video::new("https://www.youtube.com/watch?v=FZ8BxMU3BYc").unwrap();
+    ///     let info = video.get_info().await.unwrap();
+    ///
+    ///     // ...
+    ///
+    ///     let stream = video.stream_from_info(&info).await.unwrap();
+    /// ```
+    pub async fn stream_from_info(
+        &self,
+        info: &VideoInfo,
+    ) -> Result<Box<dyn Stream + Send + Sync>, VideoError> {
         let format = choose_format(&info.formats, &self.options)
             .map_err(|_op| VideoError::VideoSourceNotFound)?;
 
+        self.stream_from_format(format).await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Turn already chosen format into [`Stream`]
+    async fn stream_from_format(
+        &self,
+        format: VideoFormat,
+    ) -> Result<Box<dyn Stream + Send + Sync>, VideoError> {
+        let client = &self.client;
+
         let link = format.url;
+        let content_type = format.mime_type.mime.essence_str().to_string();
 
         if link.is_empty() {
             return Err(VideoError::VideoSourceNotFound);
@@ -325,13 +683,9 @@ impl<'opts> Video<'opts> {
             }
         }
 
-        let dl_chunk_size = self
-            .options
-            .download_options
-            .dl_chunk_size
-            .unwrap_or(DEFAULT_DL_CHUNK_SIZE);
+        let dl_chunk_size = self.options.download_options.chunk_size();
 
-        let start = 0;
+        let start = self.options.download_options.start_offset.unwrap_or(0);
         let end = start + dl_chunk_size;
 
         let mut content_length = format
@@ -353,6 +707,13 @@ impl<'opts> Video<'opts> {
             content_length = content_length_response;
         }
 
+        trace_info!(
+            video_id = %self.video_id,
+            itag = format.itag,
+            content_length,
+            "Starting download"
+        );
+
         let stream = NonLiveStream::new(NonLiveStreamOptions {
             client: Some(client.clone()),
             link,
@@ -360,6 +721,13 @@ impl<'opts> Video<'opts> {
             dl_chunk_size,
             start,
             end,
+            on_progress: self.options.download_options.on_progress.clone(),
+            refresh_link: self.refresh_link(format.itag),
+            parallel_connections: self.options.download_options.parallel_connections,
+            max_bytes_per_second: self.options.download_options.max_bytes_per_second,
+            cancellation: self.options.download_options.cancellation.clone(),
+            verify_length: self.options.download_options.verify_length,
+            content_type: Some(content_type),
             #[cfg(feature = "ffmpeg")]
             ffmpeg_args: None,
         })?;
@@ -380,6 +748,7 @@ impl<'opts> Video<'opts> {
     ///            format: Some("mp3".to_string()),
     ///            audio_filter: Some("aresample=48000,asetrate=48000*0.8".to_string()),
     ///            video_filter: Some("eq=brightness=150:saturation=2".to_string()),
+    ///            ..Default::default()
     ///        })).await.unwrap();
     ///
     ///     while let Some(chunk) = stream.chunk().await.unwrap() {
@@ -397,6 +766,7 @@ impl<'opts> Video<'opts> {
             .map_err(|_op| VideoError::VideoSourceNotFound)?;
 
         let link = format.url;
+        let content_type = format.mime_type.mime.essence_str().to_string();
 
         if link.is_empty() {
             return Err(VideoError::VideoSourceNotFound);
@@ -419,13 +789,9 @@ impl<'opts> Video<'opts> {
             }
         }
 
-        let dl_chunk_size = self
-            .options
-            .download_options
-            .dl_chunk_size
-            .unwrap_or(DEFAULT_DL_CHUNK_SIZE);
+        let dl_chunk_size = self.options.download_options.chunk_size();
 
-        let start = 0;
+        let start = self.options.download_options.start_offset.unwrap_or(0);
         let end = start + dl_chunk_size;
 
         let mut content_length = format
@@ -447,6 +813,13 @@ impl<'opts> Video<'opts> {
             content_length = content_length_response;
         }
 
+        trace_info!(
+            video_id = %self.video_id,
+            itag = format.itag,
+            content_length,
+            "Starting download"
+        );
+
         let stream = NonLiveStream::new(NonLiveStreamOptions {
             client: Some(client.clone()),
             link,
@@ -454,19 +827,187 @@ impl<'opts> Video<'opts> {
             dl_chunk_size,
             start,
             end,
+            on_progress: self.options.download_options.on_progress.clone(),
+            refresh_link: self.refresh_link(format.itag),
+            parallel_connections: self.options.download_options.parallel_connections,
+            max_bytes_per_second: self.options.download_options.max_bytes_per_second,
+            cancellation: self.options.download_options.cancellation.clone(),
+            verify_length: self.options.download_options.verify_length,
+            content_type: Some(content_type),
             ffmpeg_args,
         })?;
 
         Ok(Box::new(stream))
     }
 
-    /// Download video directly to the file
+    #[cfg(feature = "ffmpeg")]
+    /// Download the video only format chosen by the quality of [`VideoOptions`] and the highest audio only format
+    /// concurrently and mux them with FFmpeg into a single stream, since YouTube serves its high resolutions only as separate DASH formats.
+    ///
+    /// `ffmpeg` must be installed and in the `PATH`. Output is a fragmented `mp4` if both formats are `mp4`, `webm` if both are `webm`, otherwise `matroska`.
+    /// `timeout` kills FFmpeg if it runs longer than this from the start of the download, like [`FFmpegArgs::timeout`]
+    /// # Example
+    /// ```ignore
+    ///     let video_options = VideoOptions {
+    ///         quality: VideoQuality::Resolution(1080),
+    ///         ..Default::default()
+    ///     };
+    ///     let video = Video::new_with_options("https://www.youtube.com/watch?v=FZ8BxMU3BYc", video_options).unwrap();
+    ///
+    ///     let stream = video.stream_merged(Some(Duration::from_secs(600))).await.unwrap();
+    ///
+    ///     while let Some(chunk) = stream.chunk().await.unwrap() {
+    ///           println!("{:#?}", chunk);
+    ///     }
+    /// ```
+    pub async fn stream_merged(
+        &self,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<Box<dyn Stream + Send + Sync>, VideoError> {
+        let info = self.get_info().await?;
+
+        let video_options = VideoOptions {
+            filter: VideoSearchOptions::Video,
+            ..self.options.as_ref().clone()
+        };
+        let audio_options = VideoOptions {
+            quality: VideoQuality::HighestAudio,
+            filter: VideoSearchOptions::Audio,
+            ..self.options.as_ref().clone()
+        };
+
+        let video_format = choose_format(&info.formats, &video_options)
+            .map_err(|_op| VideoError::VideoSourceNotFound)?;
+        let audio_format = choose_format(&info.formats, &audio_options)
+            .map_err(|_op| VideoError::VideoSourceNotFound)?;
+
+        if video_format.url.is_empty() || audio_format.url.is_empty() {
+            return Err(VideoError::VideoSourceNotFound);
+        }
+
+        let format = match (
+            video_format.mime_type.container.as_str(),
+            audio_format.mime_type.container.as_str(),
+        ) {
+            ("mp4", "mp4") => "mp4",
+            ("webm", "webm") => "webm",
+            _ => "matroska",
+        };
+
+        let mut ffmpeg_args = [
+            "-i",
+            "-",
+            "-analyzeduration",
+            "0",
+            "-loglevel",
+            "error",
+            "-map",
+            "0:v:0",
+            "-map",
+            "1:a:0",
+            "-c",
+            "copy",
+        ]
+        .map(String::from)
+        .to_vec();
+
+        // mp4 can not be written to a pipe without fragments
+        if format == "mp4" {
+            ffmpeg_args.extend(["-movflags", "frag_keyframe+empty_moov"].map(String::from));
+        }
+
+        ffmpeg_args.extend(["-f", format, "pipe:1"].map(String::from));
+
+        let video_content_length = self.format_content_length(&video_format).await?;
+        let audio_content_length = self.format_content_length(&audio_format).await?;
+
+        trace_info!(
+            video_id = %self.video_id,
+            video_itag = video_format.itag,
+            audio_itag = audio_format.itag,
+            "Starting merged download"
+        );
+
+        let dl_chunk_size = self.options.download_options.chunk_size();
+
+        let stream = MergedStream::new(FFmpegStreamOptions {
+            client: self.client.clone(),
+            link: video_format.url,
+            content_length: video_content_length,
+            dl_chunk_size,
+            start: 0,
+            end: dl_chunk_size,
+            ffmpeg_args,
+            timeout,
+            audio: Some(FFmpegAudioInput {
+                link: audio_format.url,
+                content_length: audio_content_length,
+            }),
+        })?;
+
+        Ok(Box::new(stream))
+    }
+
+    #[cfg(feature = "ffmpeg")]
+    /// Content length of the format, requested from its url if it is not known
+    async fn format_content_length(&self, format: &VideoFormat) -> Result<u64, VideoError> {
+        let content_length = format
+            .content_length
+            .as_deref()
+            .unwrap_or("0")
+            .parse::<u64>()
+            .unwrap_or(0);
+
+        if content_length != 0 {
+            return Ok(content_length);
+        }
+
+        self.client
+            .get(&format.url)
+            .send()
+            .await
+            .map_err(VideoError::ReqwestMiddleware)?
+            .content_length()
+            .ok_or(VideoError::VideoNotFound)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Download video directly to the file.
+    /// If the path has no extension, it is inferred from the chosen format's container (e.g. `mp4`, `webm`).
+    /// With [`crate::DownloadOptions::start_offset`] the existing file is resumed, the bytes before the offset are kept
     pub async fn download<P: AsRef<Path>>(&self, path: P) -> Result<(), VideoError> {
-        use std::{fs::File, io::Write};
+        use std::{
+            fs::{File, OpenOptions},
+            io::{Seek, SeekFrom, Write},
+        };
+
+        let info = self.get_info().await?;
+        let format = choose_format(&info.formats, &self.options)
+            .map_err(|_op| VideoError::VideoSourceNotFound)?;
 
-        let stream = self.stream().await?;
+        let mut path = path.as_ref().to_path_buf();
+        if path.extension().is_none() && !format.mime_type.container.is_empty() {
+            path.set_extension(&format.mime_type.container);
+        }
 
-        let mut file = File::create(path).map_err(|e| VideoError::DownloadError(e.to_string()))?;
+        let stream = self.stream_from_format(format).await?;
+
+        let mut file = match self.options.download_options.start_offset {
+            // Resumed download keeps the bytes before the offset and writes the rest after them
+            Some(start_offset) => {
+                let mut file = OpenOptions::new()
+                    .write(true)
+                    .create(true)
+                    .truncate(false)
+                    .open(path)
+                    .map_err(|e| VideoError::DownloadError(e.to_string()))?;
+                file.set_len(start_offset)
+                    .and_then(|_| file.seek(SeekFrom::Start(start_offset)))
+                    .map_err(|e| VideoError::DownloadError(e.to_string()))?;
+                file
+            }
+            None => File::create(path).map_err(|e| VideoError::DownloadError(e.to_string()))?,
+        };
 
         while let Some(chunk) = stream.chunk().await? {
             file.write_all(&chunk)
@@ -476,6 +1017,47 @@ impl<'opts> Video<'opts> {
         Ok(())
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Download the best Opus audio format and remux it to Ogg Opus (`.opus`) without FFmpeg.
+    /// If the path has no extension, `opus` is used.
+    ///
+    /// Whole audio is buffered in memory before remuxing, see [`webm_opus_to_ogg`] for the limitations
+    /// # Example
+    /// ```ignore
+    ///     let video = Video::new("https://www.youtube.com/watch?v=FZ8BxMU3BYc").unwrap();
+    ///
+    ///     video.download_opus("audio").await.unwrap();
+    /// ```
+    pub async fn download_opus<P: AsRef<Path>>(&self, path: P) -> Result<(), VideoError> {
+        let info = self.get_info().await?;
+        let format = info
+            .formats
+            .iter()
+            .filter(|x| {
+                !x.has_video
+                    && x.has_audio
+                    && x.mime_type.container == "webm"
+                    && x.mime_type.audio_codec.as_deref() == Some("opus")
+            })
+            .max_by_key(|x| x.bitrate)
+            .cloned()
+            .ok_or(VideoError::FormatNotFound)?;
+
+        let mut path = path.as_ref().to_path_buf();
+        if path.extension().is_none() {
+            path.set_extension("opus");
+        }
+
+        let webm = self
+            .stream_from_format(format)
+            .await?
+            .download_to_end()
+            .await?;
+        let ogg = webm_opus_to_ogg(&webm)?;
+
+        std::fs::write(path, ogg).map_err(|e| VideoError::DownloadError(e.to_string()))
+    }
+
     #[cfg(feature = "ffmpeg")]
     /// Download video with ffmpeg args directly to the file
     pub async fn download_with_ffmpeg<P: AsRef<Path>>(
@@ -497,6 +1079,268 @@ impl<'opts> Video<'opts> {
         Ok(())
     }
 
+    /// Download caption track of the video by language code in the given [`CaptionFormat`].
+    /// Manually created tracks are preferred over auto generated ones
+    /// # Example
+    /// ```ignore
+    ///     let video = Video::new("https://www.youtube.com/watch?v=FZ8BxMU3BYc").unwrap();
+    ///
+    ///     let caption = video.download_caption("en", CaptionFormat::Srt).await.unwrap();
+    /// ```
+    pub async fn download_caption(
+        &self,
+        lang: &str,
+        format: CaptionFormat,
+    ) -> Result<String, VideoError> {
+        let info = self.get_basic_info().await?;
+
+        let track = info
+            .captions
+            .iter()
+            .filter(|x| x.language_code == lang)
+            .min_by_key(|x| x.is_auto_generated)
+            .ok_or(VideoError::CaptionNotFound(lang.to_string()))?;
+
+        let fmt = match format {
+            CaptionFormat::Srv3 => "srv3",
+            CaptionFormat::Vtt | CaptionFormat::Srt => "vtt",
+        };
+
+        let mut url = Url::parse(&track.base_url)?;
+        let query: Vec<(String, String)> = url
+            .query_pairs()
+            .filter(|(key, _)| key != "fmt")
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+        url.query_pairs_mut()
+            .clear()
+            .extend_pairs(query)
+            .append_pair("fmt", fmt);
+
+        let body = get_html(&self.client, url.as_str(), None).await?;
+
+        Ok(match format {
+            CaptionFormat::Srt => vtt_to_srt(&body),
+            _ => body,
+        })
+    }
+
+    /// Download thumbnail of the video selected by [`ThumbnailOptions`]
+    /// # Example
+    /// ```ignore
+    ///     let video = Video::new("https://www.youtube.com/watch?v=FZ8BxMU3BYc").unwrap();
+    ///
+    ///     let thumbnail = video.download_thumbnail(ThumbnailOptions::Highest).await.unwrap();
+    /// ```
+    pub async fn download_thumbnail(&self, options: ThumbnailOptions) -> Result<Bytes, VideoError> {
+        let info = self.get_basic_info().await?;
+        let thumbnails = info.video_details.thumbnails.iter();
+
+        let thumbnail = match options {
+            ThumbnailOptions::Highest => thumbnails.max_by_key(|x| x.width * x.height),
+            ThumbnailOptions::Lowest => thumbnails.min_by_key(|x| x.width * x.height),
+            ThumbnailOptions::Width(width) => thumbnails.min_by_key(|x| x.width.abs_diff(width)),
+        }
+        .ok_or(VideoError::VideoSourceNotFound)?;
+
+        let bytes = self
+            .client
+            .get(&thumbnail.url)
+            .send()
+            .await
+            .map_err(VideoError::ReqwestMiddleware)?
+            .error_for_status()
+            .map_err(VideoError::Reqwest)?
+            .bytes()
+            .await
+            .map_err(VideoError::Reqwest)?;
+
+        Ok(bytes)
+    }
+
+    /// Fetch top level comments of the video according to the [`CommentOptions`]
+    /// # Example
+    /// ```ignore
+    ///     let video = Video::new("https://www.youtube.com/watch?v=FZ8BxMU3BYc").unwrap();
+    ///
+    ///     let comments = video
+    ///         .get_comments(CommentOptions {
+    ///             limit: 50,
+    ///             sort_by: CommentSortBy::Newest,
+    ///         })
+    ///         .await
+    ///         .unwrap();
+    /// ```
+    pub async fn get_comments(&self, options: CommentOptions) -> Result<Vec<Comment>, VideoError> {
+        let initial_response = self.get_initial_data().await?;
+
+        let mut comments: Vec<Comment> = vec![];
+        // Comments are disabled or section not found
+        let Some(token) = get_comments_continuation(&initial_response) else {
+            return Ok(comments);
+        };
+
+        // First page only contains the sort menu of the comments
+        let header = self.get_next_response(&token).await?;
+        let mut continuation =
+            get_comments_sort_continuation(&header, options.sort_by).or(get_comments(&header).1);
+
+        while let Some(token) = continuation.take() {
+            if comments.len() as u64 >= options.limit {
+                break;
+            }
+
+            let response = self.get_next_response(&token).await?;
+            let (fetched_comments, next_token) = get_comments(&response);
+
+            // if any not new data finish the job
+            if fetched_comments.is_empty() {
+                break;
+            }
+
+            let remaining = options.limit.saturating_sub(comments.len() as u64) as usize;
+            comments.extend(fetched_comments.into_iter().take(remaining));
+            continuation = next_token;
+        }
+
+        Ok(comments)
+    }
+
+    /// Get the transcript of the video from its transcript panel, which is separate from the caption files.
+    /// Transcript is in the language of the caption track by language code (e.g. `en`) if `lang` is given,
+    /// otherwise in the default language. Manually created tracks are preferred over auto generated ones
+    /// # Example
+    /// ```ignore
+    ///     let video = Video::new("https://www.youtube.com/watch?v=FZ8BxMU3BYc").unwrap();
+    ///
+    ///     for segment in video.get_transcript(Some("en")).await.unwrap() {
+    ///         println!("{} {}", segment.start_ms, segment.text);
+    ///     }
+    /// ```
+    pub async fn get_transcript(
+        &self,
+        lang: Option<&str>,
+    ) -> Result<Vec<TranscriptSegment>, VideoError> {
+        let initial_response = self.get_initial_data().await?;
+        let params =
+            get_transcript_params(&initial_response).ok_or(VideoError::TranscriptNotFound)?;
+
+        let mut response = self
+            .post_web_endpoint("get_transcript", "params", &params)
+            .await?;
+
+        if let Some(lang) = lang {
+            // Transcript menu lists the languages by the names of the caption tracks
+            let info = self.get_basic_info().await?;
+            let mut tracks = info
+                .captions
+                .iter()
+                .filter(|x| x.language_code == lang)
+                .collect::<Vec<_>>();
+            tracks.sort_by_key(|x| x.is_auto_generated);
+
+            let languages = get_transcript_languages(&response);
+            let (_, params, selected) = tracks
+                .iter()
+                .find_map(|track| languages.iter().find(|(title, _, _)| title == &track.name))
+                .ok_or(VideoError::CaptionNotFound(lang.to_string()))?;
+
+            if !selected {
+                response = self
+                    .post_web_endpoint("get_transcript", "params", params)
+                    .await?;
+            }
+        }
+
+        let segments = get_transcript_segments(&response);
+
+        if segments.is_empty() {
+            return Err(VideoError::TranscriptNotFound);
+        }
+
+        Ok(segments)
+    }
+
+    /// Check the availability of the video with a single player request, without fetching the watch page
+    /// or deciphering the formats. Much cheaper than [`Video::get_info`], useful to check many videos
+    /// # Example
+    /// ```ignore
+    ///     let video = Video::new("FZ8BxMU3BYc").unwrap();
+    ///
+    ///     if video.check_availability().await.unwrap() == Availability::Available {
+    ///         println!("Video is available");
+    ///     }
+    /// ```
+    pub async fn check_availability(&self) -> Result<Availability, VideoError> {
+        let response = self
+            .post_player_request(
+                INNERTUBE_CLIENT.get("web").cloned().unwrap_or_default(),
+                0,
+                self.options.request_options.visitor_data.clone(),
+                self.options.request_options.po_token.as_ref(),
+            )
+            .await?;
+
+        let player_response = serde_json::from_str::<PlayerResponse>(&response)
+            .map_err(|_x| VideoError::BodyCannotParsed)?;
+
+        Ok(get_availability(&player_response))
+    }
+
+    /// Fetch only the related videos of the watch page, without the player requests and deciphering
+    /// of [`Video::get_info`]. Next pages can not be fetched, use [`Video::next_related_videos`] for that
+    /// # Example
+    /// ```ignore
+    ///     let video = Video::new("FZ8BxMU3BYc").unwrap();
+    ///
+    ///     let related_videos = video.get_related_videos().await.unwrap();
+    /// ```
+    pub async fn get_related_videos(&self) -> Result<Vec<RelatedVideo>, VideoError> {
+        let initial_response = self.get_initial_data().await?;
+
+        Ok(get_related_videos(&initial_response).unwrap_or_default())
+    }
+
+    /// Fetch the next page of the related videos of the [`VideoInfo`] like [`crate::search::Playlist::next`].
+    /// Fetched videos are appended to [`VideoInfo::related_videos`] and returned,
+    /// empty if there are no more related videos
+    /// # Example
+    /// ```ignore
+    ///     let mut info = video.get_basic_info().await.unwrap();
+    ///
+    ///     while info.related_videos.len() < 100 {
+    ///         if video.next_related_videos(&mut info).await.unwrap().is_empty() {
+    ///             break;
+    ///         }
+    ///     }
+    /// ```
+    pub async fn next_related_videos(
+        &self,
+        info: &mut VideoInfo,
+    ) -> Result<Vec<RelatedVideo>, VideoError> {
+        let Some(token) = info.related_videos_continuation.take() else {
+            return Ok(vec![]);
+        };
+
+        let response = self.get_next_response(&token).await?;
+        let (related_videos, continuation) = get_related_videos_page(&response);
+
+        info.related_videos_continuation = continuation;
+        info.related_videos.extend(related_videos.clone());
+
+        Ok(related_videos)
+    }
+
+    #[cfg(feature = "sponsorblock")]
+    /// Get SponsorBlock skip segments of the video with the client of the video,
+    /// see [`crate::sponsorblock::get_skip_segments`]
+    pub async fn get_skip_segments(
+        &self,
+        categories: &[SegmentCategory],
+    ) -> Result<Vec<Segment>, VideoError> {
+        fetch_skip_segments(&self.client, &self.video_id, categories).await
+    }
+
     /// Get video URL
     pub fn get_video_url(&self) -> String {
         format!("{}{}", BASE_URL, &self.video_id)
@@ -507,6 +1351,41 @@ impl<'opts> Video<'opts> {
         self.video_id.clone()
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Link refresher for [`NonLiveStream`], resolves a fresh link of the same itag if [`crate::RequestOptions::refresh_on_403`] is enabled
+    pub(crate) fn refresh_link(&self, itag: u64) -> Option<RefreshLink> {
+        if !self.options.request_options.refresh_on_403 {
+            return None;
+        }
+
+        // Cached info would return the expired link again, the refresh always fetches a new one
+        let mut options = self.options.as_ref().clone();
+        options.request_options.info_cache_ttl = None;
+
+        let video = Arc::new(Video {
+            video_id: self.video_id.clone(),
+            options: Cow::Owned(options),
+            client: self.client.clone(),
+            info_cache: Arc::new(InfoCache::default()),
+            clip: self.clip.clone(),
+        });
+
+        Some(Arc::new(
+            move || -> Pin<Box<dyn Future<Output = Result<String, VideoError>> + Send>> {
+                let video = video.clone();
+
+                Box::pin(async move {
+                    let info = video.get_info().await?;
+
+                    info.get_format_by_itag(itag)
+                        .map(|x| x.url)
+                        .filter(|x| !x.is_empty())
+                        .ok_or(VideoError::VideoSourceNotFound)
+                })
+            },
+        ))
+    }
+
     // Necessary to blocking api
     #[allow(dead_code)]
     pub(crate) fn get_client(&self) -> &reqwest_middleware::ClientWithMiddleware {
@@ -519,19 +1398,87 @@ impl<'opts> Video<'opts> {
         &self.options
     }
 
+    /// Fetch the watch page and parse its `ytInitialData`
+    async fn get_initial_data(&self) -> Result<serde_json::Value, VideoError> {
+        let url_parsed = Url::parse_with_params(self.get_video_url().as_str(), &[("hl", "en")])
+            .map_err(VideoError::URLParseError)?;
+
+        fetch_initial_data(&self.client, url_parsed.as_str()).await
+    }
+
+    /// Request `next` endpoint with a continuation token (comments, watch next items)
+    async fn get_next_response(&self, continuation: &str) -> Result<serde_json::Value, VideoError> {
+        self.post_web_endpoint("next", "continuation", continuation)
+            .await
+    }
+
+    /// Request the innertube endpoint with the web client context and a single string field
+    async fn post_web_endpoint(
+        &self,
+        endpoint: &str,
+        key: &str,
+        value: &str,
+    ) -> Result<serde_json::Value, VideoError> {
+        let configs = INNERTUBE_CLIENT.get("web").cloned().unwrap_or_default();
+        let client = configs.2;
+
+        let query = serde_json::from_str::<serde_json::Value>(&format!(
+            r#"{{
+            {client}
+            "{key}": "{value}"
+        }}"#
+        ))
+        .unwrap_or_default();
+
+        let response = self
+            .client
+            .post(format!("https://www.youtube.com/youtubei/v1/{endpoint}"))
+            .json(&query)
+            .send()
+            .await
+            .map_err(VideoError::ReqwestMiddleware)?
+            .error_for_status()
+            .map_err(VideoError::Reqwest)?;
+
+        response
+            .json::<serde_json::Value>()
+            .await
+            .map_err(|_x| VideoError::BodyCannotParsed)
+    }
+
     #[cfg_attr(feature = "performance_analysis", flamer::flame)]
     async fn get_player_ytconfig(
         &self,
         html: &str,
-        configs: (&str, &str, &str),
+        configs: (&str, &str, &str, &str),
         po_token: Option<&String>,
     ) -> Result<String, VideoError> {
         use std::str::FromStr;
 
         let ytcfg = get_ytconfig(html)?;
+        let visitor_data = self
+            .options
+            .request_options
+            .visitor_data
+            .clone()
+            .or(ytcfg.visitor_data)
+            .or_else(|| get_visitor_data(html));
+
+        self.post_player_request(configs, ytcfg.sts.unwrap_or(0), visitor_data, po_token)
+            .await
+    }
+
+    /// Request the innertube `player` endpoint and return the raw player response
+    async fn post_player_request(
+        &self,
+        configs: (&str, &str, &str, &str),
+        sts: u64,
+        visitor_data: Option<String>,
+        po_token: Option<&String>,
+    ) -> Result<String, VideoError> {
+        use std::str::FromStr;
 
         let client = configs.2;
-        let sts = ytcfg.sts.unwrap_or(0);
         let video_id = self.get_video_id();
 
         let mut query = serde_json::from_str::<serde_json::Value>(&format!(
@@ -556,6 +1503,9 @@ impl<'opts> Video<'opts> {
                     json!({"poToken": po_token})
                 );
         }
+        if let Some(visitor_data) = visitor_data.as_ref() {
+            query["context"]["client"]["visitorData"] = json!(visitor_data);
+        }
 
         static CONFIGS: Lazy<(HeaderMap, &str)> = Lazy::new(|| {
             (HeaderMap::from_iter([
@@ -578,6 +1528,17 @@ impl<'opts> Video<'opts> {
             HeaderName::from_str("X-Youtube-Client-Name").unwrap(),
             HeaderValue::from_str(configs.1).unwrap(),
         );
+        // Player expects the user agent of the requesting client
+        headers.insert(
+            reqwest::header::USER_AGENT,
+            HeaderValue::from_str(configs.3).unwrap(),
+        );
+        if let Some(visitor_id) = visitor_data.and_then(|x| HeaderValue::from_str(&x).ok()) {
+            headers.insert(
+                HeaderName::from_str("X-Goog-Visitor-Id").unwrap(),
+                visitor_id,
+            );
+        }
 
         let response = self
             .client
@@ -599,10 +1560,35 @@ impl<'opts> Video<'opts> {
     }
 }
 
+/// Fetch the page (watch, clip) and parse its `ytInitialData`
+async fn fetch_initial_data(
+    client: &ClientWithMiddleware,
+    url: &str,
+) -> Result<serde_json::Value, VideoError> {
+    let response = get_html(client, url, None).await?;
+
+    let document = Html::parse_document(&response);
+    let scripts_selector = Selector::parse("script").unwrap();
+    let mut initial_response_string = document
+        .select(&scripts_selector)
+        .filter(|x| x.inner_html().contains("var ytInitialData ="))
+        .map(|x| x.inner_html().replace("var ytInitialData =", ""))
+        .next()
+        .unwrap_or(String::from(""));
+
+    // remove json object last element (;)
+    initial_response_string.pop();
+
+    Ok(
+        serde_json::from_str::<serde_json::Value>(initial_response_string.trim())
+            .unwrap_or_default(),
+    )
+}
+
 async fn get_m3u8(
     url: &str,
     client: &reqwest_middleware::ClientWithMiddleware,
-) -> Result<Vec<(String, String)>, VideoError> {
+) -> Result<Vec<HlsVariant>, VideoError> {
     let base_url = Url::parse(BASE_URL)?;
     let base_url_host = base_url.host_str();
 
@@ -620,6 +1606,34 @@ async fn get_m3u8(
     static ITAG_REGEX: Lazy<regex::Regex> =
         Lazy::new(|| regex::Regex::new(r"/itag/(\d+)/").unwrap());
 
+    // Prefer master playlist attributes (resolution, bandwidth etc.) if the body can be parsed
+    if let Ok((_, master_playlist)) = parse_master_playlist(body.as_bytes()) {
+        let variants: Vec<HlsVariant> = master_playlist
+            .variants
+            .into_iter()
+            .filter(|variant| !variant.is_i_frame)
+            .filter_map(|variant| {
+                let itag = ITAG_REGEX
+                    .captures(&variant.uri)?
+                    .get(1)?
+                    .as_str()
+                    .to_string();
+
+                Some(HlsVariant {
+                    itag,
+                    bandwidth: Some(variant.bandwidth),
+                    resolution: variant.resolution.map(|x| (x.width, x.height)),
+                    frame_rate: variant.frame_rate,
+                    url: variant.uri,
+                })
+            })
+            .collect();
+
+        if !variants.is_empty() {
+            return Ok(variants);
+        }
+    }
+
     let itag_and_url = body
         .split('\n')
         .filter(|x| HTTP_REGEX.is_match(x) && ITAG_REGEX.is_match(x));
@@ -627,9 +1641,30 @@ async fn get_m3u8(
     Ok(itag_and_url
         .filter_map(|line| {
             ITAG_REGEX.captures(line).and_then(|caps| {
-                caps.get(1)
-                    .map(|itag| (itag.as_str().to_string(), line.to_string()))
+                caps.get(1).map(|itag| HlsVariant {
+                    itag: itag.as_str().to_string(),
+                    url: line.to_string(),
+                    ..Default::default()
+                })
             })
         })
-        .collect::<Vec<(String, String)>>())
+        .collect::<Vec<HlsVariant>>())
+}
+
+/// Whether any format URL expires (unix timestamp in the `expire` query param) within [`INFO_CACHE_EXPIRE_MARGIN`]
+fn is_info_expiring(info: &VideoInfo) -> bool {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|x| x.as_secs())
+        .unwrap_or(0);
+
+    info.formats
+        .iter()
+        .filter_map(|x| Url::parse(&x.url).ok())
+        .filter_map(|url| {
+            url.query_pairs()
+                .find(|(key, _)| key == "expire")
+                .and_then(|(_, value)| value.parse::<u64>().ok())
+        })
+        .any(|expire| expire <= now + INFO_CACHE_EXPIRE_MARGIN.as_secs())
 }
@@ -5,7 +5,10 @@ use serde_json::{from_str, json, map::Map, Value};
 
 use crate::{
     constants::BASE_URL,
-    structs::{Author, Chapter, PlayerResponse, RelatedVideo, StoryBoard, Thumbnail},
+    structs::{
+        Author, CaptionTrack, Chapter, Comment, CommentSortBy, HeatMarker, PlayerResponse,
+        RelatedVideo, StoryBoard, Thumbnail, TranscriptSegment,
+    },
     utils::{get_text, is_verified, parse_abbreviated_number, time_to_ms},
 };
 
@@ -39,15 +42,60 @@ pub fn get_related_videos(info: &Value) -> Option<Vec<RelatedVideo>> {
         secondary_results = vec![];
     }
 
+    Some(parse_related_results(&secondary_results, &rvs_params))
+}
+
+/// Get continuation token of the related videos from the watch page initial data
+pub fn get_related_videos_continuation(info: &Value) -> Option<String> {
+    info["contents"]["twoColumnWatchNextResults"]["secondaryResults"]["secondaryResults"]["results"]
+        .as_array()
+        .and_then(|results| get_results_continuation(results.as_slice()))
+}
+
+/// Parse related videos and the continuation token of the next page from `next` endpoint response
+pub fn get_related_videos_page(response: &Value) -> (Vec<RelatedVideo>, Option<String>) {
+    let items = response["onResponseReceivedEndpoints"]
+        .as_array()
+        .map(|endpoints| {
+            endpoints
+                .iter()
+                .flat_map(|x| {
+                    x["appendContinuationItemsAction"]["continuationItems"]
+                        .as_array()
+                        .cloned()
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<Value>>()
+        })
+        .unwrap_or_default();
+
+    (
+        parse_related_results(&items, &[]),
+        get_results_continuation(&items),
+    )
+}
+
+fn get_results_continuation(results: &[Value]) -> Option<String> {
+    results
+        .iter()
+        .rev()
+        .find_map(|x| {
+            x["continuationItemRenderer"]["continuationEndpoint"]["continuationCommand"]["token"]
+                .as_str()
+        })
+        .map(|x| x.to_string())
+}
+
+fn parse_related_results(results: &[Value], rvs_params: &[&str]) -> Vec<RelatedVideo> {
     let mut videos: Vec<RelatedVideo> = vec![];
-    for result in secondary_results {
+    for result in results {
         let details = result.as_object().and_then(|x| {
             x.get("compactVideoRenderer")
                 .map(|c| c.as_object().cloned().unwrap_or_default())
         });
 
         if let Some(details) = details {
-            if let Some(video_some) = parse_related_video(&details, &rvs_params) {
+            if let Some(video_some) = parse_related_video(&details, rvs_params) {
                 videos.push(video_some)
             }
         } else if let Some(autoplay) = result.as_object().and_then(|x| {
@@ -65,7 +113,7 @@ pub fn get_related_videos(info: &Value) -> Option<Vec<RelatedVideo>> {
                         .map(|x| x.as_object().cloned().unwrap_or_default());
 
                     if let Some(content_details) = content_details {
-                        let video = parse_related_video(&content_details, &rvs_params);
+                        let video = parse_related_video(&content_details, rvs_params);
                         if let Some(video_some) = video {
                             videos.push(video_some)
                         }
@@ -75,7 +123,7 @@ pub fn get_related_videos(info: &Value) -> Option<Vec<RelatedVideo>> {
         }
     }
 
-    Some(videos)
+    videos
 }
 
 pub fn parse_related_video(
@@ -500,6 +548,71 @@ pub fn get_media(info: &Value) -> Option<Value> {
     json_result
 }
 
+/// Category of the video from the metadata rows of the watch page, used if the microformat category is empty
+pub fn get_category(info: &Value) -> Option<String> {
+    let results = info["contents"]["twoColumnWatchNextResults"]["results"]["results"]["contents"]
+        .as_array()?;
+
+    let secondary_info = results
+        .iter()
+        .find(|x| !x["videoSecondaryInfoRenderer"].is_null())?;
+
+    let rows = secondary_info["videoSecondaryInfoRenderer"]["metadataRowContainer"]
+        ["metadataRowContainerRenderer"]["rows"]
+        .as_array()?;
+
+    let category_row = rows.iter().find_map(|row| {
+        let row = row.get("metadataRowRenderer")?;
+
+        if get_text(&row["title"]).as_str() != Some("Category") {
+            return None;
+        }
+
+        get_text(&row["contents"][0])
+            .as_str()
+            .map(|x| x.to_string())
+    });
+
+    // Games have a topic row like `Gaming` instead of the category row
+    let topic_row = || {
+        rows.iter().find_map(|row| {
+            row["richMetadataRowRenderer"]["contents"]
+                .as_array()?
+                .iter()
+                .find(|x| {
+                    x["richMetadataRenderer"]["style"].as_str()
+                        == Some("RICH_METADATA_RENDERER_STYLE_TOPIC")
+                })
+                .and_then(|x| get_text(&x["richMetadataRenderer"]["title"]).as_str())
+                .map(|x| x.to_string())
+        })
+    };
+
+    // Licensed songs have a music row
+    let music_row = || {
+        rows.iter()
+            .any(|row| get_text(&row["metadataRowRenderer"]["title"]).as_str() == Some("Song"))
+            .then(|| "Music".to_string())
+    };
+
+    category_row
+        .or_else(topic_row)
+        .or_else(music_row)
+        .filter(|x| !x.is_empty())
+}
+
+/// Tags of the video from the microformat of the watch page
+pub fn get_tags(info: &Value) -> Vec<String> {
+    info["microformat"]["microformatDataRenderer"]["tags"]
+        .as_array()
+        .map(|tags| {
+            tags.iter()
+                .filter_map(|x| x.as_str().map(|x| x.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 pub fn get_author(initial_response: &Value, player_response: &PlayerResponse) -> Option<Author> {
     let mut results: Vec<Value> = vec![];
 
@@ -799,7 +912,102 @@ pub fn get_storyboards(info: &PlayerResponse) -> Option<Vec<StoryBoard>> {
     }
 }
 
-pub fn get_chapters(info: &Value) -> Option<Vec<Chapter>> {
+pub fn get_captions(info: &PlayerResponse) -> Option<Vec<CaptionTrack>> {
+    let caption_tracks = info
+        .captions
+        .as_ref()
+        .and_then(|x| x.player_captions_tracklist_renderer.as_ref())
+        .and_then(|x| x.caption_tracks.as_ref())?;
+
+    let captions = caption_tracks
+        .iter()
+        .filter_map(|track| {
+            Some(CaptionTrack {
+                language_code: track.language_code.clone()?,
+                name: track
+                    .name
+                    .as_ref()
+                    .and_then(|x| get_text(x).as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                base_url: track.base_url.clone()?,
+                is_auto_generated: track.kind.as_deref() == Some("asr"),
+            })
+        })
+        .collect::<Vec<CaptionTrack>>();
+
+    Some(captions)
+}
+
+static DESCRIPTION_TIMESTAMP: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:^|\s|\()((?:\d{1,2}:)?\d{1,2}:\d{2})(?:$|\s|\))").unwrap());
+
+/// Chapters of the chapters renderer, or parsed from the timestamp lines of the description (e.g. `0:00 Intro`)
+/// if the video has no structured chapters
+pub fn get_chapters(info: &Value, player_response: &PlayerResponse) -> Option<Vec<Chapter>> {
+    let chapters = get_renderer_chapters(info).unwrap_or_default();
+
+    if !chapters.is_empty() {
+        return Some(chapters);
+    }
+
+    let video_details = player_response.video_details.as_ref();
+    let description = video_details
+        .and_then(|x| x.short_description.as_deref())
+        .unwrap_or("");
+    let length_seconds = video_details
+        .and_then(|x| x.length_seconds.as_ref())
+        .and_then(|x| x.parse::<i32>().ok())
+        .unwrap_or(0);
+
+    Some(get_description_chapters(description, length_seconds))
+}
+
+/// Parse chapters of the description lines which contain a timestamp. Like YouTube, the first chapter must start
+/// at `0:00` and there must be at least 3 chapters in ascending order, otherwise no chapters are returned
+fn get_description_chapters(description: &str, length_seconds: i32) -> Vec<Chapter> {
+    let mut chapters: Vec<Chapter> = vec![];
+
+    for line in description.lines() {
+        let Some(captures) = DESCRIPTION_TIMESTAMP.captures(line) else {
+            continue;
+        };
+        let timestamp = captures.get(1).unwrap();
+
+        let start_time = timestamp.as_str().split(':').fold(0, |total, part| {
+            total * 60 + part.parse::<i32>().unwrap_or(0)
+        });
+
+        // Timestamps beyond the video end are links to other parts
+        if length_seconds > 0 && start_time >= length_seconds {
+            continue;
+        }
+
+        if chapters.last().is_some_and(|x| x.start_time >= start_time) {
+            continue;
+        }
+
+        let separators: &[char] = &['-', '–', '—', ':', '|', '(', ')', '[', ']', '•'];
+        let title = format!(
+            "{} {}",
+            line[..timestamp.start()].trim().trim_matches(separators),
+            line[timestamp.end()..].trim().trim_matches(separators),
+        );
+
+        chapters.push(Chapter {
+            title: title.trim().to_string(),
+            start_time,
+        });
+    }
+
+    if chapters.len() < 3 || chapters[0].start_time != 0 {
+        return vec![];
+    }
+
+    chapters
+}
+
+fn get_renderer_chapters(info: &Value) -> Option<Vec<Chapter>> {
     let markers_map = info["playerOverlays"]["playerOverlayRenderer"]["decoratedPlayerBarRenderer"]
         ["decoratedPlayerBarRenderer"]["playerBar"]["multiMarkersPlayerBarRenderer"]["markersMap"]
         .as_array()
@@ -846,3 +1054,312 @@ pub fn get_chapters(info: &Value) -> Option<Vec<Chapter>> {
             .collect::<Vec<Chapter>>(),
     )
 }
+
+pub fn get_heatmap(info: &Value) -> Option<Vec<HeatMarker>> {
+    // Newer pages keep the markers in the framework entity mutations
+    let macro_markers = info["frameworkUpdates"]["entityBatchUpdate"]["mutations"]
+        .as_array()
+        .and_then(|x| {
+            x.iter()
+                .map(|c| &c["payload"]["macroMarkersListEntity"]["markersList"])
+                .find(|c| c["markerType"].as_str() == Some("MARKER_TYPE_HEATMAP"))
+        });
+
+    if let Some(markers_list) = macro_markers {
+        // Numbers are encoded as string or number depending on the page
+        let as_f64 = |value: &Value| {
+            value
+                .as_f64()
+                .or(value.as_str().and_then(|x| x.parse::<f64>().ok()))
+                .unwrap_or_default()
+        };
+
+        return Some(
+            markers_list["markers"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default()
+                .iter()
+                .map(|x| HeatMarker {
+                    start_millis: as_f64(&x["startMillis"]) as u64,
+                    duration_millis: as_f64(&x["durationMillis"]) as u64,
+                    intensity_score: as_f64(&x["intensityScoreNormalized"]),
+                })
+                .collect::<Vec<HeatMarker>>(),
+        );
+    }
+
+    let markers_map = info["playerOverlays"]["playerOverlayRenderer"]["decoratedPlayerBarRenderer"]
+        ["decoratedPlayerBarRenderer"]["playerBar"]["multiMarkersPlayerBarRenderer"]["markersMap"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+
+    let heat_markers = markers_map
+        .iter()
+        .find(|x| x["key"].as_str() == Some("HEATSEEKER"))
+        .and_then(|x| x["value"]["heatmap"]["heatmapRenderer"]["heatMarkers"].as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    Some(
+        heat_markers
+            .iter()
+            .map(|x| HeatMarker {
+                start_millis: x["heatMarkerRenderer"]["timeRangeStartMillis"]
+                    .as_u64()
+                    .unwrap_or_default(),
+                duration_millis: x["heatMarkerRenderer"]["markerDurationMillis"]
+                    .as_u64()
+                    .unwrap_or_default(),
+                intensity_score: x["heatMarkerRenderer"]["heatMarkerIntensityScoreNormalized"]
+                    .as_f64()
+                    .unwrap_or_default(),
+            })
+            .collect::<Vec<HeatMarker>>(),
+    )
+}
+
+/// Get continuation token of the comments section from the watch page initial data
+pub fn get_comments_continuation(info: &Value) -> Option<String> {
+    info["contents"]["twoColumnWatchNextResults"]["results"]["results"]["contents"]
+        .as_array()?
+        .iter()
+        .find(|x| {
+            x["itemSectionRenderer"]["sectionIdentifier"].as_str() == Some("comment-item-section")
+        })
+        .and_then(|x| {
+            x["itemSectionRenderer"]["contents"][0]["continuationItemRenderer"]
+                ["continuationEndpoint"]["continuationCommand"]["token"]
+                .as_str()
+        })
+        .map(|x| x.to_string())
+}
+
+/// Get continuation token of the requested sort order from the comments header
+pub fn get_comments_sort_continuation(response: &Value, sort_by: CommentSortBy) -> Option<String> {
+    let index = match sort_by {
+        CommentSortBy::Top => 0,
+        CommentSortBy::Newest => 1,
+    };
+
+    get_comments_items(response)
+        .iter()
+        .find(|x| !x["commentsHeaderRenderer"].is_null())
+        .and_then(|x| {
+            x["commentsHeaderRenderer"]["sortMenu"]["sortFilterSubMenuRenderer"]["subMenuItems"]
+                [index]["serviceEndpoint"]["continuationCommand"]["token"]
+                .as_str()
+        })
+        .map(|x| x.to_string())
+}
+
+/// Parse top level comments and the continuation token of the next page from `next` endpoint response
+pub fn get_comments(response: &Value) -> (Vec<Comment>, Option<String>) {
+    let items = get_comments_items(response);
+
+    // New layout keeps comment details in the entity mutations
+    let mutations = response["frameworkUpdates"]["entityBatchUpdate"]["mutations"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+
+    let comments = items
+        .iter()
+        .filter_map(|x| {
+            let thread = &x["commentThreadRenderer"];
+
+            if thread.is_null() {
+                return None;
+            }
+
+            let renderer = &thread["comment"]["commentRenderer"];
+
+            if !renderer.is_null() {
+                return Some(Comment {
+                    id: renderer["commentId"].as_str().unwrap_or("").to_string(),
+                    author: get_text(&renderer["authorText"])
+                        .as_str()
+                        .unwrap_or("")
+                        .to_string(),
+                    author_channel_id: renderer["authorEndpoint"]["browseEndpoint"]["browseId"]
+                        .as_str()
+                        .unwrap_or("")
+                        .to_string(),
+                    text: renderer["contentText"]["runs"]
+                        .as_array()
+                        .map(|x| {
+                            x.iter()
+                                .map(|c| c["text"].as_str().unwrap_or(""))
+                                .collect::<Vec<&str>>()
+                                .join("")
+                        })
+                        .unwrap_or_default(),
+                    published_time: get_text(&renderer["publishedTimeText"])
+                        .as_str()
+                        .unwrap_or("")
+                        .to_string(),
+                    like_count: parse_abbreviated_number(
+                        get_text(&renderer["voteCount"]).as_str().unwrap_or("0"),
+                    ) as u64,
+                    reply_count: renderer["replyCount"].as_u64().unwrap_or(0),
+                });
+            }
+
+            let comment_key = thread["commentViewModel"]["commentViewModel"]["commentKey"]
+                .as_str()
+                .unwrap_or("");
+
+            let payload = mutations
+                .iter()
+                .find(|x| x["entityKey"].as_str() == Some(comment_key))
+                .map(|x| &x["payload"]["commentEntityPayload"])?;
+
+            Some(Comment {
+                id: payload["properties"]["commentId"]
+                    .as_str()
+                    .unwrap_or("")
+                    .to_string(),
+                author: payload["author"]["displayName"]
+                    .as_str()
+                    .unwrap_or("")
+                    .to_string(),
+                author_channel_id: payload["author"]["channelId"]
+                    .as_str()
+                    .unwrap_or("")
+                    .to_string(),
+                text: payload["properties"]["content"]["content"]
+                    .as_str()
+                    .unwrap_or("")
+                    .to_string(),
+                published_time: payload["properties"]["publishedTime"]
+                    .as_str()
+                    .unwrap_or("")
+                    .to_string(),
+                like_count: parse_abbreviated_number(
+                    payload["toolbar"]["likeCountNotliked"]
+                        .as_str()
+                        .unwrap_or("0"),
+                ) as u64,
+                reply_count: parse_abbreviated_number(
+                    payload["toolbar"]["replyCount"].as_str().unwrap_or("0"),
+                ) as u64,
+            })
+        })
+        .collect::<Vec<Comment>>();
+
+    let continuation = items
+        .iter()
+        .find(|x| !x["continuationItemRenderer"].is_null())
+        .and_then(|x| {
+            let renderer = &x["continuationItemRenderer"];
+
+            renderer["continuationEndpoint"]["continuationCommand"]["token"]
+                .as_str()
+                // Newer pages wrap the continuation into a button
+                .or(
+                    renderer["button"]["buttonRenderer"]["command"]["continuationCommand"]["token"]
+                        .as_str(),
+                )
+        })
+        .map(|x| x.to_string());
+
+    (comments, continuation)
+}
+
+fn get_comments_items(response: &Value) -> Vec<Value> {
+    response["onResponseReceivedEndpoints"]
+        .as_array()
+        .map(|endpoints| {
+            endpoints
+                .iter()
+                .flat_map(|x| {
+                    x["reloadContinuationItemsCommand"]["continuationItems"]
+                        .as_array()
+                        .or(x["appendContinuationItemsAction"]["continuationItems"].as_array())
+                        .cloned()
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<Value>>()
+        })
+        .unwrap_or_default()
+}
+
+/// Get `get_transcript` endpoint params of the transcript panel from the watch page
+pub fn get_transcript_params(info: &Value) -> Option<String> {
+    info["engagementPanels"]
+        .as_array()?
+        .iter()
+        .map(|x| &x["engagementPanelSectionListRenderer"])
+        .find(|x| x["panelIdentifier"].as_str() == Some("engagement-panel-searchable-transcript"))
+        .and_then(|x| {
+            x["content"]["continuationItemRenderer"]["continuationEndpoint"]
+                ["getTranscriptEndpoint"]["params"]
+                .as_str()
+        })
+        .map(|x| x.to_string())
+}
+
+/// Parse transcript segments from `get_transcript` endpoint response, chapter headers are skipped
+pub fn get_transcript_segments(response: &Value) -> Vec<TranscriptSegment> {
+    get_transcript_panel(response)["body"]["transcriptSegmentListRenderer"]["initialSegments"]
+        .as_array()
+        .map(|segments| {
+            segments
+                .iter()
+                .map(|x| &x["transcriptSegmentRenderer"])
+                .filter(|x| !x.is_null())
+                .map(|x| {
+                    let time_ms = |key: &str| {
+                        x[key]
+                            .as_str()
+                            .and_then(|x| x.parse::<u64>().ok())
+                            .unwrap_or(0)
+                    };
+
+                    TranscriptSegment {
+                        start_ms: time_ms("startMs"),
+                        duration_ms: time_ms("endMs").saturating_sub(time_ms("startMs")),
+                        text: x["snippet"]["runs"]
+                            .as_array()
+                            .map(|x| {
+                                x.iter()
+                                    .map(|c| c["text"].as_str().unwrap_or(""))
+                                    .collect::<Vec<&str>>()
+                                    .join("")
+                            })
+                            .unwrap_or_default(),
+                    }
+                })
+                .collect::<Vec<TranscriptSegment>>()
+        })
+        .unwrap_or_default()
+}
+
+/// Get the languages of the transcript menu as `(title, params, selected)` from `get_transcript` endpoint response.
+/// Titles are the names of the caption tracks (e.g. `English (auto-generated)`)
+pub fn get_transcript_languages(response: &Value) -> Vec<(String, String, bool)> {
+    get_transcript_panel(response)["footer"]["transcriptFooterRenderer"]["languageMenu"]
+        ["sortFilterSubMenuRenderer"]["subMenuItems"]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|x| {
+                    Some((
+                        x["title"].as_str()?.to_string(),
+                        x["continuation"]["reloadContinuationData"]["continuation"]
+                            .as_str()?
+                            .to_string(),
+                        x["selected"].as_bool().unwrap_or(false),
+                    ))
+                })
+                .collect::<Vec<(String, String, bool)>>()
+        })
+        .unwrap_or_default()
+}
+
+fn get_transcript_panel(response: &Value) -> &Value {
+    &response["actions"][0]["updateEngagementPanelAction"]["content"]["transcriptRenderer"]
+        ["content"]["transcriptSearchPanelRenderer"]
+}
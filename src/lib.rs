@@ -9,12 +9,20 @@ pub extern crate flame;
 #[macro_use]
 extern crate flamer;
 
+#[cfg(all(target_arch = "wasm32", not(feature = "wasm")))]
+compile_error!("`wasm` feature must be enabled to build for `wasm32` targets");
+
+#[macro_use]
+mod trace;
+
 mod info;
 mod info_extras;
+mod remux;
 mod structs;
 mod utils;
 
 pub mod constants;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod stream;
 
 #[cfg(feature = "blocking")]
@@ -23,16 +31,25 @@ pub mod blocking;
 #[cfg(feature = "search")]
 pub mod search;
 
+#[cfg(feature = "sponsorblock")]
+pub mod sponsorblock;
+
 pub use info::Video;
+pub use remux::webm_opus_to_ogg;
 pub use structs::{
-    Author, Chapter, ColorInfo, DownloadOptions, Embed, MimeType, RangeObject, RelatedVideo,
-    RequestOptions, StoryBoard, Thumbnail, VideoDetails, VideoError, VideoFormat, VideoInfo,
-    VideoOptions, VideoQuality, VideoSearchOptions,
+    AudioTrack, Author, Availability, CancellationToken, CaptionFormat, CaptionTrack, Chapter,
+    Clip, ColorInfo, Comment, CommentOptions, CommentSortBy, DecipherDiagnostics, DownloadOptions,
+    Embed, HeatMarker, InnerTubeClient, MimeType, RangeObject, RelatedVideo, RequestOptions,
+    RequestOptionsBuilder, StoryBoard, StoryboardTile, Thumbnail, ThumbnailOptions,
+    TranscriptSegment, VideoDetails, VideoError, VideoFormat, VideoInfo, VideoOptions,
+    VideoOptionsBuilder, VideoQuality, VideoSearchOptions,
 };
 
 #[cfg(feature = "ffmpeg")]
 pub use structs::FFmpegArgs;
 
-pub use utils::{choose_format, get_random_v6_ip, get_video_id};
+pub use utils::{choose_format, get_clip_id, get_random_v4_ip, get_random_v6_ip, get_video_id};
 // export to access proxy feature
 pub use reqwest;
+// export to build a client for `Video::new_with_client`
+pub use reqwest_middleware;
@@ -0,0 +1,450 @@
+use crate::structs::VideoError;
+
+// Matroska / WebM element ids
+const EBML_HEADER_ID: u32 = 0x1A45_DFA3;
+const SEGMENT_ID: u32 = 0x1853_8067;
+const CLUSTER_ID: u32 = 0x1F43_B675;
+const SIMPLE_BLOCK_ID: u32 = 0xA3;
+const BLOCK_GROUP_ID: u32 = 0xA0;
+const BLOCK_ID: u32 = 0xA1;
+const TRACKS_ID: u32 = 0x1654_AE6B;
+const TRACK_ENTRY_ID: u32 = 0xAE;
+const TRACK_NUMBER_ID: u32 = 0xD7;
+const CODEC_ID_ID: u32 = 0x86;
+const CODEC_PRIVATE_ID: u32 = 0x63A2;
+const CODEC_DELAY_ID: u32 = 0x56AA;
+const AUDIO_ID: u32 = 0xE1;
+const CHANNELS_ID: u32 = 0x9F;
+
+/// Master elements which children are needed, every other element is skipped
+const MASTER_IDS: &[u32] = &[
+    SEGMENT_ID,
+    CLUSTER_ID,
+    BLOCK_GROUP_ID,
+    TRACKS_ID,
+    TRACK_ENTRY_ID,
+    AUDIO_ID,
+];
+
+/// Ogg page can contain at most 255 lacing values
+const MAX_PAGE_SEGMENTS: usize = 255;
+const OGG_SERIAL: u32 = 0x7275_7374;
+const OPUS_VENDOR: &str = "rusty_ytdl";
+
+#[derive(Default)]
+struct Track {
+    number: u64,
+    codec_id: String,
+    codec_private: Vec<u8>,
+    codec_delay: u64,
+    channels: u8,
+}
+
+/// Remux Opus audio in WebM container (itag `249`, `250`, `251`) to Ogg Opus (`.opus`) without FFmpeg.
+///
+/// Only the first Opus track is remuxed, end trimming of the last packet is not preserved
+/// and whole media has to be in memory.
+/// # Example
+/// ```ignore
+///     let stream = video.stream().await.unwrap();
+///     let webm = stream.download_to_end().await.unwrap();
+///
+///     std::fs::write("audio.opus", webm_opus_to_ogg(&webm).unwrap()).unwrap();
+/// ```
+pub fn webm_opus_to_ogg(webm: &[u8]) -> Result<Vec<u8>, VideoError> {
+    let mut reader = EbmlReader { data: webm, pos: 0 };
+
+    if reader.read_id() != Some(EBML_HEADER_ID) {
+        return Err(VideoError::RemuxError(
+            "Input is not a WebM file".to_string(),
+        ));
+    }
+    reader.pos = 0;
+
+    let mut tracks: Vec<Track> = vec![];
+    let mut packets: Vec<&[u8]> = vec![];
+
+    while reader.pos < webm.len() {
+        let (Some(id), Some(size)) = (reader.read_id(), reader.read_size()) else {
+            // Truncated element at the end
+            break;
+        };
+
+        if MASTER_IDS.contains(&id) {
+            if id == TRACK_ENTRY_ID {
+                tracks.push(Track::default());
+            }
+            // Children are read as the following elements
+            continue;
+        }
+
+        let end = size
+            .map(|size| reader.pos.saturating_add(size as usize).min(webm.len()))
+            .unwrap_or(webm.len());
+        let body = &webm[reader.pos..end];
+        reader.pos = end;
+
+        if id == SIMPLE_BLOCK_ID || id == BLOCK_ID {
+            let opus_track = tracks
+                .iter()
+                .find(|track| track.codec_id == "A_OPUS")
+                .map(|track| track.number);
+
+            if let Some(block_packets) = parse_block(body, opus_track) {
+                packets.extend(block_packets);
+            }
+            continue;
+        }
+
+        match (id, tracks.last_mut()) {
+            (TRACK_NUMBER_ID, Some(track)) => track.number = read_uint(body),
+            (CODEC_ID_ID, Some(track)) => {
+                track.codec_id = String::from_utf8_lossy(body)
+                    .trim_end_matches('\0')
+                    .to_string()
+            }
+            (CODEC_PRIVATE_ID, Some(track)) => track.codec_private = body.to_vec(),
+            (CODEC_DELAY_ID, Some(track)) => track.codec_delay = read_uint(body),
+            (CHANNELS_ID, Some(track)) => track.channels = read_uint(body) as u8,
+            _ => {}
+        }
+    }
+
+    let track = tracks
+        .iter()
+        .find(|track| track.codec_id == "A_OPUS")
+        .ok_or_else(|| VideoError::RemuxError("No Opus track found".to_string()))?;
+
+    if packets.is_empty() {
+        return Err(VideoError::RemuxError("No Opus packets found".to_string()));
+    }
+
+    let opus_head = if track.codec_private.starts_with(b"OpusHead") {
+        track.codec_private.clone()
+    } else {
+        build_opus_head(track)
+    };
+
+    let mut writer = OggWriter::default();
+
+    writer.write_page(&[&opus_head], 0, 0x02);
+    writer.write_page(&[&build_opus_tags()], 0, 0x00);
+
+    let mut granule_position: u64 = 0;
+    let mut page_packets: Vec<&[u8]> = vec![];
+    let mut page_segments = 0;
+
+    for packet in packets {
+        let segments = packet.len() / 255 + 1;
+
+        if page_segments + segments > MAX_PAGE_SEGMENTS && !page_packets.is_empty() {
+            writer.write_page(&page_packets, granule_position, 0x00);
+            page_packets.clear();
+            page_segments = 0;
+        }
+
+        granule_position += opus_packet_samples(packet);
+        page_packets.push(packet);
+        page_segments += segments;
+    }
+
+    // Last page marks the end of the stream
+    writer.write_page(&page_packets, granule_position, 0x04);
+
+    Ok(writer.data)
+}
+
+struct EbmlReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl EbmlReader<'_> {
+    /// Read element id, marker bits are kept
+    fn read_id(&mut self) -> Option<u32> {
+        let first = *self.data.get(self.pos)?;
+        let length = first.leading_zeros() as usize + 1;
+
+        if length > 4 {
+            return None;
+        }
+
+        let bytes = self.data.get(self.pos..self.pos + length)?;
+        self.pos += length;
+
+        Some(bytes.iter().fold(0, |id, byte| (id << 8) | *byte as u32))
+    }
+
+    /// Read element size, `None` inside of `Some` is an unknown size
+    fn read_size(&mut self) -> Option<Option<u64>> {
+        let (value, length) = read_vint(self.data.get(self.pos..)?)?;
+        self.pos += length;
+
+        // All value bits set means unknown size
+        Some(Some(value).filter(|value| *value != (1 << (7 * length)) - 1))
+    }
+}
+
+/// Read variable length integer with the length marker removed
+fn read_vint(data: &[u8]) -> Option<(u64, usize)> {
+    let first = *data.first()?;
+    let length = first.leading_zeros() as usize + 1;
+
+    if length > 8 {
+        return None;
+    }
+
+    let bytes = data.get(..length)?;
+    let value = bytes[1..]
+        .iter()
+        .fold((first as u64) & (0xFF >> length), |value, byte| {
+            (value << 8) | *byte as u64
+        });
+
+    Some((value, length))
+}
+
+fn read_uint(data: &[u8]) -> u64 {
+    data.iter()
+        .fold(0, |value, byte| (value << 8) | *byte as u64)
+}
+
+/// Split frames of the (Simple)Block of the track
+fn parse_block(body: &[u8], track_number: Option<u64>) -> Option<Vec<&[u8]>> {
+    let (number, length) = read_vint(body)?;
+
+    if Some(number) != track_number {
+        return None;
+    }
+
+    // Relative timecode (2 bytes) and flags
+    let flags = *body.get(length + 2)?;
+    let data = body.get(length + 3..)?;
+
+    match flags & 0x06 {
+        // No lacing
+        0x00 => Some(vec![data]),
+        lacing => {
+            let count = *data.first()? as usize + 1;
+            let mut pos = 1;
+            let mut sizes: Vec<usize> = vec![];
+
+            match lacing {
+                // Xiph lacing
+                0x02 => {
+                    for _ in 0..count - 1 {
+                        let mut size = 0;
+                        loop {
+                            let byte = *data.get(pos)?;
+                            pos += 1;
+                            size += byte as usize;
+                            if byte != 255 {
+                                break;
+                            }
+                        }
+                        sizes.push(size);
+                    }
+                }
+                // EBML lacing, sizes are differences of the previous size
+                0x06 => {
+                    let (first, first_length) = read_vint(data.get(pos..)?)?;
+                    pos += first_length;
+                    sizes.push(first as usize);
+
+                    for _ in 1..count - 1 {
+                        let (raw, raw_length) = read_vint(data.get(pos..)?)?;
+                        pos += raw_length;
+
+                        let bias = (1_i64 << (7 * raw_length - 1)) - 1;
+                        let size = *sizes.last()? as i64 + (raw as i64 - bias);
+                        sizes.push(usize::try_from(size).ok()?);
+                    }
+                }
+                // Fixed size lacing
+                _ => {
+                    let size = (data.len() - pos) / count;
+                    sizes = vec![size; count - 1];
+                }
+            }
+
+            let mut frames = vec![];
+            for size in sizes {
+                frames.push(data.get(pos..pos + size)?);
+                pos += size;
+            }
+            frames.push(data.get(pos..)?);
+
+            Some(frames)
+        }
+    }
+}
+
+/// Number of 48kHz samples of the Opus packet from its TOC byte (RFC 6716, section 3.1)
+fn opus_packet_samples(packet: &[u8]) -> u64 {
+    let Some(toc) = packet.first() else {
+        return 0;
+    };
+
+    let config = toc >> 3;
+    let frame_samples: u64 = match config {
+        0..=11 => [480, 960, 1920, 2880][(config % 4) as usize],
+        12..=15 => [480, 960][(config % 2) as usize],
+        _ => [120, 240, 480, 960][(config % 4) as usize],
+    };
+
+    let frames = match toc & 0x03 {
+        0 => 1,
+        1 | 2 => 2,
+        _ => packet.get(1).map(|x| (x & 0x3F) as u64).unwrap_or(0),
+    };
+
+    frame_samples * frames
+}
+
+/// Identification header for tracks without `OpusHead` codec private data (RFC 7845, section 5.1)
+fn build_opus_head(track: &Track) -> Vec<u8> {
+    // Codec delay is in nanoseconds
+    let pre_skip = (track.codec_delay * 48_000 / 1_000_000_000) as u16;
+
+    let mut head = b"OpusHead".to_vec();
+    head.push(1);
+    head.push(track.channels.max(1));
+    head.extend(pre_skip.to_le_bytes());
+    head.extend(48_000_u32.to_le_bytes());
+    head.extend(0_i16.to_le_bytes());
+    head.push(0);
+
+    head
+}
+
+/// Comment header with only the vendor string (RFC 7845, section 5.2)
+fn build_opus_tags() -> Vec<u8> {
+    let mut tags = b"OpusTags".to_vec();
+    tags.extend((OPUS_VENDOR.len() as u32).to_le_bytes());
+    tags.extend(OPUS_VENDOR.as_bytes());
+    tags.extend(0_u32.to_le_bytes());
+
+    tags
+}
+
+#[derive(Default)]
+struct OggWriter {
+    data: Vec<u8>,
+    sequence: u32,
+}
+
+impl OggWriter {
+    fn write_page(&mut self, packets: &[&[u8]], granule_position: u64, header_type: u8) {
+        let mut lacing_values: Vec<u8> = vec![];
+        for packet in packets {
+            lacing_values.resize(lacing_values.len() + packet.len() / 255, 255);
+            lacing_values.push((packet.len() % 255) as u8);
+        }
+
+        let start = self.data.len();
+
+        self.data.extend(b"OggS");
+        self.data.push(0);
+        self.data.push(header_type);
+        self.data.extend(granule_position.to_le_bytes());
+        self.data.extend(OGG_SERIAL.to_le_bytes());
+        self.data.extend(self.sequence.to_le_bytes());
+        // Checksum is calculated with zeroed field
+        self.data.extend([0; 4]);
+        self.data.push(lacing_values.len() as u8);
+        self.data.extend(lacing_values);
+        for packet in packets {
+            self.data.extend(*packet);
+        }
+
+        let checksum = ogg_crc(&self.data[start..]);
+        self.data[start + 22..start + 26].copy_from_slice(&checksum.to_le_bytes());
+
+        self.sequence += 1;
+    }
+}
+
+/// CRC-32 of Ogg pages (polynomial `0x04c11db7`, not reflected, zero initial value)
+fn ogg_crc(data: &[u8]) -> u32 {
+    data.iter().fold(0_u32, |crc, byte| {
+        (0..8).fold(crc ^ ((*byte as u32) << 24), |crc, _| {
+            if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ 0x04C1_1DB7
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(id: &[u8], body: &[u8]) -> Vec<u8> {
+        let mut element = id.to_vec();
+        // 8 bytes size
+        element.push(0x01);
+        element.extend(&(body.len() as u64).to_be_bytes()[1..]);
+        element.extend(body);
+
+        element
+    }
+
+    #[test]
+    fn test_webm_opus_to_ogg() {
+        let opus_head = build_opus_head(&Track {
+            channels: 2,
+            codec_delay: 6_500_000,
+            ..Default::default()
+        });
+
+        let track_entry = [
+            element(&[0xD7], &[1]),
+            element(&[0x86], b"A_OPUS"),
+            element(&[0x63, 0xA2], &opus_head),
+        ]
+        .concat();
+
+        // TOC 0xFC: CELT 20ms, single frame => 960 samples
+        let simple_block = |frame: u8| element(&[0xA3], &[0x81, 0x00, 0x00, 0x80, 0xFC, frame]);
+
+        let webm = [
+            element(&[0x1A, 0x45, 0xDF, 0xA3], &element(&[0x42, 0x82], b"webm")),
+            element(
+                &[0x18, 0x53, 0x80, 0x67],
+                &[
+                    element(&[0x16, 0x54, 0xAE, 0x6B], &element(&[0xAE], &track_entry)),
+                    element(
+                        &[0x1F, 0x43, 0xB6, 0x75],
+                        &[element(&[0xE7], &[0]), simple_block(1), simple_block(2)].concat(),
+                    ),
+                ]
+                .concat(),
+            ),
+        ]
+        .concat();
+
+        let ogg = webm_opus_to_ogg(&webm).unwrap();
+
+        let pages = ogg
+            .windows(4)
+            .enumerate()
+            .filter(|(_, x)| x == b"OggS")
+            .map(|(index, _)| index)
+            .collect::<Vec<usize>>();
+
+        assert_eq!(pages.len(), 3);
+        assert_eq!(&ogg[28..36], b"OpusHead");
+        assert_eq!(ogg[pages[0] + 5], 0x02);
+        assert_eq!(ogg[pages[2] + 5], 0x04);
+
+        let last_granule = u64::from_le_bytes(ogg[pages[2] + 6..pages[2] + 14].try_into().unwrap());
+        assert_eq!(last_granule, 1920);
+        assert_eq!(&ogg[ogg.len() - 4..], &[0xFC, 1, 0xFC, 2]);
+        println!("[PASSED] test_webm_opus_to_ogg");
+
+        assert!(webm_opus_to_ogg(b"not a webm").is_err());
+        println!("[PASSED] test_webm_opus_to_ogg_invalid_input");
+    }
+}
@@ -1,8 +1,9 @@
 mod youtube;
 
 pub use youtube::{
-    Channel, EmbedOptions, Playlist, PlaylistSearchOptions, RequestOptions, SearchOptions,
-    SearchResult, SearchType, Video, YouTube,
+    Channel, ChannelSearchOptions, ChannelVideos, DurationFilter, EmbedOptions, Playlist,
+    PlaylistSearchOptions, RequestOptions, SearchOptions, SearchPage, SearchResult, SearchType,
+    SortBy, SuggestionOptions, UploadDate, Video, YouTube,
 };
 
 #[derive(Debug, Clone)]
@@ -1,4 +1,7 @@
-use std::sync::{Arc, RwLock};
+use std::{
+    ops::ControlFlow,
+    sync::{Arc, RwLock},
+};
 
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -11,7 +14,10 @@ pub use crate::structs::RequestOptions;
 use crate::{
     constants::DEFAULT_HEADERS,
     structs::VideoError,
-    utils::{get_html, get_random_v6_ip, time_to_ms},
+    utils::{
+        get_html, get_scheduled_start_time, is_live_now, is_upcoming, parse_abbreviated_number,
+        parse_relative_time, parse_suggestions, time_to_ms,
+    },
     Thumbnail,
 };
 
@@ -25,6 +31,16 @@ static PLAYLIST_ID: Lazy<Regex> =
 static ALBUM_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(RDC|O)LAK5uy_[a-zA-Z0-9-_]{33}").unwrap());
 
+static MIX_ID: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:^|list=)(RD[a-zA-Z0-9-_]{11,})").unwrap());
+
+static CHANNEL_ID: Lazy<Regex> = Lazy::new(|| Regex::new(r"UC[a-zA-Z0-9-_]{22}").unwrap());
+
+static CHANNEL_HANDLE: Lazy<Regex> = Lazy::new(|| Regex::new(r"@[a-zA-Z0-9-_.]{3,30}").unwrap());
+
+static CHANNEL_CUSTOM_URL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"youtube\.com/(c|user)/[^/?#&]+").unwrap());
+
 #[derive(Clone, derive_more::Display, derivative::Derivative)]
 #[display("YouTube()")]
 #[derivative(Debug, PartialEq, Eq)]
@@ -38,11 +54,7 @@ pub struct YouTube {
 impl YouTube {
     /// Create new YouTube search struct with default [`RequestOptions`]
     pub fn new() -> Result<Self, VideoError> {
-        let client = reqwest::Client::builder()
-            .build()
-            .map_err(VideoError::Reqwest)?;
-
-        let client = reqwest_middleware::ClientBuilder::new(client).build();
+        let client = RequestOptions::default().build_client()?;
 
         Ok(Self {
             client,
@@ -52,35 +64,7 @@ impl YouTube {
 
     /// Create new YouTube search struct with custom [`RequestOptions`]
     pub fn new_with_options(request_options: &RequestOptions) -> Result<Self, VideoError> {
-        let client = if let Some(client) = request_options.client.as_ref() {
-            client.clone()
-        } else {
-            let mut client = reqwest::Client::builder();
-
-            // Assign request options to client
-
-            if let Some(proxy) = request_options.proxy.as_ref() {
-                client = client.proxy(proxy.clone());
-            }
-
-            if let Some(ipv6_block) = request_options.ipv6_block.as_ref() {
-                let ipv6 = get_random_v6_ip(ipv6_block)?;
-                client = client.local_address(ipv6);
-            }
-
-            if let Some(cookie) = request_options.cookies.as_ref() {
-                let host = "https://youtube.com".parse::<url::Url>().unwrap();
-
-                let jar = reqwest::cookie::Jar::default();
-                jar.add_cookie_str(cookie, &host);
-
-                client = client.cookie_provider(Arc::new(jar));
-            }
-
-            client.build().map_err(VideoError::Reqwest)?
-        };
-
-        let client = reqwest_middleware::ClientBuilder::new(client).build();
+        let client = request_options.build_client()?;
 
         Ok(Self {
             client,
@@ -89,6 +73,7 @@ impl YouTube {
     }
 
     /// Search with spesific `query`. If nothing found, its return empty [`Vec<SearchResult>`]
+    /// - Next pages are fetched until `SearchOptions.limit` is reached or no more results found
     /// # Example
     /// ```ignore
     ///     let youtube = YouTube::new().unwrap();
@@ -111,8 +96,63 @@ impl YouTube {
             &default_options
         };
 
+        let mut page = self.search_page(query, Some(options)).await?;
+        let mut results = std::mem::take(&mut page.results);
+
+        // limit 0 means no limit, only first page fetched
+        while options.limit > 0 && (results.len() as u64) < options.limit {
+            let Some(continuation) = page.continuation.take() else {
+                break;
+            };
+
+            page = self
+                .search_continued(
+                    continuation,
+                    Some(&SearchOptions {
+                        limit: options.limit - results.len() as u64,
+                        ..options.clone()
+                    }),
+                )
+                .await?;
+
+            if page.results.is_empty() {
+                break;
+            }
+
+            results.append(&mut page.results);
+        }
+
+        Ok(results)
+    }
+
+    /// Search with spesific `query` and return only the first page of results with the continuation token of the next page
+    /// # Example
+    /// ```ignore
+    /// let youtube = YouTube::new().unwrap();
+    ///
+    /// let page = youtube.search_page("i know your ways", None).await.unwrap();
+    ///
+    /// if let Some(continuation) = page.continuation {
+    ///     let next_page = youtube.search_continued(continuation, None).await;
+    ///     println!("{next_page:#?}");
+    /// }
+    /// ```
+    pub async fn search_page(
+        &self,
+        query: impl Into<String>,
+        search_options: Option<&SearchOptions>,
+    ) -> Result<SearchPage, VideoError> {
+        let default_options = SearchOptions::default();
+
+        // if SearchOptions is None get default
+        let options: &SearchOptions = if let Some(some_search_options) = search_options {
+            some_search_options
+        } else {
+            &default_options
+        };
+
         let query: String = query.into();
-        let filter = filter_string(&options.search_type);
+        let filter = filter_string(options);
         let query_regex = Regex::new(r"%20").unwrap();
 
         // First try with youtube backend
@@ -132,6 +172,7 @@ impl YouTube {
                     "https://youtube.com/results?search_query={encoded_query}{filter}",
                     encoded_query = query_regex.replace(&encode(query.trim()), "+")
                 ),
+                continuation: None,
             },
         )
         .await;
@@ -142,19 +183,19 @@ impl YouTube {
                 ["sectionListRenderer"]["contents"][0]["itemSectionRenderer"]["contents"]
                 .is_null()
         {
-            return Ok(format_search_result(
+            return Ok(format_search_page(
                 &self.client,
                 &res["contents"]["twoColumnSearchResultsRenderer"]["primaryContents"]
-                    ["sectionListRenderer"]["contents"][0]["itemSectionRenderer"]["contents"],
+                    ["sectionListRenderer"]["contents"],
                 options,
             ));
         }
 
         // get html body if backend return null
-        let filter = if options.search_type == SearchType::All {
+        let filter = if filter.is_empty() {
             "".to_string()
         } else {
-            format!("&sp={}", filter_string(&options.search_type))
+            format!("&sp={filter}")
         };
 
         let url = format!(
@@ -178,6 +219,47 @@ impl YouTube {
         Ok(parse_search_result(&self.client, body, options))
     }
 
+    /// Fetch the next page of a search with the continuation token of the previous [`SearchPage`]
+    pub async fn search_continued(
+        &self,
+        continuation: impl Into<String>,
+        search_options: Option<&SearchOptions>,
+    ) -> Result<SearchPage, VideoError> {
+        let default_options = SearchOptions::default();
+
+        // if SearchOptions is None get default
+        let options: &SearchOptions = if let Some(some_search_options) = search_options {
+            some_search_options
+        } else {
+            &default_options
+        };
+
+        let res = make_request(
+            &self.client,
+            self.innertube_key().await,
+            "/search",
+            options,
+            &RequestFuncOptions {
+                query: "".to_string(),
+                filter: None,
+                original_url: "https://youtube.com/results".to_string(),
+                continuation: Some(continuation.into()),
+            },
+        )
+        .await;
+
+        if res.is_null() {
+            return Err(VideoError::BodyCannotParsed);
+        }
+
+        Ok(format_search_page(
+            &self.client,
+            &res["onResponseReceivedCommands"][0]["appendContinuationItemsAction"]
+                ["continuationItems"],
+            options,
+        ))
+    }
+
     /// Classic search function but only get first [`SearchResult`] item. `SearchOptions.limit` not use in request its will be always `1`
     pub async fn search_one(
         &self,
@@ -215,6 +297,38 @@ impl YouTube {
         &self,
         query: impl Into<String>,
         language: Option<LanguageTags>,
+    ) -> Result<Vec<String>, VideoError> {
+        self.suggestion_with_options(
+            query,
+            &SuggestionOptions {
+                language: language.map(|x| x.to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// Fetch search suggestion with specific `query` in the language and region of [`SuggestionOptions`]
+    /// # Example
+    /// ```ignore
+    /// let youtube = YouTube::new().unwrap();
+    ///
+    /// let res = youtube
+    ///     .suggestion_with_options(
+    ///         "i know ",
+    ///         &SuggestionOptions {
+    ///             language: Some("de".to_string()),
+    ///             region: Some("DE".to_string()),
+    ///         },
+    ///     )
+    ///     .await;
+    ///
+    /// println!("{res:#?}");
+    /// ```
+    pub async fn suggestion_with_options(
+        &self,
+        query: impl Into<String>,
+        options: &SuggestionOptions,
     ) -> Result<Vec<String>, VideoError> {
         let query: String = query.into();
 
@@ -224,23 +338,42 @@ impl YouTube {
         )
         .map_err(VideoError::URLParseError)?;
 
-        if let Some(language) = language {
-            url.query_pairs_mut()
-                .append_pair("hl", &language.to_string());
+        if let Some(language) = options.language.as_ref() {
+            url.query_pairs_mut().append_pair("hl", language);
+        }
+
+        if let Some(region) = options.region.as_ref() {
+            url.query_pairs_mut().append_pair("gl", region);
         }
 
         let body = get_html(&self.client, url, None).await?;
 
-        let serde_value = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+        parse_suggestions(&body)
+    }
 
-        let suggestion = serde_value
-            .as_array()
-            .and_then(|x| x.get(1))
-            .and_then(|x| x.as_array())
-            .map(|x| x.iter().map(|x| x.to_string()).collect())
-            .unwrap_or_default();
+    /// Fetch channel details (subscriber count, description, banner and verification)
+    /// with channel id, handle (`@name`) or channel URL
+    /// # Example
+    /// ```ignore
+    /// let youtube = YouTube::new().unwrap();
+    ///
+    /// let channel = youtube.channel("@YouTube").await;
+    ///
+    /// println!("{channel:#?}");
+    /// ```
+    pub async fn channel(&self, url_or_id: impl Into<String>) -> Result<Channel, VideoError> {
+        let url_or_id: String = url_or_id.into();
+
+        let url = Channel::get_channel_url(&url_or_id)
+            .ok_or(VideoError::IsNotChannel(url_or_id.clone()))?;
+
+        let (initial_data, _) = Channel::get_channel_page(&self.client, &url, "about").await?;
+
+        let mut channel = Channel::from_initial_data(&initial_data, &url)
+            .ok_or(VideoError::ChannelBodyCannotParsed)?;
+        channel.client = Some(self.client.clone());
 
-        Ok(suggestion)
+        Ok(channel)
     }
 
     async fn innertube_key(&self) -> String {
@@ -292,13 +425,61 @@ pub enum SearchType {
     All,
 }
 
+#[derive(Clone, Default, derive_more::Display, derivative::Derivative)]
+#[derivative(Debug, PartialEq, Eq)]
+pub enum SortBy {
+    #[default]
+    #[display("Relevance")]
+    Relevance,
+    #[display("UploadDate")]
+    UploadDate,
+    #[display("ViewCount")]
+    ViewCount,
+    #[display("Rating")]
+    Rating,
+}
+
+#[derive(Clone, derive_more::Display, derivative::Derivative)]
+#[derivative(Debug, PartialEq, Eq)]
+pub enum DurationFilter {
+    /// Under 4 minutes
+    #[display("Short")]
+    Short,
+    /// Between 4 and 20 minutes
+    #[display("Medium")]
+    Medium,
+    /// Over 20 minutes
+    #[display("Long")]
+    Long,
+}
+
+#[derive(Clone, derive_more::Display, derivative::Derivative)]
+#[derivative(Debug, PartialEq, Eq)]
+pub enum UploadDate {
+    #[display("LastHour")]
+    LastHour,
+    #[display("Today")]
+    Today,
+    #[display("ThisWeek")]
+    ThisWeek,
+    #[display("ThisMonth")]
+    ThisMonth,
+    #[display("ThisYear")]
+    ThisYear,
+}
+
 #[derive(Clone, derive_more::Display, derivative::Derivative)]
-#[display("SearchOptions(type: {search_type}, limit: {limit}, safe search: {safe_search})")]
+#[display("SearchOptions(type: {search_type}, limit: {limit}, safe search: {safe_search}, sort by: {sort_by})")]
 #[derivative(Debug, PartialEq, Eq)]
 pub struct SearchOptions {
     pub limit: u64,
     pub search_type: SearchType,
     pub safe_search: bool,
+    pub sort_by: SortBy,
+    /// Filter videos by duration. [`None`] means any duration
+    pub duration: Option<DurationFilter>,
+    /// Filter results by upload date. [`None`] means any time
+    pub upload_date: Option<UploadDate>,
 }
 
 impl Default for SearchOptions {
@@ -307,14 +488,26 @@ impl Default for SearchOptions {
             limit: 100,
             search_type: SearchType::Video,
             safe_search: false,
+            sort_by: SortBy::Relevance,
+            duration: None,
+            upload_date: None,
         }
     }
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SuggestionOptions {
+    /// Language of the suggestions like `en` or `de`, sent as `hl`. [`None`] means the default language
+    pub language: Option<String>,
+    /// Region of the suggestions like `US` or `DE`, sent as `gl`. [`None`] means the default region
+    pub region: Option<String>,
+}
+
 struct RequestFuncOptions {
     query: String,
     filter: Option<String>,
     original_url: String,
+    continuation: Option<String>,
 }
 
 pub struct PlaylistSearchOptions {
@@ -334,6 +527,23 @@ impl Default for PlaylistSearchOptions {
     }
 }
 
+pub struct ChannelSearchOptions {
+    pub limit: u64,
+    pub request_options: Option<RequestOptions>,
+    /// Fetch all uploaded videos and avoid limit
+    pub fetch_all: bool,
+}
+
+impl Default for ChannelSearchOptions {
+    fn default() -> Self {
+        Self {
+            limit: 100,
+            request_options: None,
+            fetch_all: false,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SearchResult {
     Video(Video),
@@ -341,6 +551,14 @@ pub enum SearchResult {
     Channel(Channel),
 }
 
+/// One page of [`SearchResult`]s, see [`YouTube::search_page`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchPage {
+    pub results: Vec<SearchResult>,
+    /// Token to fetch the next page with [`YouTube::search_continued`]. [`None`] if this is the last page
+    pub continuation: Option<String>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Video {
@@ -354,9 +572,27 @@ pub struct Video {
     pub channel: Channel,
     pub uploaded_at: Option<String>,
     pub views: u64,
+    /// Video is streaming live now
+    #[serde(default)]
+    pub is_live: bool,
+    /// Video is an upcoming live stream or premiere
+    #[serde(default)]
+    pub is_upcoming: bool,
+    /// Scheduled start time of the upcoming video as unix timestamp in seconds
+    #[serde(default)]
+    pub scheduled_start_time: Option<u64>,
+    /// 1-based position of the video in the playlist, `None` outside playlists
+    #[serde(default)]
+    pub playlist_index: Option<u64>,
 }
 
 impl Video {
+    /// Approximate time since the video is uploaded, parsed from `uploaded_at` like `2 years ago`
+    /// - Months are counted as 30 days and years as 365 days
+    pub fn uploaded_at_relative(&self) -> Option<std::time::Duration> {
+        self.uploaded_at.as_deref().and_then(parse_relative_time)
+    }
+
     /// Get video embed url with [`EmbedOptions`]
     /// - if [`Video`] id is empty or corrupted, this function return [`None`]
     pub fn get_embed_html(&self, options: Option<&EmbedOptions>) -> Option<String> {
@@ -475,6 +711,7 @@ pub struct Playlist {
 
 impl Playlist {
     /// Try to get [`Playlist`] than fetch videos according to the [`PlaylistSearchOptions`]
+    /// - YouTube Mix (`RD...`) playlists are infinite, `fetch_all` is ignored and `limit` is always applied
     pub async fn get(
         url: impl Into<String>,
         options: Option<&PlaylistSearchOptions>,
@@ -492,74 +729,30 @@ impl Playlist {
             return Err(VideoError::IsNotPlaylist(url.clone()));
         }
 
-        let url_option = Self::get_playlist_url(&url);
+        // Mix ids like `RDCM...` or `RDEM...` also match the playlist id pattern, so mixes are checked first
+        let mix_id = Self::get_mix_id(&url);
+        let url_option = if mix_id.is_none() {
+            Self::get_playlist_url(&url)
+        } else {
+            None
+        };
 
-        if url_option.is_none() {
+        if url_option.is_none() && mix_id.is_none() {
             return Err(VideoError::IsNotPlaylist(url.clone()));
         }
 
-        let url = url_option.unwrap();
-
-        // Assign request options to client
-        let mut client = reqwest::Client::builder();
-
-        if options
-            .request_options
-            .as_ref()
-            .map(|x| x.proxy.is_some())
-            .unwrap_or(false)
-        {
-            let proxy = options
-                .request_options
-                .as_ref()
-                .unwrap()
-                .proxy
-                .as_ref()
-                .unwrap()
-                .clone();
-            client = client.proxy(proxy);
-        }
-
-        if options
-            .request_options
-            .as_ref()
-            .map(|x| x.ipv6_block.is_some())
-            .unwrap_or(false)
-        {
-            let ipv6 = options
-                .request_options
-                .as_ref()
-                .unwrap()
-                .ipv6_block
-                .as_ref()
-                .unwrap();
-            let ipv6 = get_random_v6_ip(ipv6)?;
-            client = client.local_address(ipv6);
-        }
-
-        if options
-            .request_options
-            .as_ref()
-            .map(|x| x.cookies.is_some())
-            .unwrap_or(false)
-        {
-            let cookie = options
-                .request_options
-                .as_ref()
-                .unwrap()
-                .cookies
-                .as_ref()
-                .unwrap();
-            let host = "https://youtube.com".parse::<url::Url>().unwrap();
-
-            let jar = reqwest::cookie::Jar::default();
-            jar.add_cookie_str(cookie.as_str(), &host);
+        let client = if let Some(request_options) = options.request_options.as_ref() {
+            YouTube::new_with_options(request_options)?.client
+        } else {
+            YouTube::new()?.client
+        };
 
-            client = client.cookie_provider(Arc::new(jar));
+        // Mixes are not browsable playlists, they are generated with watch next queue
+        if let Some(mix_id) = mix_id {
+            return Self::get_mix(client, mix_id, options.limit).await;
         }
 
-        let client = client.build().map_err(VideoError::Reqwest)?;
-        let client = reqwest_middleware::ClientBuilder::new(client).build();
+        let url = url_option.unwrap();
 
         let html_first = get_html(
             &client,
@@ -599,7 +792,14 @@ impl Playlist {
 
             // if contents found try to format values
             if !contents.is_null() && !playlist_primary_data.is_null() {
-                let videos = Self::get_playlist_videos(contents, Some(options.limit));
+                let mut continuation = Continuation {
+                    api: Some(get_api_key(&html_first)),
+                    token: Self::get_continuation_token(contents),
+                    client_version: Some(get_client_version(&html_first)),
+                    pending: vec![],
+                };
+                let videos =
+                    continuation.take_page(Self::get_playlist_videos(contents), options.limit);
 
                 let videos_length = videos.len();
                 let mut playlist = Playlist {
@@ -705,6 +905,8 @@ impl Playlist {
                         },
                         verified: false,
                         subscribers: 0,
+                        description: String::from(""),
+                        banner: vec![],
                     },
                     thumbnails: if playlist_primary_data["thumbnailRenderer"]
                         ["playlistVideoThumbnailRenderer"]["thumbnail"]["thumbnails"]
@@ -792,11 +994,7 @@ impl Playlist {
                     } else {
                         None
                     },
-                    continuation: Some(Continuation {
-                        api: Some(get_api_key(&html_first)),
-                        token: Self::get_continuation_token(contents),
-                        client_version: Some(get_client_version(&html_first)),
-                    }),
+                    continuation: Some(continuation),
                     client,
                 };
 
@@ -811,11 +1009,85 @@ impl Playlist {
 
                 return Ok(playlist);
             }
+
+            // Private, deleted or unviewable playlists have an error alert instead of the contents
+            if let Some(err) = get_playlist_alert_error(&serde_value, &url) {
+                return Err(err);
+            }
         }
 
         Err(VideoError::PlaylistBodyCannotParsed)
     }
 
+    /// Get the video at the `index` query param (1-based, first video if missing) of the playlist URL
+    /// (e.g. `watch?v=...&list=...&index=3`). Only the videos up to the index are fetched,
+    /// `limit` and `fetch_all` of the [`PlaylistSearchOptions`] are ignored
+    /// # Example
+    /// ```ignore
+    ///     let video = Playlist::get_video_at_index(
+    ///         "https://www.youtube.com/watch?v=FZ8BxMU3BYc&list=PLwMEL7UNT4o9iMzrvNBXZqXbNPFfT6rVD&index=3",
+    ///         None,
+    ///     )
+    ///     .await
+    ///     .unwrap();
+    ///
+    ///     let info = rusty_ytdl::Video::new(video.id).unwrap().get_info().await.unwrap();
+    /// ```
+    pub async fn get_video_at_index(
+        url: impl Into<String>,
+        options: Option<&PlaylistSearchOptions>,
+    ) -> Result<Video, VideoError> {
+        let url: String = url.into();
+        // `index=0` is treated as the first video
+        let index = get_index_param(&url).unwrap_or(1).max(1);
+
+        let mut playlist = Self::get(
+            &url,
+            Some(&PlaylistSearchOptions {
+                limit: index,
+                request_options: options.and_then(|x| x.request_options.clone()),
+                fetch_all: false,
+            }),
+        )
+        .await?;
+
+        let mut videos = std::mem::take(&mut playlist.videos);
+        loop {
+            if let ControlFlow::Break(video) = Self::find_video_at_index(videos, index) {
+                return video.ok_or(VideoError::VideoNotFound);
+            }
+
+            videos = playlist.next(Some(100)).await?;
+            if videos.is_empty() {
+                return Err(VideoError::VideoNotFound);
+            }
+        }
+    }
+
+    /// Find the video at the 1-based playlist `index` in the fetched videos.
+    /// Unavailable videos are skipped in the playlist, so the position in the list is not the index
+    /// - Breaks with [`None`] if the videos are already past the index, the video is not in the playlist
+    /// - Continues if the index is not fetched yet
+    fn find_video_at_index(videos: Vec<Video>, index: u64) -> ControlFlow<Option<Video>> {
+        let mut passed = false;
+
+        for video in videos {
+            match video.playlist_index {
+                Some(playlist_index) if playlist_index == index => {
+                    return ControlFlow::Break(Some(video))
+                }
+                Some(playlist_index) if playlist_index > index => passed = true,
+                _ => {}
+            }
+        }
+
+        if passed {
+            ControlFlow::Break(None)
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+
     /// Get next chunk of videos from playlist and return fetched [`Video`] array.
     /// - If limit is [`None`] it will be [`u64::MAX`]
     /// - If [`Playlist`] is coming from [`SearchResult`] this function always return empty [`Vec<Video>`]!
@@ -850,6 +1122,14 @@ impl Playlist {
     pub async fn next(&mut self, limit: Option<u64>) -> Result<Vec<Video>, VideoError> {
         let limit = limit.unwrap_or(u64::MAX);
 
+        // Videos of the last page beyond the previous limit come first
+        if let Some(continuation) = self.continuation.as_mut().filter(|x| !x.pending.is_empty()) {
+            let videos = continuation.take_pending(limit);
+            self.videos.extend(videos.clone());
+
+            return Ok(videos);
+        }
+
         if self.continuation.is_none()
             || self
                 .continuation
@@ -860,89 +1140,25 @@ impl Playlist {
             return Ok(vec![]);
         }
 
-        // request body
-        let continuation_token = self
-            .continuation
-            .as_ref()
-            .and_then(|x| x.token.clone())
-            .unwrap_or("".to_string());
-        let mut client_version = self
-            .continuation
-            .as_ref()
-            .and_then(|x| x.client_version.clone())
-            .unwrap_or("".to_string());
-
-        if client_version.is_empty() {
-            client_version = "".to_string();
-        } else {
-            client_version = format!(r#""clientVersion": "{client_version}""#);
-        }
-
-        let continuation_api = self
-            .continuation
-            .as_ref()
-            .and_then(|x| x.api.clone())
-            .unwrap_or("".to_string());
-
-        let format_str = format!(
-            r#"{{
-                "continuation": "{continuation_token}",
-                "context": {{
-                    "client": {{
-                        "utcOffsetMinutes": 0,
-                        "gl": "US",
-                        "hl": "en",
-                        "clientName": "WEB",
-                        {client_version}
-                    }},
-                    "user": {{}},
-                    "request": {{}}
-                }}
-            }}
-            "#
-        );
-
-        // Get json object with continuation token
-        let body: serde_json::Value = serde_json::from_str(&format_str).unwrap();
-
-        let res = self
-            .client
-            .post(format!(
-                "https://www.youtube.com/youtubei/v1/browse?key={continuation_api}"
-            ))
-            .json(&body)
-            .send()
-            .await;
-
-        if res.is_err() {
-            return Err(VideoError::ReqwestMiddleware(res.err().unwrap()));
-        }
-
-        let res = res.unwrap().json::<serde_json::Value>().await;
-
-        if res.is_err() {
-            return Err(VideoError::BodyCannotParsed);
-        }
-        let res = res.unwrap();
-
-        let contents = res["onResponseReceivedActions"][0]["appendContinuationItemsAction"]
-            ["continuationItems"]
-            .clone();
+        let contents =
+            fetch_continuation(&self.client, self.continuation.as_ref().unwrap()).await?;
 
         if contents.is_null() {
             return Ok(vec![]);
         }
 
-        let fetched_videos = Self::get_playlist_videos(&contents, Some(limit));
-
-        self.continuation = Some(Continuation {
+        let mut continuation = Continuation {
             token: Self::get_continuation_token(&contents),
             api: self.continuation.as_ref().and_then(|x| x.api.clone()),
             client_version: self
                 .continuation
                 .as_ref()
                 .and_then(|x| x.client_version.clone()),
-        });
+            pending: vec![],
+        };
+        let fetched_videos = continuation.take_page(Self::get_playlist_videos(&contents), limit);
+
+        self.continuation = Some(continuation);
 
         self.videos.extend(fetched_videos.clone());
 
@@ -982,29 +1198,17 @@ impl Playlist {
     /// ```
     pub async fn fetch(&mut self, limit: Option<u64>) -> &mut Self {
         let limit = limit.unwrap_or(u64::MAX);
-        // if continuation token not found return self without fetch videos
-        let if_and_while_situation = self.continuation.is_none()
-            || self
-                .continuation
-                .as_ref()
-                .and_then(|x| x.token.clone())
-                .is_none();
-
-        if if_and_while_situation {
-            return self;
-        }
-
-        while !(self.continuation.is_none()
-            || self
-                .continuation
-                .as_ref()
-                .and_then(|x| x.token.clone())
-                .is_none())
+        // if continuation token not found and no videos kept return self without fetch videos
+        while self
+            .continuation
+            .as_ref()
+            .map(|x| x.has_more())
+            .unwrap_or(false)
         {
             if self.videos.len() as u64 >= limit {
                 break;
             }
-            let chunk = self.next(Some(limit)).await;
+            let chunk = self.next(Some(limit - self.videos.len() as u64)).await;
 
             // if error encountered finish the job
             if chunk.is_err() {
@@ -1022,26 +1226,48 @@ impl Playlist {
 
     pub fn is_playlist(url_or_id: impl Into<String>) -> bool {
         let url_or_id: String = url_or_id.into();
+        let list_id = get_list_param(&url_or_id);
+        let list_id = list_id.as_deref().unwrap_or(&url_or_id);
 
-        if PLAYLIST_ID.is_match(&url_or_id) || ALBUM_REGEX.is_match(&url_or_id) {
+        if PLAYLIST_ID.is_match(list_id)
+            || ALBUM_REGEX.is_match(list_id)
+            || MIX_ID.is_match(&url_or_id)
+        {
             return true;
         }
 
         false
     }
 
+    /// Get id of the YouTube Mix (`RD...`) playlist. Albums are not counted as mix
+    pub fn get_mix_id(url_or_id: impl Into<String>) -> Option<String> {
+        let url_or_id: String = url_or_id.into();
+
+        if ALBUM_REGEX.is_match(&url_or_id) {
+            return None;
+        }
+
+        MIX_ID
+            .captures(&url_or_id)
+            .and_then(|x| x.get(1))
+            .map(|x| x.as_str().to_string())
+    }
+
     pub fn get_playlist_url(url_or_id: impl Into<String>) -> Option<String> {
         let url_or_id: String = url_or_id.into();
-        let matched_id = if PLAYLIST_ID.captures(&url_or_id).is_some() {
+        let list_id = get_list_param(&url_or_id);
+        let list_id = list_id.as_deref().unwrap_or(&url_or_id);
+
+        let matched_id = if PLAYLIST_ID.captures(list_id).is_some() {
             PLAYLIST_ID
-                .captures(&url_or_id)
+                .captures(list_id)
                 .unwrap()
                 .get(0)
                 .map(|x| x.as_str())
                 .unwrap_or("")
-        } else if ALBUM_REGEX.captures(&url_or_id).is_some() {
+        } else if ALBUM_REGEX.captures(list_id).is_some() {
             ALBUM_REGEX
-                .captures(&url_or_id)
+                .captures(list_id)
                 .unwrap()
                 .get(0)
                 .map(|x| x.as_str())
@@ -1065,9 +1291,123 @@ impl Playlist {
         ))
     }
 
-    fn get_playlist_videos(container: &serde_json::Value, limit: Option<u64>) -> Vec<Video> {
-        let limit = limit.unwrap_or(u64::MAX);
+    /// Fetch queued videos of the mix with `next` endpoint. Mixes are infinite so `limit` always applied
+    async fn get_mix(
+        client: reqwest_middleware::ClientWithMiddleware,
+        mix_id: String,
+        limit: u64,
+    ) -> Result<Self, VideoError> {
+        // Mix of a video contains its id after `RD` prefix
+        let mut video_id = if mix_id.len() == 13 {
+            Some(mix_id[2..].to_string())
+        } else {
+            None
+        };
+
+        let mut playlist: Option<Playlist> = None;
+
+        loop {
+            let video_id_str = video_id
+                .as_ref()
+                .map(|x| format!(r#""videoId": "{x}","#))
+                .unwrap_or("".to_string());
+
+            let format_str = format!(
+                r#"{{
+                    "playlistId": "{mix_id}",
+                    {video_id_str}
+                    "context": {{
+                        "client": {{
+                            "utcOffsetMinutes": 0,
+                            "gl": "US",
+                            "hl": "en",
+                            "clientName": "WEB",
+                            "clientVersion": "{DEFAULT_CLIENT_VERSOIN}"
+                        }},
+                        "user": {{}},
+                        "request": {{}}
+                    }}
+                }}
+                "#
+            );
+
+            let body: serde_json::Value = serde_json::from_str(&format_str).unwrap();
+
+            let res = client
+                .post(format!(
+                    "https://www.youtube.com/youtubei/v1/next?key={DEFAULT_INNERTUBE_KEY}"
+                ))
+                .json(&body)
+                .send()
+                .await
+                .map_err(VideoError::ReqwestMiddleware)?
+                .json::<serde_json::Value>()
+                .await
+                .map_err(|_| VideoError::BodyCannotParsed)?;
+
+            let mix = &res["contents"]["twoColumnWatchNextResults"]["playlist"]["playlist"];
+
+            if mix.is_null() {
+                break;
+            }
+
+            let playlist = playlist.get_or_insert_with(|| Playlist {
+                id: mix_id.clone(),
+                name: mix["title"].as_str().unwrap_or("").to_string(),
+                url: String::from(""),
+                channel: Channel {
+                    id: String::from(""),
+                    name: mix["ownerName"]["simpleText"]
+                        .as_str()
+                        .unwrap_or("")
+                        .to_string(),
+                    url: String::from(""),
+                    icon: vec![],
+                    verified: false,
+                    subscribers: 0,
+                    description: String::from(""),
+                    banner: vec![],
+                },
+                thumbnails: vec![],
+                views: 0,
+                videos: vec![],
+                last_update: None,
+                // mix has no continuation token
+                continuation: None,
+                client: client.clone(),
+            });
+
+            let fetched_videos = Self::get_mix_videos(&mix["contents"])
+                .into_iter()
+                .filter(|x| !playlist.videos.iter().any(|c| c.id == x.id))
+                .take(limit.saturating_sub(playlist.videos.len() as u64) as usize)
+                .collect::<Vec<Video>>();
+
+            // if any not new data finish the job
+            if fetched_videos.is_empty() {
+                break;
+            }
+
+            playlist.videos.extend(fetched_videos);
 
+            if playlist.videos.len() as u64 >= limit {
+                break;
+            }
+
+            video_id = playlist.videos.last().map(|x| x.id.clone());
+        }
+
+        let mut playlist = playlist.ok_or(VideoError::PlaylistBodyCannotParsed)?;
+
+        if let Some(first) = playlist.videos.first() {
+            playlist.url = format!("https://www.youtube.com/watch?v={}&list={mix_id}", first.id);
+            playlist.thumbnails = first.thumbnails.clone();
+        }
+
+        Ok(playlist)
+    }
+
+    fn get_mix_videos(container: &serde_json::Value) -> Vec<Video> {
         let mut videos: Vec<Video> = vec![];
 
         if !container.is_array() {
@@ -1075,11 +1415,93 @@ impl Playlist {
         }
 
         for info in container.as_array().unwrap() {
-            // If limit reached break the loop
-            if limit == videos.len() as u64 {
-                break;
+            let video = &info["playlistPanelVideoRenderer"];
+            // video not proper type skip it!
+            if video.is_null() || video["videoId"].is_null() {
+                continue;
             }
 
+            videos.push(Video {
+                id: video["videoId"].as_str().unwrap_or("").to_string(),
+                url: format!(
+                    "https://www.youtube.com/watch?v={}",
+                    video["videoId"].as_str().unwrap_or("")
+                ),
+                title: video["title"]["simpleText"]
+                    .as_str()
+                    .unwrap_or("")
+                    .to_string(),
+                description: "".to_string(),
+                duration: time_to_ms(&get_duration_raw(&video["lengthText"])) as u64,
+                duration_raw: get_duration_raw(&video["lengthText"]),
+                thumbnails: if video["thumbnail"]["thumbnails"].is_array() {
+                    video["thumbnail"]["thumbnails"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|x| Thumbnail {
+                            width: x.get("width").and_then(|x| x.as_u64()).unwrap_or(0),
+                            height: x.get("height").and_then(|x| x.as_u64()).unwrap_or(0),
+                            url: x
+                                .get("url")
+                                .and_then(|x| x.as_str())
+                                .unwrap_or("")
+                                .to_string(),
+                        })
+                        .collect::<Vec<Thumbnail>>()
+                } else {
+                    vec![]
+                },
+                channel: Channel {
+                    id: video["shortBylineText"]["runs"][0]["navigationEndpoint"]["browseEndpoint"]
+                        ["browseId"]
+                        .as_str()
+                        .unwrap_or("")
+                        .to_string(),
+                    name: video["shortBylineText"]["runs"][0]["text"]
+                        .as_str()
+                        .unwrap_or("")
+                        .to_string(),
+                    url: if video["shortBylineText"]["runs"][0]["navigationEndpoint"]
+                        ["browseEndpoint"]["canonicalBaseUrl"]
+                        .is_string()
+                    {
+                        format!(
+                            "https://www.youtube.com{}",
+                            video["shortBylineText"]["runs"][0]["navigationEndpoint"]
+                                ["browseEndpoint"]["canonicalBaseUrl"]
+                                .as_str()
+                                .unwrap_or("")
+                        )
+                    } else {
+                        String::from("")
+                    },
+                    icon: vec![],
+                    verified: false,
+                    subscribers: 0,
+                    description: String::from(""),
+                    banner: vec![],
+                },
+                uploaded_at: None,
+                views: 0,
+                is_live: is_live_now(video),
+                is_upcoming: is_upcoming(video),
+                scheduled_start_time: get_scheduled_start_time(video),
+                playlist_index: None,
+            });
+        }
+
+        videos
+    }
+
+    fn get_playlist_videos(container: &serde_json::Value) -> Vec<Video> {
+        let mut videos: Vec<Video> = vec![];
+
+        if !container.is_array() {
+            return vec![];
+        }
+
+        for info in container.as_array().unwrap() {
             let video = &info["playlistVideoRenderer"];
             // video not proper type skip it!
             if video.is_null() || video["shortBylineText"].is_null() {
@@ -1098,19 +1520,8 @@ impl Playlist {
                     .unwrap_or("")
                     .to_string(),
                 description: "".to_string(),
-                duration: if !video["lengthText"]["simpleText"].is_null() {
-                    time_to_ms(video["lengthText"]["simpleText"].as_str().unwrap_or("0:00")) as u64
-                } else {
-                    0
-                },
-                duration_raw: if !video["lengthText"]["simpleText"].is_null() {
-                    video["lengthText"]["simpleText"]
-                        .as_str()
-                        .unwrap_or("")
-                        .to_string()
-                } else {
-                    "0:00".to_string()
-                },
+                duration: time_to_ms(&get_duration_raw(&video["lengthText"])) as u64,
+                duration_raw: get_duration_raw(&video["lengthText"]),
                 thumbnails: if video["thumbnail"]["thumbnails"].is_array() {
                     video["thumbnail"]["thumbnails"]
                         .as_array()
@@ -1185,9 +1596,17 @@ impl Playlist {
                     icon: vec![],
                     verified: false,
                     subscribers: 0,
+                    description: String::from(""),
+                    banner: vec![],
                 },
                 uploaded_at: None,
                 views: 0,
+                is_live: is_live_now(video),
+                is_upcoming: is_upcoming(video),
+                scheduled_start_time: get_scheduled_start_time(video),
+                playlist_index: video["index"]["simpleText"]
+                    .as_str()
+                    .and_then(|x| x.parse::<u64>().ok()),
             });
         }
 
@@ -1226,6 +1645,33 @@ pub struct Continuation {
     api: Option<String>,
     token: Option<String>,
     client_version: Option<String>,
+    /// Videos of the last fetched page beyond the limit, returned first by the next call
+    pending: Vec<Video>,
+}
+
+impl Continuation {
+    /// Whether more videos can be returned, kept from the last page or from the next page
+    fn has_more(&self) -> bool {
+        self.token.is_some() || !self.pending.is_empty()
+    }
+
+    /// Take at most `limit` videos of the fetched page, the rest is kept for the next call
+    fn take_page(&mut self, mut videos: Vec<Video>, limit: u64) -> Vec<Video> {
+        if videos.len() as u64 > limit {
+            self.pending = videos.split_off(limit as usize);
+        }
+
+        videos
+    }
+
+    /// Take at most `limit` videos kept from the last page
+    fn take_pending(&mut self, limit: u64) -> Vec<Video> {
+        let rest = self
+            .pending
+            .split_off(self.pending.len().min(limit as usize));
+
+        std::mem::replace(&mut self.pending, rest)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -1237,16 +1683,536 @@ pub struct Channel {
     pub icon: Vec<Thumbnail>,
     pub verified: bool,
     pub subscribers: u64,
+    /// Only available when fetched with [`YouTube::channel`] or [`Channel::get`]
+    #[serde(default)]
+    pub description: String,
+    /// Only available when fetched with [`YouTube::channel`] or [`Channel::get`]
+    #[serde(default)]
+    pub banner: Vec<Thumbnail>,
+}
+
+/// Uploaded videos of a [`Channel`], returned by [`Channel::get`] and [`Channel::videos`]
+#[derive(Clone, derivative::Derivative, Serialize)]
+#[derivative(Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelVideos {
+    pub channel: Channel,
+    pub videos: Vec<Video>,
+
+    #[serde(skip_serializing)]
+    #[derivative(PartialEq = "ignore")]
+    continuation: Continuation,
+    #[serde(skip_serializing)]
+    #[derivative(PartialEq = "ignore")]
+    client: reqwest_middleware::ClientWithMiddleware,
+}
+
+impl Channel {
+    /// Try to get [`Channel`] than fetch uploaded videos according to the [`ChannelSearchOptions`]
+    /// - `url_or_id` can be channel id, handle (`@name`) or channel URL
+    pub async fn get(
+        url_or_id: impl Into<String>,
+        options: Option<&ChannelSearchOptions>,
+    ) -> Result<ChannelVideos, VideoError> {
+        let url_or_id: String = url_or_id.into();
+        let default_options = ChannelSearchOptions::default();
+        let options = if let Some(some_options) = options {
+            drop(default_options);
+            some_options
+        } else {
+            &default_options
+        };
+
+        let url =
+            Self::get_channel_url(&url_or_id).ok_or(VideoError::IsNotChannel(url_or_id.clone()))?;
+
+        let client = if let Some(request_options) = options.request_options.as_ref() {
+            YouTube::new_with_options(request_options)?.client
+        } else {
+            YouTube::new()?.client
+        };
+
+        let (initial_data, html) = Self::get_channel_page(&client, &url, "videos").await?;
+
+        let channel = Self::from_initial_data(&initial_data, &url)
+            .ok_or(VideoError::ChannelBodyCannotParsed)?;
+
+        Ok(ChannelVideos::new(channel, client, &initial_data, &html, options).await)
+    }
+
+    /// Fetch uploaded videos of the channel (e.g. coming from [`SearchResult`]) according to the [`ChannelSearchOptions`]
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let youtube = YouTube::new().unwrap();
+    ///
+    /// let res = youtube
+    ///    .search_one(
+    ///       "YouTube",
+    ///       Some(&SearchOptions {
+    ///           search_type: SearchType::Channel,
+    ///           ..Default::default()
+    ///       }),
+    /// )
+    /// .await;
+    ///
+    /// if let Ok(Some(SearchResult::Channel(channel))) = res {
+    ///     let uploads = channel.videos(None).await.unwrap();
+    /// }
+    /// ```
+    pub async fn videos(
+        &self,
+        options: Option<&ChannelSearchOptions>,
+    ) -> Result<ChannelVideos, VideoError> {
+        let default_options = ChannelSearchOptions::default();
+        let options = if let Some(some_options) = options {
+            drop(default_options);
+            some_options
+        } else {
+            &default_options
+        };
+
+        let url = Self::get_channel_url(if self.url.is_empty() {
+            &self.id
+        } else {
+            &self.url
+        })
+        .ok_or(VideoError::IsNotChannel(self.url.clone()))?;
+
+        let client = if let Some(request_options) = options.request_options.as_ref() {
+            YouTube::new_with_options(request_options)?.client
+        } else {
+            YouTube::new()?.client
+        };
+
+        let (initial_data, html) = Self::get_channel_page(&client, &url, "videos").await?;
+
+        if Self::get_uploads_contents(&initial_data).is_null() {
+            return Err(VideoError::ChannelBodyCannotParsed);
+        }
+
+        Ok(ChannelVideos::new(self.clone(), client, &initial_data, &html, options).await)
+    }
+
+    /// Get URL of the channel from channel id, handle (`@name`) or channel URL
+    pub fn get_channel_url(url_or_id: impl Into<String>) -> Option<String> {
+        let url_or_id: String = url_or_id.into();
+
+        if let Some(id) = CHANNEL_ID.find(&url_or_id) {
+            return Some(format!("https://www.youtube.com/channel/{}", id.as_str()));
+        }
+
+        if let Some(handle) = CHANNEL_HANDLE.find(&url_or_id) {
+            return Some(format!("https://www.youtube.com/{}", handle.as_str()));
+        }
+
+        CHANNEL_CUSTOM_URL
+            .captures(&url_or_id)
+            .and_then(|x| x.get(0))
+            .map(|x| format!("https://www.{}", x.as_str()))
+    }
+
+    /// Parse channel details from `ytInitialData` of the channel page
+    fn from_initial_data(initial_data: &serde_json::Value, url: &str) -> Option<Channel> {
+        let metadata = &initial_data["metadata"]["channelMetadataRenderer"];
+
+        if metadata.is_null() {
+            return None;
+        }
+
+        let header = &initial_data["header"]["c4TabbedHeaderRenderer"];
+        let badges = &header["badges"];
+
+        // Full description only exists on the about tab
+        let about = initial_data["contents"]["twoColumnBrowseResultsRenderer"]["tabs"]
+            .as_array()
+            .and_then(|tabs| {
+                tabs.iter()
+                    .map(|tab| {
+                        &tab["tabRenderer"]["content"]["sectionListRenderer"]["contents"][0]
+                            ["itemSectionRenderer"]["contents"][0]
+                            ["channelAboutFullMetadataRenderer"]
+                    })
+                    .find(|x| !x.is_null())
+            });
+
+        let thumbnails = |value: &serde_json::Value| {
+            value
+                .as_array()
+                .map(|x| {
+                    x.iter()
+                        .map(|x| Thumbnail {
+                            width: x.get("width").and_then(|x| x.as_u64()).unwrap_or(0),
+                            height: x.get("height").and_then(|x| x.as_u64()).unwrap_or(0),
+                            url: x
+                                .get("url")
+                                .and_then(|x| x.as_str())
+                                .unwrap_or("")
+                                .to_string(),
+                        })
+                        .collect::<Vec<Thumbnail>>()
+                })
+                .unwrap_or_default()
+        };
+
+        Some(Channel {
+            id: metadata["externalId"].as_str().unwrap_or("").to_string(),
+            name: metadata["title"].as_str().unwrap_or("").to_string(),
+            url: metadata["channelUrl"].as_str().unwrap_or(url).to_string(),
+            icon: thumbnails(&metadata["avatar"]["thumbnails"]),
+            verified: if badges.is_array() {
+                badges.as_array().unwrap().iter().any(|badge| {
+                    badge["metadataBadgeRenderer"]["style"]
+                        .as_str()
+                        .unwrap_or("")
+                        .to_lowercase()
+                        .contains("verified")
+                })
+            } else {
+                false
+            },
+            subscribers: header["subscriberCountText"]["simpleText"]
+                .as_str()
+                .or(header["subscriberCountText"]["runs"][0]["text"].as_str())
+                .map(|x| parse_abbreviated_number(x) as u64)
+                .unwrap_or(0),
+            description: about
+                .and_then(|x| x["description"]["simpleText"].as_str())
+                .or(metadata["description"].as_str())
+                .unwrap_or("")
+                .to_string(),
+            banner: thumbnails(&header["banner"]["thumbnails"]),
+        })
+    }
+
+    async fn get_channel_page(
+        client: &reqwest_middleware::ClientWithMiddleware,
+        url: &str,
+        tab: &str,
+    ) -> Result<(serde_json::Value, String), VideoError> {
+        let html = get_html(
+            client,
+            format!("{url}/{tab}?hl=en"),
+            Some(&DEFAULT_HEADERS.clone()),
+        )
+        .await?;
+
+        let initial_response_string = {
+            let document = Html::parse_document(&html);
+            let scripts_selector = Selector::parse("script").unwrap();
+            let mut initial_response_string = document
+                .select(&scripts_selector)
+                .filter(|x| x.inner_html().contains("var ytInitialData ="))
+                .map(|x| x.inner_html().replace("var ytInitialData =", ""))
+                .next()
+                .unwrap_or(String::from(""))
+                .trim()
+                .to_string();
+
+            initial_response_string.pop();
+
+            initial_response_string
+        };
+
+        let initial_data = serde_json::from_str::<serde_json::Value>(&initial_response_string)
+            .map_err(|_| VideoError::ChannelBodyCannotParsed)?;
+
+        Ok((initial_data, html))
+    }
+
+    fn get_uploads_contents(initial_data: &serde_json::Value) -> &serde_json::Value {
+        let tabs = &initial_data["contents"]["twoColumnBrowseResultsRenderer"]["tabs"];
+
+        tabs.as_array()
+            .and_then(|tabs| {
+                tabs.iter().find(|tab| {
+                    tab["tabRenderer"]["selected"].as_bool().unwrap_or(false)
+                        && !tab["tabRenderer"]["content"]["richGridRenderer"].is_null()
+                })
+            })
+            .map(|tab| &tab["tabRenderer"]["content"]["richGridRenderer"]["contents"])
+            .unwrap_or(&serde_json::Value::Null)
+    }
+
+    fn get_channel_videos(&self, container: &serde_json::Value) -> Vec<Video> {
+        let only_numbers_regex = Regex::new(r"[^0-9]").unwrap();
+
+        let mut videos: Vec<Video> = vec![];
+
+        if !container.is_array() {
+            return vec![];
+        }
+
+        // Videos owner is the channel itself
+        let owner = Channel {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            url: self.url.clone(),
+            icon: self.icon.clone(),
+            verified: self.verified,
+            subscribers: self.subscribers,
+            description: String::from(""),
+            banner: vec![],
+        };
+
+        for info in container.as_array().unwrap() {
+            let video = &info["richItemRenderer"]["content"]["videoRenderer"];
+            // video not proper type skip it!
+            if video.is_null() || video["videoId"].is_null() {
+                continue;
+            }
+
+            videos.push(Video {
+                id: video["videoId"].as_str().unwrap_or("").to_string(),
+                url: format!(
+                    "https://www.youtube.com/watch?v={}",
+                    video["videoId"].as_str().unwrap_or("")
+                ),
+                title: video["title"]["runs"][0]["text"]
+                    .as_str()
+                    .unwrap_or("")
+                    .to_string(),
+                description: video["descriptionSnippet"]["runs"]
+                    .as_array()
+                    .map(|x| {
+                        x.iter()
+                            .map(|c| c["text"].as_str().unwrap_or(""))
+                            .collect::<Vec<&str>>()
+                            .join("")
+                    })
+                    .unwrap_or("".to_string()),
+                duration: time_to_ms(&get_duration_raw(&video["lengthText"])) as u64,
+                duration_raw: get_duration_raw(&video["lengthText"]),
+                thumbnails: if video["thumbnail"]["thumbnails"].is_array() {
+                    video["thumbnail"]["thumbnails"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|x| Thumbnail {
+                            width: x.get("width").and_then(|x| x.as_u64()).unwrap_or(0),
+                            height: x.get("height").and_then(|x| x.as_u64()).unwrap_or(0),
+                            url: x
+                                .get("url")
+                                .and_then(|x| x.as_str())
+                                .unwrap_or("")
+                                .to_string(),
+                        })
+                        .collect::<Vec<Thumbnail>>()
+                } else {
+                    vec![]
+                },
+                channel: owner.clone(),
+                uploaded_at: video["publishedTimeText"]["simpleText"]
+                    .as_str()
+                    .map(|x| x.to_string()),
+                views: only_numbers_regex
+                    .replace_all(
+                        video["viewCountText"]["simpleText"].as_str().unwrap_or("0"),
+                        "",
+                    )
+                    .parse::<u64>()
+                    .unwrap_or(0),
+                is_live: is_live_now(video),
+                is_upcoming: is_upcoming(video),
+                scheduled_start_time: get_scheduled_start_time(video),
+                playlist_index: None,
+            });
+        }
+
+        videos
+    }
+}
+
+/// Build `sp` search filter param from [`SearchOptions`].
+/// It is base64 encoded protobuf message with sort order (field 1) and
+/// filters (field 2: upload date 1, type 2, duration 3)
+impl ChannelVideos {
+    async fn new(
+        channel: Channel,
+        client: reqwest_middleware::ClientWithMiddleware,
+        initial_data: &serde_json::Value,
+        html: &str,
+        options: &ChannelSearchOptions,
+    ) -> Self {
+        let contents = Channel::get_uploads_contents(initial_data);
+
+        let mut continuation = Continuation {
+            api: Some(get_api_key(html)),
+            token: Playlist::get_continuation_token(contents),
+            client_version: Some(get_client_version(html)),
+            pending: vec![],
+        };
+        let videos = continuation.take_page(channel.get_channel_videos(contents), options.limit);
+
+        let mut channel_videos = Self {
+            channel,
+            videos,
+            continuation,
+            client,
+        };
+
+        // we will try to fetch all uploaded videos from channel
+        if options.fetch_all {
+            channel_videos.fetch(u64::MAX).await;
+
+        // if fetch_all false but limit not reached we can try to reach limit
+        } else if options.limit > channel_videos.videos.len() as u64 {
+            channel_videos.fetch(options.limit).await;
+        }
+
+        channel_videos
+    }
+
+    /// Get next chunk of uploaded videos from channel and return fetched [`Video`] array.
+    /// - If limit is [`None`] it will be [`u64::MAX`]
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut uploads = Channel::get("@YouTube", None).await.unwrap();
+    ///
+    /// while let Ok(videos) = uploads.next(Some(50)).await {
+    ///     if videos.is_empty() {
+    ///         break;
+    ///     }
+    /// }
+    /// ```
+    pub async fn next(&mut self, limit: Option<u64>) -> Result<Vec<Video>, VideoError> {
+        let limit = limit.unwrap_or(u64::MAX);
+
+        // Videos of the last page beyond the previous limit come first
+        if !self.continuation.pending.is_empty() {
+            let videos = self.continuation.take_pending(limit);
+            self.videos.extend(videos.clone());
+
+            return Ok(videos);
+        }
+
+        if self.continuation.token.is_none() {
+            return Ok(vec![]);
+        }
+
+        let contents = fetch_continuation(&self.client, &self.continuation).await?;
+
+        if contents.is_null() {
+            return Ok(vec![]);
+        }
+
+        let mut continuation = Continuation {
+            token: Playlist::get_continuation_token(&contents),
+            api: self.continuation.api.clone(),
+            client_version: self.continuation.client_version.clone(),
+            pending: vec![],
+        };
+        let fetched_videos =
+            continuation.take_page(self.channel.get_channel_videos(&contents), limit);
+
+        self.continuation = continuation;
+
+        self.videos.extend(fetched_videos.clone());
+
+        Ok(fetched_videos)
+    }
+
+    async fn fetch(&mut self, limit: u64) {
+        while self.continuation.has_more() {
+            if self.videos.len() as u64 >= limit {
+                break;
+            }
+
+            let chunk = self.next(Some(limit - self.videos.len() as u64)).await;
+
+            // if error encountered or any not new data finish the job
+            if chunk.map(|x| x.is_empty()).unwrap_or(true) {
+                break;
+            }
+        }
+    }
 }
 
-fn filter_string(filter: &SearchType) -> String {
-    match filter {
-        SearchType::Video => "EgIQAQ%253D%253D".to_string(),
-        SearchType::Channel => "EgIQAg%253D%253D".to_string(),
-        SearchType::Playlist => "EgIQAw%253D%253D".to_string(),
-        SearchType::Film => "EgIQBA%253D%253D".to_string(),
-        SearchType::All => "".to_string(),
+fn filter_string(options: &SearchOptions) -> String {
+    let mut filters: Vec<u8> = vec![];
+
+    if let Some(upload_date) = options.upload_date.as_ref() {
+        let value = match upload_date {
+            UploadDate::LastHour => 1,
+            UploadDate::Today => 2,
+            UploadDate::ThisWeek => 3,
+            UploadDate::ThisMonth => 4,
+            UploadDate::ThisYear => 5,
+        };
+        filters.extend([0x08, value]);
+    }
+
+    let search_type = match options.search_type {
+        SearchType::Video => Some(1),
+        SearchType::Channel => Some(2),
+        SearchType::Playlist => Some(3),
+        SearchType::Film => Some(4),
+        SearchType::All => None,
+    };
+    if let Some(value) = search_type {
+        filters.extend([0x10, value]);
+    }
+
+    if let Some(duration) = options.duration.as_ref() {
+        let value = match duration {
+            DurationFilter::Short => 1,
+            DurationFilter::Long => 2,
+            DurationFilter::Medium => 3,
+        };
+        filters.extend([0x18, value]);
+    }
+
+    let mut message: Vec<u8> = vec![];
+
+    let sort_by = match options.sort_by {
+        SortBy::Relevance => 0,
+        SortBy::Rating => 1,
+        SortBy::UploadDate => 2,
+        SortBy::ViewCount => 3,
+    };
+    if sort_by != 0 {
+        message.extend([0x08, sort_by]);
+    }
+
+    if !filters.is_empty() {
+        message.extend([0x12, filters.len() as u8]);
+        message.extend(filters);
     }
+
+    if message.is_empty() {
+        return "".to_string();
+    }
+
+    // YouTube expects the param as double url encoded
+    encode(&base64_encode(&message)).replace('%', "%25")
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::new();
+
+    for chunk in bytes.chunks(3) {
+        let buf = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = ((buf[0] as u32) << 16) | ((buf[1] as u32) << 8) | buf[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((n >> (18 - i * 6)) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
 }
 
 fn get_client_version(html: impl Into<String>) -> String {
@@ -1260,7 +2226,6 @@ fn get_client_version(html: impl Into<String>) -> String {
             let second_collect = x.split('"').collect::<Vec<&str>>();
             if !second_collect.is_empty() {
                 let inner_tube = second_collect.first().unwrap().to_string();
-                // println!("INNERTUBE_CONTEXT_CLIENT_VERSION => {inner_tube}");
 
                 inner_tube
             } else {
@@ -1273,7 +2238,6 @@ fn get_client_version(html: impl Into<String>) -> String {
                         let forth_collect = c.split('"').collect::<Vec<&str>>();
                         if !forth_collect.is_empty() {
                             let inner_tube = forth_collect.first().unwrap().to_string();
-                            // println!("innertube_context_client_version => {inner_tube}");
                             inner_tube
                         } else {
                             DEFAULT_CLIENT_VERSOIN.to_string()
@@ -1293,7 +2257,6 @@ fn get_client_version(html: impl Into<String>) -> String {
                     let forth_collect = c.split('"').collect::<Vec<&str>>();
                     if !forth_collect.is_empty() {
                         let inner_tube = forth_collect.first().unwrap().to_string();
-                        // println!("innertube_context_client_version => {inner_tube}");
                         inner_tube
                     } else {
                         DEFAULT_CLIENT_VERSOIN.to_string()
@@ -1317,7 +2280,6 @@ fn get_api_key(html: impl Into<String>) -> String {
             let second_collect = x.split('"').collect::<Vec<&str>>();
             if !second_collect.is_empty() {
                 let inner_tube = second_collect.first().unwrap().to_string();
-                // println!("INNERTUBE_API_KEY => {inner_tube}");
                 inner_tube
             } else {
                 let third_collect = html.split(r#""innertubeApiKey":""#).collect::<Vec<&str>>();
@@ -1327,7 +2289,6 @@ fn get_api_key(html: impl Into<String>) -> String {
                         let forth_collect = c.split('"').collect::<Vec<&str>>();
                         if !forth_collect.is_empty() {
                             let inner_tube = forth_collect.first().unwrap().to_string();
-                            // println!("innertubeApiKey => {inner_tube}");
 
                             inner_tube
                         } else {
@@ -1346,7 +2307,6 @@ fn get_api_key(html: impl Into<String>) -> String {
                     let forth_collect = c.split('"').collect::<Vec<&str>>();
                     if !forth_collect.is_empty() {
                         let inner_tube = forth_collect.first().unwrap().to_string();
-                        // println!("innertubeApiKey => {inner_tube}");
                         inner_tube
                     } else {
                         DEFAULT_INNERTUBE_KEY.to_string()
@@ -1392,7 +2352,11 @@ async fn make_request(
     }
 
     let original_url = &request_options.original_url;
-    let query = &request_options.query;
+    let query = if let Some(continuation) = request_options.continuation.as_ref() {
+        format!(r#""continuation": "{continuation}""#)
+    } else {
+        format!(r#""query": "{}""#, request_options.query)
+    };
     let filter = if request_options.filter.is_some() {
         format!(
             r#""params": "{}","#,
@@ -1404,7 +2368,7 @@ async fn make_request(
 
     let format_str = format!(
         r#"{{
-            "query": "{query}",
+            {query},
             {filter}
             "context": {{
                 "client": {{
@@ -1442,11 +2406,87 @@ async fn make_request(
     res.unwrap()
 }
 
+/// Fetch next items of a browse page (playlist videos, channel uploads) with the [`Continuation`] token
+async fn fetch_continuation(
+    client: &reqwest_middleware::ClientWithMiddleware,
+    continuation: &Continuation,
+) -> Result<serde_json::Value, VideoError> {
+    // request body
+    let continuation_token = continuation.token.clone().unwrap_or("".to_string());
+    let mut client_version = continuation
+        .client_version
+        .clone()
+        .unwrap_or("".to_string());
+
+    if client_version.is_empty() {
+        client_version = "".to_string();
+    } else {
+        client_version = format!(r#""clientVersion": "{client_version}""#);
+    }
+
+    let continuation_api = continuation.api.clone().unwrap_or("".to_string());
+
+    let format_str = format!(
+        r#"{{
+            "continuation": "{continuation_token}",
+            "context": {{
+                "client": {{
+                    "utcOffsetMinutes": 0,
+                    "gl": "US",
+                    "hl": "en",
+                    "clientName": "WEB",
+                    {client_version}
+                }},
+                "user": {{}},
+                "request": {{}}
+            }}
+        }}
+        "#
+    );
+
+    // Get json object with continuation token
+    let body: serde_json::Value = serde_json::from_str(&format_str).unwrap();
+
+    let res = client
+        .post(format!(
+            "https://www.youtube.com/youtubei/v1/browse?key={continuation_api}"
+        ))
+        .json(&body)
+        .send()
+        .await;
+
+    if res.is_err() {
+        return Err(VideoError::ReqwestMiddleware(res.err().unwrap()));
+    }
+
+    let res = res.unwrap().json::<serde_json::Value>().await;
+
+    if res.is_err() {
+        return Err(VideoError::BodyCannotParsed);
+    }
+    let res = res.unwrap();
+
+    Ok(
+        res["onResponseReceivedActions"][0]["appendContinuationItemsAction"]["continuationItems"]
+            .clone(),
+    )
+}
+
+/// Raw duration of the `lengthText` of a video renderer, `0:00` if not found
+fn get_duration_raw(length_text: &serde_json::Value) -> String {
+    length_text["simpleText"]
+        .as_str()
+        .or(length_text["runs"][0]["text"].as_str())
+        .unwrap_or("0:00")
+        .trim()
+        .to_string()
+}
+
 fn parse_search_result(
     client: &reqwest_middleware::ClientWithMiddleware,
     html: impl Into<String>,
     options: &SearchOptions,
-) -> Vec<SearchResult> {
+) -> SearchPage {
     let mut html: String = html.into();
 
     html = {
@@ -1470,17 +2510,46 @@ fn parse_search_result(
     if !html.is_empty() {
         let serde_value = serde_json::from_str::<serde_json::Value>(&html).unwrap();
         let contents = &serde_value["contents"]["twoColumnSearchResultsRenderer"]
-            ["primaryContents"]["sectionListRenderer"]["contents"][0]["itemSectionRenderer"]
-            ["contents"];
+            ["primaryContents"]["sectionListRenderer"]["contents"];
 
         // if contents found try to format values
-        if !contents.is_null() {
-            return format_search_result(client, contents, options);
+        if !contents[0]["itemSectionRenderer"]["contents"].is_null() {
+            return format_search_page(client, contents, options);
         }
     }
 
-    // if cannot fetch initial data return empty array
-    vec![]
+    // if cannot fetch initial data return empty page
+    SearchPage::default()
+}
+
+/// Format the sections of a search page, results are in the `itemSectionRenderer` and the next page token in the `continuationItemRenderer`
+fn format_search_page(
+    client: &reqwest_middleware::ClientWithMiddleware,
+    sections: &serde_json::Value,
+    options: &SearchOptions,
+) -> SearchPage {
+    let results = sections
+        .as_array()
+        .map(|sections| {
+            sections
+                .iter()
+                .filter(|x| !x["itemSectionRenderer"]["contents"].is_null())
+                .flat_map(|x| {
+                    format_search_result(client, &x["itemSectionRenderer"]["contents"], options)
+                })
+                .take(if options.limit > 0 {
+                    options.limit as usize
+                } else {
+                    usize::MAX
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    SearchPage {
+        results,
+        continuation: Playlist::get_continuation_token(sections),
+    }
 }
 
 fn format_search_result(
@@ -1581,25 +2650,10 @@ fn format_search_result(
                         } else {
                             String::from("")
                         },
-                        duration: if !data["videoRenderer"]["lengthText"].is_null() {
-                            time_to_ms(
-                                data["videoRenderer"]["lengthText"]["simpleText"]
-                                    .as_str()
-                                    .unwrap_or("0:00"),
-                            ) as u64
-                        } else {
-                            0u64
-                        },
-                        duration_raw: if !data["videoRenderer"]["lengthText"]["simpleText"]
-                            .is_null()
-                        {
-                            data["videoRenderer"]["lengthText"]["simpleText"]
-                                .as_str()
-                                .unwrap_or("0:00")
-                                .to_string()
-                        } else {
-                            String::from("0:00")
-                        },
+                        duration: time_to_ms(&get_duration_raw(
+                            &data["videoRenderer"]["lengthText"],
+                        )) as u64,
+                        duration_raw: get_duration_raw(&data["videoRenderer"]["lengthText"]),
                         thumbnails: if data["videoRenderer"]["thumbnail"]["thumbnails"].is_array() {
                             data["videoRenderer"]["thumbnail"]["thumbnails"]
                                 .as_array()
@@ -1775,6 +2829,8 @@ fn format_search_result(
                                 false
                             },
                             subscribers: 0,
+                            description: String::from(""),
+                            banner: vec![],
                         },
                         uploaded_at: if data["videoRenderer"]["publishedTimeText"]["simpleText"]
                             .is_string()
@@ -1800,6 +2856,10 @@ fn format_search_result(
                         } else {
                             0u64
                         },
+                        is_live: is_live_now(&data["videoRenderer"]),
+                        is_upcoming: is_upcoming(&data["videoRenderer"]),
+                        scheduled_start_time: get_scheduled_start_time(&data["videoRenderer"]),
+                        playlist_index: None,
                     };
 
                     res.push(SearchResult::Video(video));
@@ -1913,6 +2973,8 @@ fn format_search_result(
                         } else {
                             0
                         },
+                        description: String::from(""),
+                        banner: vec![],
                     };
 
                     res.push(SearchResult::Channel(channel));
@@ -1998,6 +3060,8 @@ fn format_search_result(
                                 false
                             },
                             subscribers: 0,
+                            description: String::from(""),
+                            banner: vec![],
                         },
                         thumbnails: if data["playlistRenderer"]["thumbnails"][0]["thumbnails"]
                             .is_array()
@@ -2102,3 +3166,312 @@ fn format_search_result(
     // return results array
     res
 }
+
+/// Error of the first `ERROR` type alert of the playlist page
+fn get_playlist_alert_error(initial_data: &serde_json::Value, url: &str) -> Option<VideoError> {
+    let alert = initial_data["alerts"]
+        .as_array()?
+        .iter()
+        .filter_map(|x| {
+            x.get("alertRenderer")
+                .or_else(|| x.get("alertWithButtonRenderer"))
+        })
+        .find(|x| x["type"].as_str() == Some("ERROR"))?;
+
+    let message = alert["text"]["simpleText"]
+        .as_str()
+        .map(|x| x.to_string())
+        .or_else(|| {
+            alert["text"]["runs"].as_array().map(|runs| {
+                runs.iter()
+                    .filter_map(|x| x["text"].as_str())
+                    .collect::<String>()
+            })
+        })
+        .unwrap_or_default();
+
+    let lowercase_message = message.to_lowercase();
+
+    Some(if lowercase_message.contains("private") {
+        VideoError::PlaylistIsPrivate(url.to_string())
+    } else if lowercase_message.contains("does not exist") {
+        VideoError::PlaylistNotFound(url.to_string())
+    } else {
+        VideoError::PlaylistUnavailable(message)
+    })
+}
+
+/// Value of the `index` query param of the URL
+fn get_index_param(url: &str) -> Option<u64> {
+    url::Url::parse(url)
+        .ok()?
+        .query_pairs()
+        .find(|(key, _)| key == "index")
+        .and_then(|(_, value)| value.parse::<u64>().ok())
+}
+
+/// Value of the `list` query param of the URL. Preferred over matching the whole URL,
+/// because other params (e.g. `v`, `si`, `pp`) can contain playlist id like strings
+fn get_list_param(url: &str) -> Option<String> {
+    url::Url::parse(url)
+        .ok()?
+        .query_pairs()
+        .find(|(key, _)| key == "list")
+        .map(|(_, value)| value.into_owned())
+        .filter(|x| !x.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_playlist_alert_error() {
+        let alert = |alert_type: &str, text: &str| {
+            serde_json::json!({
+                "alerts": [{ "alertWithButtonRenderer": {
+                    "type": alert_type,
+                    "text": { "simpleText": text }
+                }}]
+            })
+        };
+
+        assert!(matches!(
+            get_playlist_alert_error(&alert("ERROR", "This playlist is private."), "url"),
+            Some(VideoError::PlaylistIsPrivate(_))
+        ));
+        println!("[PASSED] test_private_playlist_alert");
+
+        assert!(matches!(
+            get_playlist_alert_error(
+                &serde_json::json!({
+                    "alerts": [{ "alertRenderer": {
+                        "type": "ERROR",
+                        "text": { "runs": [{ "text": "The playlist does not exist." }] }
+                    }}]
+                }),
+                "url"
+            ),
+            Some(VideoError::PlaylistNotFound(_))
+        ));
+        println!("[PASSED] test_not_found_playlist_alert");
+
+        assert!(matches!(
+            get_playlist_alert_error(&alert("ERROR", "This playlist type is unviewable."), "url"),
+            Some(VideoError::PlaylistUnavailable(message)) if message == "This playlist type is unviewable."
+        ));
+        println!("[PASSED] test_unavailable_playlist_alert");
+
+        assert!(
+            get_playlist_alert_error(&alert("INFO", "Unavailable videos are hidden"), "url")
+                .is_none()
+        );
+        assert!(get_playlist_alert_error(&serde_json::json!({}), "url").is_none());
+        println!("[PASSED] test_no_playlist_alert_error");
+    }
+
+    #[test]
+    fn test_get_index_param() {
+        assert_eq!(
+            get_index_param("https://www.youtube.com/watch?v=FZ8BxMU3BYc&list=PLwMEL7UNT4o9iMzrvNBXZqXbNPFfT6rVD&index=3"),
+            Some(3)
+        );
+        assert_eq!(
+            get_index_param(
+                "https://www.youtube.com/playlist?list=PLwMEL7UNT4o9iMzrvNBXZqXbNPFfT6rVD"
+            ),
+            None
+        );
+        println!("[PASSED] test_get_index_param");
+    }
+
+    #[test]
+    fn test_get_playlist_url() {
+        let playlist_url = |id: &str| Some(format!("https://www.youtube.com/playlist?list={id}"));
+
+        assert_eq!(
+            Playlist::get_playlist_url(
+                "https://www.youtube.com/playlist?list=PLwMEL7UNT4o9iMzrvNBXZqXbNPFfT6rVD"
+            ),
+            playlist_url("PLwMEL7UNT4o9iMzrvNBXZqXbNPFfT6rVD")
+        );
+        println!("[PASSED] test_playlist_list_url");
+
+        // `si` param matches the playlist id pattern before the `list` param
+        let watch_url = "https://www.youtube.com/watch?v=FZ8BxMU3BYc&si=PLa1b2c3d4e5f6g7h8i9&list=PLwMEL7UNT4o9iMzrvNBXZqXbNPFfT6rVD&index=2";
+        assert!(Playlist::is_playlist(watch_url));
+        assert_eq!(
+            Playlist::get_playlist_url(watch_url),
+            playlist_url("PLwMEL7UNT4o9iMzrvNBXZqXbNPFfT6rVD")
+        );
+        println!("[PASSED] test_watch_with_list_url");
+
+        let album_url =
+            "https://music.youtube.com/playlist?list=OLAK5uy_kGcDvSqdVjkBlC_UbbhTmPyPrFuXnrQ0A";
+        assert!(Playlist::is_playlist(album_url));
+        assert_eq!(
+            Playlist::get_playlist_url(album_url),
+            playlist_url("OLAK5uy_kGcDvSqdVjkBlC_UbbhTmPyPrFuXnrQ0A")
+        );
+        println!("[PASSED] test_album_url");
+
+        assert!(!Playlist::is_playlist(
+            "https://www.youtube.com/watch?v=FZ8BxMU3BYc"
+        ));
+        assert_eq!(
+            Playlist::get_playlist_url(
+                "https://www.youtube.com/watch?v=FZ8BxMU3BYc&list=RDFZ8BxMU3BYc"
+            ),
+            None
+        );
+        println!("[PASSED] test_not_playlist_url");
+    }
+
+    #[test]
+    fn test_find_video_at_index() {
+        let renderer = |id: &str, index: &str, available: bool| {
+            let mut video = serde_json::json!({ "playlistVideoRenderer": {
+                "videoId": id,
+                "index": { "simpleText": index },
+                "title": { "runs": [{ "text": id }] },
+            }});
+
+            if available {
+                video["playlistVideoRenderer"]["shortBylineText"] =
+                    serde_json::json!({ "runs": [{ "text": "Channel" }] });
+            }
+
+            video
+        };
+
+        // Second video is deleted, YouTube keeps its index but it is not listed
+        let contents = serde_json::json!([
+            renderer("aaaaaaaaaaa", "1", true),
+            renderer("bbbbbbbbbbb", "2", false),
+            renderer("ccccccccccc", "3", true),
+        ]);
+        let videos = Playlist::get_playlist_videos(&contents);
+        assert_eq!(videos.len(), 2);
+
+        assert_eq!(videos[1].id, "ccccccccccc");
+        assert_eq!(videos[1].playlist_index, Some(3));
+        assert_eq!(
+            Playlist::find_video_at_index(videos.clone(), 3),
+            ControlFlow::Break(Some(videos[1].clone()))
+        );
+        println!("[PASSED] test_index_after_unavailable_video");
+
+        assert_eq!(
+            Playlist::find_video_at_index(videos.clone(), 2),
+            ControlFlow::Break(None)
+        );
+        println!("[PASSED] test_unavailable_index");
+
+        assert_eq!(
+            Playlist::find_video_at_index(videos, 4),
+            ControlFlow::Continue(())
+        );
+        println!("[PASSED] test_index_not_fetched");
+    }
+
+    #[test]
+    fn test_get_mix_id() {
+        assert_eq!(
+            Playlist::get_mix_id("https://www.youtube.com/watch?v=FZ8BxMU3BYc&list=RDFZ8BxMU3BYc"),
+            Some("RDFZ8BxMU3BYc".to_string())
+        );
+        println!("[PASSED] test_video_mix_id");
+
+        // Channel and artist mixes are also valid playlist ids, they must be found as mix
+        let channel_mix =
+            "https://www.youtube.com/watch?v=FZ8BxMU3BYc&list=RDCMUCBR8-60-B28hp2BmDPdntcQ";
+        assert!(Playlist::get_playlist_url(channel_mix).is_some());
+        assert_eq!(
+            Playlist::get_mix_id(channel_mix),
+            Some("RDCMUCBR8-60-B28hp2BmDPdntcQ".to_string())
+        );
+        println!("[PASSED] test_channel_mix_id");
+
+        let artist_mix =
+            "https://www.youtube.com/watch?v=FZ8BxMU3BYc&list=RDEMhe2e0e2o7qvR5ZnC8ho1LA";
+        assert!(Playlist::get_playlist_url(artist_mix).is_some());
+        assert_eq!(
+            Playlist::get_mix_id(artist_mix),
+            Some("RDEMhe2e0e2o7qvR5ZnC8ho1LA".to_string())
+        );
+        println!("[PASSED] test_artist_mix_id");
+
+        assert_eq!(
+            Playlist::get_mix_id(
+                "https://music.youtube.com/playlist?list=OLAK5uy_kGcDvSqdVjkBlC_UbbhTmPyPrFuXnrQ0A"
+            ),
+            None
+        );
+        assert_eq!(
+            Playlist::get_mix_id(
+                "https://www.youtube.com/playlist?list=PLwMEL7UNT4o9iMzrvNBXZqXbNPFfT6rVD"
+            ),
+            None
+        );
+        println!("[PASSED] test_not_mix_id");
+    }
+
+    #[test]
+    fn test_filter_string() {
+        assert_eq!(
+            filter_string(&SearchOptions::default()),
+            "EgIQAQ%253D%253D".to_string()
+        );
+        println!("[PASSED] test_video_type_filter");
+
+        assert_eq!(
+            filter_string(&SearchOptions {
+                search_type: SearchType::All,
+                ..Default::default()
+            }),
+            "".to_string()
+        );
+        println!("[PASSED] test_empty_filter");
+
+        assert_eq!(
+            filter_string(&SearchOptions {
+                duration: Some(DurationFilter::Short),
+                upload_date: Some(UploadDate::ThisWeek),
+                ..Default::default()
+            }),
+            "EgYIAxABGAE%253D".to_string()
+        );
+        println!("[PASSED] test_duration_and_upload_date_filter");
+
+        assert_eq!(
+            filter_string(&SearchOptions {
+                search_type: SearchType::All,
+                sort_by: SortBy::UploadDate,
+                ..Default::default()
+            }),
+            "CAI%253D".to_string()
+        );
+        println!("[PASSED] test_sort_by_filter");
+
+        assert_eq!(
+            filter_string(&SearchOptions {
+                sort_by: SortBy::ViewCount,
+                ..Default::default()
+            }),
+            "CAMSAhAB".to_string()
+        );
+        println!("[PASSED] test_sort_by_with_type_filter");
+
+        assert_eq!(
+            filter_string(&SearchOptions {
+                search_type: SearchType::All,
+                sort_by: SortBy::Rating,
+                duration: Some(DurationFilter::Long),
+                upload_date: Some(UploadDate::ThisMonth),
+                ..Default::default()
+            }),
+            "CAESBAgEGAI%253D".to_string()
+        );
+        println!("[PASSED] test_all_filters");
+    }
+}
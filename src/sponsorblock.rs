@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+
+use crate::structs::{RequestOptions, VideoError};
+
+pub const SPONSORBLOCK_API_URL: &str = "https://sponsor.ajay.app/api/skipSegments";
+
+/// Category of a SponsorBlock segment
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SegmentCategory {
+    /// Paid promotion, paid referrals and direct advertisements
+    #[serde(rename = "sponsor")]
+    Sponsor,
+    /// Unpaid or self promotion (merchandise, donations, ...)
+    #[serde(rename = "selfpromo")]
+    SelfPromo,
+    /// Reminder to like, subscribe or follow in the middle of the content
+    #[serde(rename = "interaction")]
+    Interaction,
+    /// Intro animation or intermission
+    #[serde(rename = "intro")]
+    Intro,
+    /// Credits or endcards
+    #[serde(rename = "outro")]
+    Outro,
+    /// Recap or preview of the content
+    #[serde(rename = "preview")]
+    Preview,
+    /// Non-music section of a music video
+    #[serde(rename = "music_offtopic")]
+    MusicOfftopic,
+    /// Tangential scene added only for filler or humor
+    #[serde(rename = "filler")]
+    Filler,
+    /// Category not known by this version of the crate
+    #[serde(other)]
+    Unknown,
+}
+
+impl SegmentCategory {
+    /// All known skippable categories
+    pub const ALL: &'static [SegmentCategory] = &[
+        SegmentCategory::Sponsor,
+        SegmentCategory::SelfPromo,
+        SegmentCategory::Interaction,
+        SegmentCategory::Intro,
+        SegmentCategory::Outro,
+        SegmentCategory::Preview,
+        SegmentCategory::MusicOfftopic,
+        SegmentCategory::Filler,
+    ];
+
+    /// Name of the category in the SponsorBlock API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SegmentCategory::Sponsor => "sponsor",
+            SegmentCategory::SelfPromo => "selfpromo",
+            SegmentCategory::Interaction => "interaction",
+            SegmentCategory::Intro => "intro",
+            SegmentCategory::Outro => "outro",
+            SegmentCategory::Preview => "preview",
+            SegmentCategory::MusicOfftopic => "music_offtopic",
+            SegmentCategory::Filler => "filler",
+            SegmentCategory::Unknown => "unknown",
+        }
+    }
+}
+
+/// Skip segment of a video returned by SponsorBlock
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Segment {
+    /// Start of the segment in seconds
+    pub start: f64,
+    /// End of the segment in seconds
+    pub end: f64,
+    pub category: SegmentCategory,
+}
+
+#[derive(Deserialize)]
+struct SegmentResponse {
+    segment: (f64, f64),
+    category: SegmentCategory,
+}
+
+/// Get skip segments of the video from the SponsorBlock API with the default [`RequestOptions`].
+/// Empty `categories` only returns [`SegmentCategory::Sponsor`] segments
+/// # Example
+/// ```ignore
+///     let segments = get_skip_segments("FZ8BxMU3BYc", &[SegmentCategory::Sponsor, SegmentCategory::Intro])
+///         .await
+///         .unwrap();
+///
+///     for segment in segments {
+///         println!("{:?}: {} - {}", segment.category, segment.start, segment.end);
+///     }
+/// ```
+pub async fn get_skip_segments(
+    video_id: &str,
+    categories: &[SegmentCategory],
+) -> Result<Vec<Segment>, VideoError> {
+    let client = RequestOptions::default().build_client()?;
+
+    fetch_skip_segments(&client, video_id, categories).await
+}
+
+pub(crate) async fn fetch_skip_segments(
+    client: &reqwest_middleware::ClientWithMiddleware,
+    video_id: &str,
+    categories: &[SegmentCategory],
+) -> Result<Vec<Segment>, VideoError> {
+    let mut url = url::Url::parse_with_params(SPONSORBLOCK_API_URL, &[("videoID", video_id)])?;
+
+    // API defaults to sponsor segments only if no categories given
+    if !categories.is_empty() {
+        let categories = serde_json::to_string(
+            &categories
+                .iter()
+                .filter(|x| **x != SegmentCategory::Unknown)
+                .map(|x| x.as_str())
+                .collect::<Vec<&str>>(),
+        )
+        .map_err(|_x| VideoError::BodyCannotParsed)?;
+
+        url.query_pairs_mut()
+            .append_pair("categories", categories.as_str());
+    }
+
+    let response = client
+        .get(url.as_str())
+        .send()
+        .await
+        .map_err(VideoError::ReqwestMiddleware)?;
+
+    // API responds with 404 if the video has no segments
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(vec![]);
+    }
+
+    let segments = response
+        .error_for_status()?
+        .json::<Vec<SegmentResponse>>()
+        .await
+        .map_err(|_x| VideoError::BodyCannotParsed)?;
+
+    Ok(segments
+        .into_iter()
+        .map(|x| Segment {
+            start: x.segment.0,
+            end: x.segment.1,
+            category: x.category,
+        })
+        .collect())
+}
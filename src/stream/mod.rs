@@ -8,6 +8,8 @@ mod media_format;
 #[cfg(feature = "live")]
 mod segment;
 
+#[cfg(feature = "ffmpeg")]
+pub(crate) use streams::{FFmpegAudioInput, FFmpegStreamOptions, MergedStream};
 #[cfg(feature = "live")]
 pub use streams::{LiveStream, LiveStreamOptions};
-pub use streams::{NonLiveStream, NonLiveStreamOptions, Stream};
+pub use streams::{NonLiveStream, NonLiveStreamOptions, RefreshLink, Stream};
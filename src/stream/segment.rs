@@ -10,6 +10,8 @@ pub struct Segment {
     pub seq: u64,
     pub format: MediaFormat,
     pub initialization: Option<RemoteData>,
+    /// Duration of the segment from `#EXTINF`
+    pub duration: std::time::Duration,
 }
 
 impl Segment {
@@ -4,14 +4,20 @@ use crate::stream::{
     streams::Stream,
 };
 use crate::structs::{CustomRetryableStrategy, VideoError};
-use crate::utils::{get_html, make_absolute_url};
+use crate::utils::{get_dvr_start_index, get_html, make_absolute_url};
 
 use async_trait::async_trait;
 use bytes::{Bytes, BytesMut};
 use m3u8_rs::parse_media_playlist;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use tokio::sync::RwLock;
 
+/// Playlist refresh interval in milliseconds until the target duration of the playlist is known
+const DEFAULT_REFRESH_INTERVAL: u128 = 20000;
+
 pub struct LiveStreamOptions {
     pub client: Option<reqwest_middleware::ClientWithMiddleware>,
     pub stream_url: String,
@@ -22,6 +28,8 @@ pub struct LiveStream {
     stream_url: String,
 
     last_refresh: RwLock<u128>,
+    /// Target duration of the playlist segments in milliseconds, the playlist is refreshed at this interval
+    refresh_interval: RwLock<u128>,
     segments: RwLock<Vec<(Segment, Encryption)>>,
     is_end: RwLock<bool>,
     last_seg: RwLock<Option<(u64, u64)>>,
@@ -56,12 +64,95 @@ impl LiveStream {
             client,
             stream_url: options.stream_url,
             last_refresh: RwLock::new(0),
+            refresh_interval: RwLock::new(DEFAULT_REFRESH_INTERVAL),
             segments: RwLock::new(vec![]),
             is_end: RwLock::new(false),
             last_seg: RwLock::new(None),
         })
     }
 
+    /// Record the live stream to the file at `path` by appending the new segments of the playlist,
+    /// until the stream ends or the `duration` elapses. `None` records until the end of the stream
+    /// # Example
+    /// ```ignore
+    ///     let info = video.get_info().await.unwrap();
+    ///     let format = choose_format(&info.formats, &VideoOptions::default()).unwrap();
+    ///
+    ///     let stream = LiveStream::new(LiveStreamOptions {
+    ///         client: None,
+    ///         stream_url: format.url,
+    ///     })
+    ///     .unwrap();
+    ///
+    ///     stream
+    ///         .record_to_file("live.ts", Some(Duration::from_secs(60)))
+    ///         .await
+    ///         .unwrap();
+    /// ```
+    pub async fn record_to_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        duration: Option<Duration>,
+    ) -> Result<(), VideoError> {
+        use std::{fs::File, io::Write};
+
+        let mut file = File::create(path).map_err(|e| VideoError::DownloadError(e.to_string()))?;
+        let deadline = duration.map(|x| tokio::time::Instant::now() + x);
+
+        loop {
+            let chunk = match deadline {
+                Some(deadline) => match tokio::time::timeout_at(deadline, self.chunk()).await {
+                    Ok(chunk) => chunk?,
+                    // Duration elapsed while waiting for the next segment
+                    Err(_) => break,
+                },
+                None => self.chunk().await?,
+            };
+
+            let Some(bytes) = chunk else {
+                break;
+            };
+
+            file.write_all(&bytes)
+                .map_err(|e| VideoError::DownloadError(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Start the stream `offset` back from the live edge, e.g. to capture the last 2 minutes of the stream.
+    /// Segments older than the offset are skipped, so call it before reading the stream.
+    /// Returns the actual duration until the live edge, which is shorter than the offset
+    /// if the playlist does not reach back that far. Sliding window live playlists only contain the DVR window,
+    /// while event playlists (`#EXT-X-PLAYLIST-TYPE:EVENT`) contain the whole stream since its start
+    /// # Example
+    /// ```ignore
+    ///     let stream = LiveStream::new(LiveStreamOptions {
+    ///         client: None,
+    ///         stream_url: format.url,
+    ///     })
+    ///     .unwrap();
+    ///
+    ///     stream.segments_from(Duration::from_secs(120)).await.unwrap();
+    ///
+    ///     stream.record_to_file("last_2_minutes.ts", None).await.unwrap();
+    /// ```
+    pub async fn segments_from(&self, offset: Duration) -> Result<Duration, VideoError> {
+        self.refresh_playlist().await?;
+
+        let mut segments = self.segments.write().await;
+
+        let durations = segments
+            .iter()
+            .map(|(segment, _)| segment.duration)
+            .collect::<Vec<Duration>>();
+        let (start_index, duration) = get_dvr_start_index(&durations, offset);
+
+        segments.drain(..start_index);
+
+        Ok(duration)
+    }
+
     async fn last_refresh(&self) -> u128 {
         *self.last_refresh.read().await
     }
@@ -85,6 +176,12 @@ impl LiveStream {
             .map_err(|e| VideoError::M3U8ParseError(e.to_string()))?
             .1;
 
+        // Refresh the playlist as often as new segments are published
+        let target_duration = media_playlist.target_duration as f64;
+        if target_duration > 0.0 {
+            *self.refresh_interval.write().await = (target_duration * 1000.0) as u128;
+        }
+
         let mut cur_init = None;
 
         // Loop through media segments
@@ -135,6 +232,7 @@ impl LiveStream {
                 seq,
                 format: MediaFormat::Unknown,
                 initialization: init,
+                duration: Duration::from_secs_f32(segment.duration.max(0.0)),
             };
 
             // if segments already in segment vector skip it
@@ -179,7 +277,7 @@ impl Stream for LiveStream {
             return Ok(None);
         }
 
-        let live_seconds = 20000; // refresh millis
+        let live_seconds = *self.refresh_interval.read().await; // refresh millis
 
         let start = SystemTime::now();
         let current_time = start
@@ -244,4 +342,13 @@ impl Stream for LiveStream {
 
         Ok(Some(buf.into()))
     }
+
+    fn content_type(&self) -> Option<&str> {
+        // HLS segments are MPEG transport streams
+        Some("video/mp2t")
+    }
+
+    fn is_live(&self) -> bool {
+        true
+    }
 }
@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use tokio::sync::Mutex;
+
+use super::{FFmpegStream, FFmpegStreamOptions, Stream};
+use crate::VideoError;
+
+/// Video only and audio only formats downloaded concurrently and muxed by FFmpeg into a single stream
+pub(crate) struct MergedStream {
+    /// `None` after the output of FFmpeg is exhausted
+    ffmpeg_stream: Mutex<Option<FFmpegStream>>,
+    content_length: u64,
+}
+
+impl MergedStream {
+    pub fn new(options: FFmpegStreamOptions) -> Result<Self, VideoError> {
+        let content_length = options.content_length
+            + options
+                .audio
+                .as_ref()
+                .map(|audio| audio.content_length)
+                .unwrap_or(0);
+
+        Ok(Self {
+            ffmpeg_stream: Mutex::new(Some(FFmpegStream::new(options)?)),
+            content_length,
+        })
+    }
+}
+
+#[async_trait]
+impl Stream for MergedStream {
+    async fn chunk(&self) -> Result<Option<Bytes>, VideoError> {
+        let mut ffmpeg_stream_guard = self.ffmpeg_stream.lock().await;
+
+        let Some(ffmpeg_stream) = &mut *ffmpeg_stream_guard else {
+            return Ok(None);
+        };
+
+        // notify to start download tasks
+        ffmpeg_stream.start_download();
+
+        let Some(reciever) = ffmpeg_stream.refined_data_reciever.clone() else {
+            return Ok(None);
+        };
+
+        let byte_value = reciever.lock().await.recv().await;
+
+        if byte_value.is_none() {
+            if let Some(mut ffmpeg_stream) = ffmpeg_stream_guard.take() {
+                ffmpeg_stream.finish().await?;
+            }
+        }
+
+        Ok(byte_value)
+    }
+
+    /// Sum of the content lengths of both formats, the muxed output is close to it but not exact
+    fn content_length(&self) -> usize {
+        self.content_length as usize
+    }
+}
@@ -1,5 +1,7 @@
 #[cfg(feature = "live")]
 mod live;
+#[cfg(feature = "ffmpeg")]
+mod merged;
 mod non_live;
 
 use async_trait::async_trait;
@@ -9,14 +11,14 @@ use bytes::Bytes;
 use bytes::BytesMut;
 
 #[cfg(feature = "ffmpeg")]
-use std::{process::Stdio, sync::Arc};
+use std::{process::Stdio, sync::Arc, time::Duration};
 
 #[cfg(feature = "ffmpeg")]
 use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt},
-    process::{Child, Command},
+    io::{AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    process::Command,
     sync::{
-        mpsc::{channel, Receiver},
+        mpsc::{channel, Receiver, Sender},
         Mutex, Notify,
     },
     task::JoinHandle,
@@ -24,10 +26,12 @@ use tokio::{
 
 #[cfg(feature = "live")]
 pub use live::{LiveStream, LiveStreamOptions};
-pub use non_live::{NonLiveStream, NonLiveStreamOptions};
+#[cfg(feature = "ffmpeg")]
+pub(crate) use merged::MergedStream;
+pub use non_live::{NonLiveStream, NonLiveStreamOptions, RefreshLink};
 
 #[cfg(feature = "ffmpeg")]
-use crate::constants::DEFAULT_HEADERS;
+use crate::utils::get_download_headers;
 use crate::VideoError;
 
 #[async_trait]
@@ -43,6 +47,37 @@ pub trait Stream {
     fn content_length(&self) -> usize {
         0
     }
+
+    /// Mime type of the stream without codecs (e.g. `audio/webm`), useful as `Content-Type` when proxying the stream
+    ///
+    /// If stream is [`LiveStream`] returns always `video/mp2t`, `None` if the stream is transcoded by FFmpeg
+    fn content_type(&self) -> Option<&str> {
+        None
+    }
+
+    /// Whether the stream is a [`LiveStream`], which has no end
+    fn is_live(&self) -> bool {
+        false
+    }
+
+    /// Download the whole stream into memory and return all the [`Bytes`]
+    ///
+    /// Live streams are refused because they have no end, use [`Stream::chunk`] instead
+    async fn download_to_end(&self) -> Result<Bytes, VideoError> {
+        if self.is_live() {
+            return Err(VideoError::DownloadError(
+                "Stream has unbounded length (live stream), use chunk instead".to_string(),
+            ));
+        }
+
+        let mut buffer = bytes::BytesMut::with_capacity(self.content_length());
+
+        while let Some(chunk) = self.chunk().await? {
+            buffer.extend_from_slice(&chunk);
+        }
+
+        Ok(buffer.freeze())
+    }
 }
 
 #[cfg(feature = "ffmpeg")]
@@ -54,13 +89,38 @@ pub struct FFmpegStreamOptions {
     pub start: u64,
     pub end: u64,
     pub ffmpeg_args: Vec<String>,
+    /// Kill the FFmpeg process if it runs longer than this from the start of the download
+    pub timeout: Option<Duration>,
+    /// Audio format given to FFmpeg as a second input, after the `-i -` input of the link
+    pub audio: Option<FFmpegAudioInput>,
 }
 
+/// Second input of FFmpeg, downloaded concurrently and written to the inherited pipe `pipe:3` on unix.
+/// Other platforms can not pass an extra descriptor to the child, there FFmpeg reads it from a loopback socket
+#[cfg(feature = "ffmpeg")]
+pub struct FFmpegAudioInput {
+    pub link: String,
+    pub content_length: u64,
+}
+
+/// Descriptor of the audio input in the FFmpeg process
+#[cfg(all(feature = "ffmpeg", unix))]
+const AUDIO_INPUT_FD: i32 = 3;
+
+/// FFmpeg connects to the audio socket after probing the first input, a stray client is not waited for longer
+#[cfg(all(feature = "ffmpeg", not(unix)))]
+const AUDIO_INPUT_ACCEPT_TIMEOUT: Duration = Duration::from_secs(30);
+
 #[cfg(feature = "ffmpeg")]
 pub(crate) struct FFmpegStream {
     pub refined_data_reciever: Option<Arc<Mutex<Receiver<Bytes>>>>,
     download_notify: Arc<Notify>,
-    ffmpeg_child: Child,
+    /// Starts the download of the audio input with the download
+    audio_notify: Arc<Notify>,
+    /// Starts the timeout of the FFmpeg process with the download
+    timeout_notify: Arc<Notify>,
+    /// Waits for the FFmpeg process to exit, owns the child so it is killed when the task is aborted
+    wait_task: JoinHandle<Result<(), VideoError>>,
 
     tasks: Vec<JoinHandle<Result<(), VideoError>>>,
 }
@@ -71,17 +131,43 @@ impl FFmpegStream {
         let (tx, mut rx) = channel::<Bytes>(16384);
         let (refined_tx, refined_rx) = channel::<Bytes>(16384);
 
+        let mut ffmpeg_args = options.ffmpeg_args.clone();
+
+        let mut command = Command::new("ffmpeg");
+
+        // Stdin takes only one input, the audio is read by FFmpeg from a pipe or a loopback socket
+        let audio_input = match &options.audio {
+            Some(_) => {
+                let (audio_input, url) = AudioInput::new(&mut command)?;
+
+                let input_index = ffmpeg_args
+                    .iter()
+                    .position(|x| x == "-")
+                    .map(|x| x + 1)
+                    .unwrap_or(0);
+                ffmpeg_args.splice(input_index..input_index, ["-i".to_string(), url]);
+
+                Some(audio_input)
+            }
+            None => None,
+        };
+
         // Spawn FFmpeg process
-        let mut ffmpeg_child = Command::new("ffmpeg")
-            .args(&options.ffmpeg_args)
+        let mut ffmpeg_child = command
+            .args(&ffmpeg_args)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
             .kill_on_drop(true)
             .spawn()
             .map_err(|x| VideoError::FFmpeg(x.to_string()))?;
 
+        // Read end of the pipe is only for FFmpeg, it sees the end of the input when the write end is closed
+        let audio_input = audio_input.map(AudioInput::spawned);
+
         let mut stdin = ffmpeg_child.stdin.take().unwrap();
         let mut stdout = ffmpeg_child.stdout.take().unwrap();
+        let mut stderr = ffmpeg_child.stderr.take().unwrap();
 
         let read_stdout_task = tokio::spawn(async move {
             let mut buffer = vec![0u8; 16384];
@@ -114,79 +200,339 @@ impl FFmpegStream {
 
         let download_notify = Arc::new(Notify::new());
         let download_notify_task = download_notify.clone();
+        let audio_notify = Arc::new(Notify::new());
+        let audio_notify_task = audio_notify.clone();
+        let timeout_notify = Arc::new(Notify::new());
+        let timeout_notify_task = timeout_notify.clone();
+        let timeout = options.timeout;
+
+        let wait_task = tokio::spawn(async move {
+            if timeout.is_some() {
+                timeout_notify_task.notified().await;
+            }
 
-        let download_task = tokio::spawn(async move {
-            let mut end = options.end;
-            let mut start = options.start;
-            let content_length = options.content_length;
-            let client = options.client;
-            let link = options.link;
-            let dl_chunk_size = options.dl_chunk_size;
+            let res = {
+                // stderr is read until the end, otherwise FFmpeg blocks when the pipe is full
+                let wait = async {
+                    let mut output = vec![];
+                    let (status, _) =
+                        tokio::join!(ffmpeg_child.wait(), stderr.read_to_end(&mut output));
 
-            download_notify_task.notified().await;
+                    check_ffmpeg_exit(status, &output)
+                };
 
-            loop {
-                // Nothing else remain send break to finish
-                if end == 0 {
-                    break;
+                match timeout {
+                    Some(timeout) => tokio::time::timeout(timeout, wait).await.ok(),
+                    None => Some(wait.await),
                 }
+            };
 
-                if end >= content_length {
-                    end = 0;
+            match res {
+                Some(res) => res,
+                None => {
+                    let _ = ffmpeg_child.kill().await;
+
+                    Err(VideoError::FFmpeg(format!(
+                        "FFmpeg did not finish in {:?}, the process is killed",
+                        timeout.unwrap_or_default()
+                    )))
                 }
+            }
+        });
 
-                let mut headers = DEFAULT_HEADERS.clone();
+        let mut tasks = vec![];
 
-                let range_end = if end == 0 {
-                    "".to_string()
-                } else {
-                    end.to_string()
-                };
+        if let (Some(audio_input), Some(audio)) = (audio_input, options.audio) {
+            let client = options.client.clone();
+            let dl_chunk_size = options.dl_chunk_size;
 
-                headers.insert(
-                    reqwest::header::RANGE,
-                    format!("bytes={}-{}", start, range_end).parse().unwrap(),
-                );
+            tasks.push(tokio::spawn(async move {
+                audio_notify_task.notified().await;
 
-                let mut response = client
-                    .get(&link)
-                    .headers(headers)
-                    .send()
-                    .await
-                    .map_err(VideoError::ReqwestMiddleware)?
-                    .error_for_status()
-                    .map_err(VideoError::Reqwest)?;
+                let mut writer = audio_input.writer().await?;
 
-                let mut buf: BytesMut = BytesMut::new();
+                let (audio_tx, mut audio_rx) = channel::<Bytes>(16384);
 
-                while let Some(chunk) = response.chunk().await.map_err(VideoError::Reqwest)? {
-                    buf.extend(chunk);
-                }
+                let download = download_ranges(
+                    &client,
+                    &audio.link,
+                    audio.content_length,
+                    dl_chunk_size,
+                    (0, dl_chunk_size),
+                    audio_tx,
+                );
+                let write = async {
+                    while let Some(data) = audio_rx.recv().await {
+                        writer
+                            .write_all(&data)
+                            .await
+                            .map_err(|x| VideoError::FFmpeg(x.to_string()))?;
+                    }
 
-                if end != 0 {
-                    start = end + 1;
+                    // Closing the writer ends the audio input
+                    writer
+                        .shutdown()
+                        .await
+                        .map_err(|x| VideoError::FFmpeg(x.to_string()))
+                };
 
-                    end += dl_chunk_size;
-                }
+                let (download, write) = tokio::join!(download, write);
+                download.and(write)
+            }));
+        }
 
-                tx.send(buf.into())
-                    .await
-                    .map_err(|x| VideoError::FFmpeg(x.to_string()))?;
-            }
+        let client = options.client;
+        let link = options.link;
+        let content_length = options.content_length;
+        let dl_chunk_size = options.dl_chunk_size;
+        let range = (options.start, options.end);
 
-            Ok(())
+        let download_task = tokio::spawn(async move {
+            download_notify_task.notified().await;
+
+            download_ranges(&client, &link, content_length, dl_chunk_size, range, tx).await
         });
 
+        tasks.extend([download_task, write_stdin_task, read_stdout_task]);
+
         Ok(Self {
             refined_data_reciever: Some(Arc::new(Mutex::new(refined_rx))),
             download_notify,
-            ffmpeg_child,
-            tasks: vec![download_task, write_stdin_task, read_stdout_task],
+            audio_notify,
+            timeout_notify,
+            wait_task,
+            tasks,
         })
     }
 
     pub fn start_download(&self) {
         self.download_notify.notify_one();
+        self.audio_notify.notify_one();
+        self.timeout_notify.notify_one();
+    }
+
+    /// Wait for the FFmpeg process after its output is exhausted and return its error, if any
+    pub async fn finish(&mut self) -> Result<(), VideoError> {
+        (&mut self.wait_task)
+            .await
+            .unwrap_or_else(|err| Err(VideoError::FFmpeg(err.to_string())))
+    }
+}
+
+/// Other end of the audio input given to FFmpeg
+#[cfg(all(feature = "ffmpeg", unix))]
+struct AudioInput {
+    /// Read end inherited by FFmpeg, `None` after spawn
+    reader: Option<std::os::fd::OwnedFd>,
+    writer: tokio::net::unix::pipe::Sender,
+}
+
+#[cfg(all(feature = "ffmpeg", unix))]
+impl AudioInput {
+    /// Create the pipe and make its read end [`AUDIO_INPUT_FD`] of the command, returns the input url for FFmpeg
+    fn new(command: &mut Command) -> Result<(Self, String), VideoError> {
+        use std::os::fd::AsRawFd;
+
+        let (writer, reader) =
+            tokio::net::unix::pipe::pipe().map_err(|x| VideoError::FFmpeg(x.to_string()))?;
+        let reader = reader
+            .into_blocking_fd()
+            .map_err(|x| VideoError::FFmpeg(x.to_string()))?;
+        let reader_fd = reader.as_raw_fd();
+
+        // SAFETY: only async-signal-safe `fcntl` and `dup2` run between fork and exec
+        unsafe {
+            command.pre_exec(move || {
+                // Both ends are close-on-exec, `dup2` clears it on the copy. A copy to itself keeps the flag
+                let res = if reader_fd == AUDIO_INPUT_FD {
+                    let flags = libc::fcntl(reader_fd, libc::F_GETFD);
+                    if flags == -1 {
+                        -1
+                    } else {
+                        libc::fcntl(reader_fd, libc::F_SETFD, flags & !libc::FD_CLOEXEC)
+                    }
+                } else {
+                    libc::dup2(reader_fd, AUDIO_INPUT_FD)
+                };
+
+                if res == -1 {
+                    return Err(std::io::Error::last_os_error());
+                }
+
+                Ok(())
+            });
+        }
+
+        Ok((
+            Self {
+                reader: Some(reader),
+                writer,
+            },
+            format!("pipe:{AUDIO_INPUT_FD}"),
+        ))
+    }
+
+    /// Close the read end in this process after FFmpeg is spawned
+    fn spawned(mut self) -> Self {
+        self.reader = None;
+        self
+    }
+
+    async fn writer(self) -> Result<impl AsyncWrite + Unpin, VideoError> {
+        Ok(self.writer)
+    }
+}
+
+/// Other end of the audio input given to FFmpeg
+#[cfg(all(feature = "ffmpeg", not(unix)))]
+struct AudioInput(tokio::net::TcpListener);
+
+#[cfg(all(feature = "ffmpeg", not(unix)))]
+impl AudioInput {
+    /// Bind a loopback socket, returns the input url for FFmpeg
+    fn new(_command: &mut Command) -> Result<(Self, String), VideoError> {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| {
+                listener.set_nonblocking(true)?;
+                Ok(listener)
+            })
+            .map_err(|x| VideoError::FFmpeg(x.to_string()))?;
+        let address = listener
+            .local_addr()
+            .map_err(|x| VideoError::FFmpeg(x.to_string()))?;
+
+        Ok((
+            Self(
+                tokio::net::TcpListener::from_std(listener)
+                    .map_err(|x| VideoError::FFmpeg(x.to_string()))?,
+            ),
+            format!("tcp://{address}"),
+        ))
+    }
+
+    fn spawned(self) -> Self {
+        self
+    }
+
+    /// Accept the first loopback connection, FFmpeg connects after probing the first input
+    async fn writer(self) -> Result<impl AsyncWrite + Unpin, VideoError> {
+        let accept = async {
+            loop {
+                let (socket, peer) = self
+                    .0
+                    .accept()
+                    .await
+                    .map_err(|x| VideoError::FFmpeg(x.to_string()))?;
+
+                if peer.ip().is_loopback() {
+                    return Ok(socket);
+                }
+            }
+        };
+
+        tokio::time::timeout(AUDIO_INPUT_ACCEPT_TIMEOUT, accept)
+            .await
+            .map_err(|_| {
+                VideoError::FFmpeg(format!(
+                    "FFmpeg did not connect to the audio input in {AUDIO_INPUT_ACCEPT_TIMEOUT:?}"
+                ))
+            })?
+    }
+}
+
+/// Download the link from `range` until `content_length` in chunks of `dl_chunk_size` and send them to `tx`
+#[cfg(feature = "ffmpeg")]
+async fn download_ranges(
+    client: &reqwest_middleware::ClientWithMiddleware,
+    link: &str,
+    content_length: u64,
+    dl_chunk_size: u64,
+    range: (u64, u64),
+    tx: Sender<Bytes>,
+) -> Result<(), VideoError> {
+    let (mut start, mut end) = range;
+
+    loop {
+        // Nothing else remain send break to finish
+        if end == 0 || start >= content_length {
+            break;
+        }
+
+        if end >= content_length {
+            end = 0;
+        }
+
+        let mut headers = get_download_headers(link);
+
+        let range_end = if end == 0 {
+            "".to_string()
+        } else {
+            end.to_string()
+        };
+
+        headers.insert(
+            reqwest::header::RANGE,
+            format!("bytes={}-{}", start, range_end).parse().unwrap(),
+        );
+
+        let mut response = client
+            .get(link)
+            .headers(headers)
+            .send()
+            .await
+            .map_err(VideoError::ReqwestMiddleware)?
+            .error_for_status()
+            .map_err(VideoError::Reqwest)?;
+
+        let mut buf: BytesMut = BytesMut::new();
+
+        while let Some(chunk) = response.chunk().await.map_err(VideoError::Reqwest)? {
+            buf.extend(chunk);
+        }
+
+        if end != 0 {
+            start = end + 1;
+
+            end += dl_chunk_size;
+        }
+
+        tx.send(buf.into())
+            .await
+            .map_err(|x| VideoError::FFmpeg(x.to_string()))?;
+    }
+
+    Ok(())
+}
+
+/// Number of the last stderr lines of FFmpeg in the error
+#[cfg(feature = "ffmpeg")]
+const FFMPEG_STDERR_TAIL_LINES: usize = 10;
+
+#[cfg(feature = "ffmpeg")]
+fn check_ffmpeg_exit(
+    status: std::io::Result<std::process::ExitStatus>,
+    stderr: &[u8],
+) -> Result<(), VideoError> {
+    let status = status.map_err(|x| VideoError::FFmpeg(x.to_string()))?;
+
+    if status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(stderr);
+    let lines = stderr
+        .lines()
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty())
+        .collect::<Vec<&str>>();
+    let tail = lines[lines.len().saturating_sub(FFMPEG_STDERR_TAIL_LINES)..].join("\n");
+
+    if tail.is_empty() {
+        Err(VideoError::FFmpeg(format!("FFmpeg exited with {status}")))
+    } else {
+        Err(VideoError::FFmpeg(format!(
+            "FFmpeg exited with {status}:\n{tail}"
+        )))
     }
 }
 
@@ -197,5 +543,8 @@ impl Drop for FFmpegStream {
         for handle in &self.tasks {
             handle.abort();
         }
+
+        // Drops the child, which kills the process
+        self.wait_task.abort();
     }
 }
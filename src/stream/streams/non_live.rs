@@ -1,16 +1,26 @@
 use async_trait::async_trait;
 use bytes::{Bytes, BytesMut};
-
-#[cfg(feature = "ffmpeg")]
-use std::sync::Arc;
-
+use futures_util::future::{join_all, select, Either};
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use tokio::io::{AsyncRead, ReadBuf};
 #[cfg(feature = "ffmpeg")]
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
 
-use crate::constants::{DEFAULT_HEADERS, DEFAULT_MAX_RETRIES};
+use crate::constants::DEFAULT_MAX_RETRIES;
 use crate::stream::streams::Stream;
-use crate::structs::{CustomRetryableStrategy, VideoError};
+use crate::structs::{
+    CancellationToken, CustomRetryableStrategy, RetryAfterMiddleware, VideoError,
+};
+use crate::utils::{clamp_chunk_size, get_download_headers};
 
 #[cfg(feature = "ffmpeg")]
 use crate::structs::FFmpegArgs;
@@ -22,28 +32,94 @@ pub struct NonLiveStreamOptions {
     pub client: Option<reqwest_middleware::ClientWithMiddleware>,
     pub link: String,
     pub content_length: u64,
+    /// Clamped between 64KB and 10MB like [`crate::DownloadOptions::dl_chunk_size`]
     pub dl_chunk_size: u64,
     pub start: u64,
     pub end: u64,
+    pub on_progress: Option<Arc<dyn Fn(u64, u64) + Sync + Send + 'static>>,
+    /// Resolves a fresh link of the same source when the current one is rejected with `403 Forbidden`
+    pub refresh_link: Option<RefreshLink>,
+    /// Number of ranges fetched concurrently. `None` or `1` downloads sequentially
+    pub parallel_connections: Option<u8>,
+    /// Caps the average download rate. `None` downloads as fast as possible
+    pub max_bytes_per_second: Option<u64>,
+    /// Aborts the download when cancelled, see [`crate::DownloadOptions::cancellation`]
+    pub cancellation: Option<CancellationToken>,
+    /// Fails the end of the stream if fewer bytes than the content length were received, see [`crate::DownloadOptions::verify_length`]
+    pub verify_length: bool,
+    /// Mime type of the source without codecs (e.g. `audio/webm`), see [`Stream::content_type`]
+    pub content_type: Option<String>,
 
     #[cfg(feature = "ffmpeg")]
     pub ffmpeg_args: Option<FFmpegArgs>,
 }
 
+pub type RefreshLink =
+    Arc<dyn Fn() -> Pin<Box<dyn Future<Output = Result<String, VideoError>> + Send>> + Sync + Send>;
+
+/// Token bucket which allows bursts up to one second worth of bytes
+struct RateLimiter {
+    bytes_per_second: u64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    fn new(bytes_per_second: u64) -> Self {
+        Self {
+            bytes_per_second,
+            tokens: bytes_per_second as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Consume `len` tokens and return how long to wait until the bucket is no longer in debt
+    fn consume(&mut self, len: u64) -> Duration {
+        let rate = self.bytes_per_second as f64;
+        let now = Instant::now();
+
+        self.tokens =
+            (self.tokens + now.duration_since(self.last_refill).as_secs_f64() * rate).min(rate);
+        self.last_refill = now;
+        self.tokens -= len as f64;
+
+        if self.tokens < 0.0 {
+            Duration::from_secs_f64(-self.tokens / rate)
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
 pub struct NonLiveStream {
-    link: String,
+    link: RwLock<String>,
     content_length: u64,
     dl_chunk_size: u64,
     start: RwLock<u64>,
     end: RwLock<u64>,
     start_static: u64,
     end_static: u64,
+    downloaded: RwLock<u64>,
+    on_progress: Option<Arc<dyn Fn(u64, u64) + Sync + Send + 'static>>,
+    refresh_link: Option<RefreshLink>,
+    parallel_connections: u64,
+    /// Already fetched chunks waiting to be yielded in order
+    buffer: RwLock<VecDeque<Bytes>>,
+    rate_limiter: Option<RwLock<RateLimiter>>,
+    cancellation: Option<CancellationToken>,
+    verify_length: bool,
+    content_type: Option<String>,
+    /// Inclusive last byte of the stream, see [`NonLiveStream::range`]. `None` streams until the end of the source
+    range_end: Option<u64>,
 
     client: reqwest_middleware::ClientWithMiddleware,
 
     #[cfg(feature = "ffmpeg")]
     ffmpeg_args: Vec<String>,
 
+    #[cfg(feature = "ffmpeg")]
+    ffmpeg_timeout: Option<std::time::Duration>,
+
     #[cfg(feature = "ffmpeg")]
     ffmpeg_stream: Arc<Mutex<Option<FFmpegStream>>>,
 }
@@ -64,6 +140,9 @@ impl NonLiveStream {
                 )
                 .build_with_max_retries(DEFAULT_MAX_RETRIES);
             reqwest_middleware::ClientBuilder::new(client)
+                .with(RetryAfterMiddleware {
+                    max_retries: DEFAULT_MAX_RETRIES,
+                })
                 .with(
                     reqwest_retry::RetryTransientMiddleware::new_with_policy_and_strategy(
                         retry_policy,
@@ -73,6 +152,9 @@ impl NonLiveStream {
                 .build()
         };
 
+        // `0` would never advance the requested range
+        let dl_chunk_size = clamp_chunk_size(options.dl_chunk_size);
+
         #[cfg(feature = "ffmpeg")]
         {
             let ffmpeg_args = options
@@ -81,15 +163,19 @@ impl NonLiveStream {
                 .map(|x| x.build())
                 .unwrap_or_default();
 
+            let ffmpeg_timeout = options.ffmpeg_args.as_ref().and_then(|x| x.timeout);
+
             let ffmpeg_stream = if !ffmpeg_args.is_empty() {
                 Arc::new(Mutex::new(Some(FFmpegStream::new(FFmpegStreamOptions {
                     client: client.clone(),
                     link: options.link.clone(),
                     content_length: options.content_length,
-                    dl_chunk_size: options.dl_chunk_size,
+                    dl_chunk_size,
                     start: options.start,
                     end: options.end,
                     ffmpeg_args: ffmpeg_args.clone(),
+                    timeout: ffmpeg_timeout,
+                    audio: None,
                 })?)))
             } else {
                 Arc::new(Mutex::new(None))
@@ -97,14 +183,29 @@ impl NonLiveStream {
 
             Ok(Self {
                 client,
-                link: options.link,
+                link: RwLock::new(options.link),
                 content_length: options.content_length,
-                dl_chunk_size: options.dl_chunk_size,
+                dl_chunk_size,
                 start: RwLock::new(options.start),
                 end: RwLock::new(options.end),
                 start_static: options.start,
                 end_static: options.end,
+                downloaded: RwLock::new(0),
+                on_progress: options.on_progress,
+                refresh_link: options.refresh_link,
+                parallel_connections: options.parallel_connections.unwrap_or(1).max(1) as u64,
+                buffer: RwLock::new(VecDeque::new()),
+                rate_limiter: options
+                    .max_bytes_per_second
+                    .filter(|rate| *rate > 0)
+                    .map(|rate| RwLock::new(RateLimiter::new(rate))),
+                cancellation: options.cancellation,
+                verify_length: options.verify_length,
+                // Output of FFmpeg is not the source container anymore
+                content_type: options.content_type.filter(|_| ffmpeg_args.is_empty()),
+                range_end: None,
                 ffmpeg_args,
+                ffmpeg_timeout,
                 ffmpeg_stream,
             })
         }
@@ -113,19 +214,107 @@ impl NonLiveStream {
         {
             Ok(Self {
                 client,
-                link: options.link,
+                link: RwLock::new(options.link),
                 content_length: options.content_length,
-                dl_chunk_size: options.dl_chunk_size,
+                dl_chunk_size,
                 start: RwLock::new(options.start),
                 end: RwLock::new(options.end),
                 start_static: options.start,
                 end_static: options.end,
+                downloaded: RwLock::new(0),
+                on_progress: options.on_progress,
+                refresh_link: options.refresh_link,
+                parallel_connections: options.parallel_connections.unwrap_or(1).max(1) as u64,
+                buffer: RwLock::new(VecDeque::new()),
+                rate_limiter: options
+                    .max_bytes_per_second
+                    .filter(|rate| *rate > 0)
+                    .map(|rate| RwLock::new(RateLimiter::new(rate))),
+                cancellation: options.cancellation,
+                verify_length: options.verify_length,
+                content_type: options.content_type,
+                range_end: None,
             })
         }
     }
 
+    /// Turn the stream into an [`AsyncRead`] which yields the downloaded bytes in order.
+    /// Leftover bytes of a chunk that don't fit into the read buffer are kept for the next read
+    /// # Example
+    /// ```ignore
+    ///     let mut reader = stream.into_async_read();
+    ///     let mut file = tokio::fs::File::create("video.mp4").await.unwrap();
+    ///
+    ///     tokio::io::copy(&mut reader, &mut file).await.unwrap();
+    /// ```
+    pub fn into_async_read(self) -> impl AsyncRead + Send + Unpin {
+        NonLiveStreamReader {
+            stream: Arc::new(self),
+            leftover: Bytes::new(),
+            pending: None,
+            finished: false,
+        }
+    }
+
+    /// New stream of only the `start..=end` bytes of the same source, e.g. to serve a `Range: bytes=A-B` request of a client.
+    /// `end` defaults to the last byte of the source. Options of the stream (chunk size, parallel connections, rate limit,
+    /// cancellation, ...) are kept, FFmpeg arguments are not applied to the range
+    /// # Example
+    /// ```ignore
+    ///     // Range: bytes=1048576-
+    ///     let range = stream.range(1048576, None).await?;
+    ///
+    ///     while let Some(chunk) = range.chunk().await? {
+    ///         // write the chunk to the response body
+    ///     }
+    /// ```
+    pub async fn range(&self, start: u64, end: Option<u64>) -> Result<NonLiveStream, VideoError> {
+        let last_byte = self.content_length.saturating_sub(1);
+        let end = end.map(|end| end.min(last_byte)).unwrap_or(last_byte);
+
+        if start > end || start >= self.content_length {
+            return Err(VideoError::DownloadError(format!(
+                "Range {start}-{end} is not satisfiable for content length {}",
+                self.content_length
+            )));
+        }
+
+        let max_bytes_per_second = match self.rate_limiter.as_ref() {
+            Some(rate_limiter) => Some(rate_limiter.read().await.bytes_per_second),
+            None => None,
+        };
+
+        let mut stream = NonLiveStream::new(NonLiveStreamOptions {
+            client: Some(self.client.clone()),
+            link: self.link.read().await.clone(),
+            content_length: self.content_length,
+            dl_chunk_size: self.dl_chunk_size,
+            start,
+            end: start + self.dl_chunk_size,
+            on_progress: self.on_progress.clone(),
+            refresh_link: self.refresh_link.clone(),
+            parallel_connections: Some(self.parallel_connections.min(u8::MAX as u64) as u8),
+            max_bytes_per_second,
+            cancellation: self.cancellation.clone(),
+            verify_length: self.verify_length,
+            content_type: self.content_type.clone(),
+            #[cfg(feature = "ffmpeg")]
+            ffmpeg_args: None,
+        })?;
+
+        stream.range_end = Some(end);
+
+        Ok(stream)
+    }
+
+    /// Remaining content length from the start offset to the end of the source (or the end of the range)
     pub fn content_length(&self) -> u64 {
-        self.content_length
+        self.range_limit().saturating_sub(self.start_static)
+    }
+
+    /// Mime type of the source without codecs (e.g. `audio/webm`)
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
     }
 
     async fn end_index(&self) -> u64 {
@@ -135,34 +324,272 @@ impl NonLiveStream {
     async fn start_index(&self) -> u64 {
         *self.start.read().await
     }
+
+    /// Exclusive end of the stream, the content length of the source unless the stream is a range of it
+    fn range_limit(&self) -> u64 {
+        self.range_end
+            .map(|end| (end + 1).min(self.content_length))
+            .unwrap_or(self.content_length)
+    }
+
+    /// Get byte range of the chunk at the given controllers and the controllers of the next chunk.
+    /// Range end `0` means until the end of the content
+    fn next_range(&self, start: u64, end: u64) -> Option<((u64, u64), (u64, u64))> {
+        let limit = self.range_limit();
+
+        // Nothing else remain (start offset beyond the content length also results an empty stream)
+        if end == 0 || start >= limit {
+            return None;
+        }
+
+        if end >= limit {
+            // Ranges of the source must not read past their last byte
+            let last = self.range_end.map(|_| limit - 1).unwrap_or(0);
+
+            return Some(((start, last), (start, 0)));
+        }
+
+        Some(((start, end), (end + 1, end + self.dl_chunk_size)))
+    }
+
+    /// Set controllers to the beginning state
+    async fn reset(&self) {
+        let mut end = self.end.write().await;
+        let mut start = self.start.write().await;
+        let mut downloaded = self.downloaded.write().await;
+        *end = self.end_static;
+        *start = self.start_static;
+        *downloaded = 0;
+    }
+
+    /// Reset the controllers at the end of the stream, fails if the received bytes are fewer than the content length
+    async fn finish(&self) -> Result<Option<Bytes>, VideoError> {
+        let downloaded = *self.downloaded.read().await;
+        let total = self.content_length();
+
+        self.reset().await;
+
+        if self.verify_length && downloaded < total {
+            return Err(VideoError::DownloadError(format!(
+                "Received {downloaded} bytes, expected {total} bytes"
+            )));
+        }
+
+        Ok(None)
+    }
+
+    async fn fetch_range(&self, (start, end): (u64, u64)) -> Result<Bytes, VideoError> {
+        let mut headers = get_download_headers(self.link.read().await.as_str());
+
+        let range_end = if end == 0 {
+            "".to_string()
+        } else {
+            end.to_string()
+        };
+
+        headers.insert(
+            reqwest::header::RANGE,
+            format!("bytes={start}-{range_end}").parse().unwrap(),
+        );
+
+        let mut response = self
+            .client
+            .get(self.link.read().await.as_str())
+            .headers(headers.clone())
+            .send()
+            .await
+            .map_err(VideoError::ReqwestMiddleware)?;
+
+        // Signature of the link may be expired, refresh the link and retry the same range once
+        if response.status() == reqwest::StatusCode::FORBIDDEN {
+            if let Some(refresh_link) = self.refresh_link.as_ref() {
+                trace_warn!(
+                    start,
+                    end,
+                    "Range rejected with 403 Forbidden, refreshing the link"
+                );
+
+                let link = refresh_link().await?;
+
+                response = self
+                    .client
+                    .get(&link)
+                    .headers(headers)
+                    .send()
+                    .await
+                    .map_err(VideoError::ReqwestMiddleware)?;
+
+                *self.link.write().await = link;
+            }
+        }
+
+        let mut response = response.error_for_status().map_err(VideoError::Reqwest)?;
+
+        let mut buf: BytesMut = BytesMut::new();
+
+        while let Some(chunk) = response.chunk().await.map_err(VideoError::Reqwest)? {
+            buf.extend(chunk);
+        }
+
+        Ok(buf.into())
+    }
+
+    /// Fetch next `parallel_connections` ranges concurrently and keep them in order.
+    /// At most `parallel_connections * dl_chunk_size` bytes are buffered at once
+    async fn parallel_chunk(&self) -> Result<Option<Bytes>, VideoError> {
+        let mut buffer = self.buffer.write().await;
+
+        if buffer.is_empty() {
+            let mut controllers = (self.start_index().await, self.end_index().await);
+            let mut ranges = vec![];
+
+            while (ranges.len() as u64) < self.parallel_connections {
+                let Some((range, next)) = self.next_range(controllers.0, controllers.1) else {
+                    break;
+                };
+
+                ranges.push(range);
+                controllers = next;
+            }
+
+            if ranges.is_empty() {
+                // Send None to close
+                return self.finish().await;
+            }
+
+            let chunks = self
+                .cancellable(async {
+                    join_all(ranges.into_iter().map(|range| self.fetch_range(range)))
+                        .await
+                        .into_iter()
+                        .collect::<Result<Vec<Bytes>, VideoError>>()
+                })
+                .await?;
+
+            *self.start.write().await = controllers.0;
+            *self.end.write().await = controllers.1;
+
+            buffer.extend(chunks);
+        }
+
+        let chunk = buffer.pop_front();
+
+        if let Some(chunk) = chunk.as_ref() {
+            self.throttle(chunk.len() as u64).await;
+            self.report_progress(chunk.len() as u64).await;
+        }
+
+        Ok(chunk)
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .map(|x| x.is_cancelled())
+            .unwrap_or(false)
+    }
+
+    /// Drive the future until it completes or the download is cancelled.
+    /// On cancellation the future (and the in-flight request with it) is dropped
+    async fn cancellable<T>(
+        &self,
+        future: impl Future<Output = Result<T, VideoError>>,
+    ) -> Result<T, VideoError> {
+        let Some(cancellation) = self.cancellation.as_ref() else {
+            return future.await;
+        };
+
+        match select(
+            std::pin::pin!(future),
+            std::pin::pin!(cancellation.cancelled()),
+        )
+        .await
+        {
+            Either::Left((result, _)) => result,
+            Either::Right(_) => Err(VideoError::Cancelled),
+        }
+    }
+
+    /// Sleep long enough to keep the average download rate under `max_bytes_per_second`
+    async fn throttle(&self, len: u64) {
+        if let Some(rate_limiter) = self.rate_limiter.as_ref() {
+            let wait = rate_limiter.write().await.consume(len);
+
+            if !wait.is_zero() {
+                let _ = self
+                    .cancellable(async {
+                        tokio::time::sleep(wait).await;
+                        Ok(())
+                    })
+                    .await;
+            }
+        }
+    }
+
+    async fn report_progress(&self, len: u64) {
+        let total = self.content_length();
+        let mut downloaded = self.downloaded.write().await;
+        *downloaded = (*downloaded + len).min(total);
+
+        if let Some(on_progress) = self.on_progress.as_ref().filter(|_| total > 0) {
+            on_progress(*downloaded, total);
+        }
+    }
 }
 
 #[async_trait]
 impl Stream for NonLiveStream {
     async fn chunk(&self) -> Result<Option<Bytes>, VideoError> {
+        if self.is_cancelled() {
+            // Dropping the ffmpeg stream aborts its tasks and kills the child process
+            #[cfg(feature = "ffmpeg")]
+            {
+                *self.ffmpeg_stream.lock().await = None;
+            }
+
+            return Err(VideoError::Cancelled);
+        }
+
         #[cfg(feature = "ffmpeg")]
         {
             if !self.ffmpeg_args.is_empty() {
-                if let Some(ffmpeg_stream) = &mut *self.ffmpeg_stream.lock().await {
+                let mut ffmpeg_stream_guard = self.ffmpeg_stream.lock().await;
+
+                if let Some(ffmpeg_stream) = &mut *ffmpeg_stream_guard {
                     // notify to start download task
                     ffmpeg_stream.start_download();
 
                     if let Some(reciever) = ffmpeg_stream.refined_data_reciever.clone() {
                         let mut reciever = reciever.lock().await;
 
-                        let byte_value = reciever.recv().await;
+                        let byte_value =
+                            match self.cancellable(async { Ok(reciever.recv().await) }).await {
+                                Ok(byte_value) => byte_value,
+                                Err(err) => {
+                                    drop(reciever);
+                                    *ffmpeg_stream_guard = None;
+
+                                    return Err(err);
+                                }
+                            };
 
                         // reset ffmpeg_stream for reuse
                         if byte_value.is_none() {
+                            let res = ffmpeg_stream.finish().await;
+
                             *ffmpeg_stream = FFmpegStream::new(FFmpegStreamOptions {
                                 client: self.client.clone(),
-                                link: self.link.clone(),
+                                link: self.link.read().await.clone(),
                                 content_length: self.content_length,
                                 dl_chunk_size: self.dl_chunk_size,
                                 start: self.start_static,
                                 end: self.end_static,
                                 ffmpeg_args: self.ffmpeg_args.clone(),
+                                timeout: self.ffmpeg_timeout,
+                                audio: None,
                             })?;
+
+                            res?;
                         }
 
                         return Ok(byte_value);
@@ -171,67 +598,87 @@ impl Stream for NonLiveStream {
             }
         }
 
-        let end = self.end_index().await;
+        if self.parallel_connections > 1 {
+            return self.parallel_chunk().await;
+        }
+
+        let Some((range, next)) = self.next_range(self.start_index().await, self.end_index().await)
+        else {
+            // Nothing else remain set controllers to the beginning state and send None to finish
+            return self.finish().await;
+        };
 
-        // Nothing else remain set controllers to the beginning state and send None to finish
-        if end == 0 {
-            let mut end = self.end.write().await;
-            let mut start = self.start.write().await;
-            *end = self.end_static;
-            *start = self.start_static;
+        let buf = self.cancellable(self.fetch_range(range)).await?;
 
-            // Send None to close
-            return Ok(None);
-        }
+        *self.start.write().await = next.0;
+        *self.end.write().await = next.1;
 
-        if end >= self.content_length {
-            let mut end = self.end.write().await;
-            *end = 0;
-        }
+        self.throttle(buf.len() as u64).await;
+        self.report_progress(buf.len() as u64).await;
 
-        let mut headers = DEFAULT_HEADERS.clone();
+        Ok(Some(buf))
+    }
 
-        let end = self.end_index().await;
-        let range_end = if end == 0 {
-            "".to_string()
-        } else {
-            end.to_string()
-        };
+    fn content_length(&self) -> usize {
+        self.content_length() as usize
+    }
 
-        headers.insert(
-            reqwest::header::RANGE,
-            format!("bytes={}-{}", self.start_index().await, range_end)
-                .parse()
-                .unwrap(),
-        );
+    fn content_type(&self) -> Option<&str> {
+        self.content_type()
+    }
+}
 
-        let mut response = self
-            .client
-            .get(&self.link)
-            .headers(headers)
-            .send()
-            .await
-            .map_err(VideoError::ReqwestMiddleware)?
-            .error_for_status()
-            .map_err(VideoError::Reqwest)?;
+type ChunkFuture = Pin<Box<dyn Future<Output = Result<Option<Bytes>, VideoError>> + Send>>;
 
-        let mut buf: BytesMut = BytesMut::new();
+struct NonLiveStreamReader {
+    stream: Arc<NonLiveStream>,
+    leftover: Bytes,
+    pending: Option<ChunkFuture>,
+    finished: bool,
+}
 
-        while let Some(chunk) = response.chunk().await.map_err(VideoError::Reqwest)? {
-            buf.extend(chunk);
-        }
+impl AsyncRead for NonLiveStreamReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = &mut *self;
 
-        if end != 0 {
-            let mut start = self.start.write().await;
-            *start = end + 1;
-            let mut end = self.end.write().await;
-            *end += self.dl_chunk_size;
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
         }
 
-        Ok(Some(buf.into()))
-    }
+        loop {
+            if !this.leftover.is_empty() {
+                let len = this.leftover.len().min(buf.remaining());
+                buf.put_slice(&this.leftover.split_to(len));
 
-    fn content_length(&self) -> usize {
-        self.content_length() as usize
+                return Poll::Ready(Ok(()));
+            }
+
+            // Reading nothing means EOF
+            if this.finished {
+                return Poll::Ready(Ok(()));
+            }
+
+            let stream = this.stream.clone();
+            let pending = this
+                .pending
+                .get_or_insert_with(|| Box::pin(async move { stream.chunk().await }));
+
+            let chunk = match pending.as_mut().poll(cx) {
+                Poll::Ready(chunk) => chunk,
+                Poll::Pending => return Poll::Pending,
+            };
+
+            this.pending = None;
+
+            match chunk {
+                Ok(Some(chunk)) => this.leftover = chunk,
+                Ok(None) => this.finished = true,
+                Err(err) => return Poll::Ready(Err(std::io::Error::other(err))),
+            }
+        }
     }
 }
@@ -1,18 +1,18 @@
 use mime::Mime;
-use serde::{
-    de::{Error, Unexpected},
-    Deserialize, Deserializer, Serialize, Serializer,
-};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{
     borrow::Cow,
     cmp::Ordering,
     fmt::{Debug, Formatter, Result as fmtResult},
     ops::{Bound, RangeBounds},
     str::FromStr,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering as AtomicOrdering},
+        Arc,
+    },
 };
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VideoInfo {
     #[serde(rename = "dashManifestUrl")]
     pub dash_manifest_url: Option<String>,
@@ -21,11 +21,136 @@ pub struct VideoInfo {
     pub formats: Vec<VideoFormat>,
     #[serde(rename = "relatedVideos")]
     pub related_videos: Vec<RelatedVideo>,
+    /// Continuation token of the next related videos page, see [`crate::Video::next_related_videos`]
+    #[serde(rename = "relatedVideosContinuation", default)]
+    pub related_videos_continuation: Option<String>,
     #[serde(rename = "videoDetails")]
     pub video_details: VideoDetails,
+    /// Available caption tracks of the video
+    #[serde(default)]
+    pub captions: Vec<CaptionTrack>,
+    /// Innertube client which the formats were received from, can be passed to [`RequestOptions::innertube_client`].
+    /// [`None`] on JSON of older versions
+    #[serde(rename = "innertubeClient", default)]
+    pub innertube_client: Option<InnerTubeClient>,
+    /// "Most replayed" graph of the video. Empty if the video has not enough views
+    #[serde(default)]
+    pub heatmap: Vec<HeatMarker>,
+    /// Whether the formats were received from the `tv_embedded` client because the video is age restricted or requires login
+    #[serde(rename = "ageRestrictedBypass", default)]
+    pub age_restricted_bypass: bool,
 }
 
-#[derive(Clone, derive_more::Display)]
+impl VideoInfo {
+    /// Serialize to JSON, which can be read back with [`VideoInfo::from_json`] e.g. for caches or IPC
+    pub fn to_json(&self) -> Result<String, VideoError> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserialize from the JSON of [`VideoInfo::to_json`]
+    pub fn from_json(json: &str) -> Result<VideoInfo, VideoError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Get format by exact itag number from already parsed formats
+    pub fn get_format_by_itag(&self, itag: u64) -> Option<VideoFormat> {
+        self.formats.iter().find(|x| x.itag == itag).cloned()
+    }
+
+    /// Get formats in the given container (e.g. `mp4`, `webm`)
+    pub fn formats_with_container(&self, container: &str) -> Vec<VideoFormat> {
+        self.formats
+            .iter()
+            .filter(|x| x.mime_type.container.eq_ignore_ascii_case(container))
+            .cloned()
+            .collect()
+    }
+
+    /// Tile of the highest resolution storyboard shown at `time_ms`, e.g. for seek bar previews
+    /// # Example
+    /// ```ignore
+    ///     let info = video.get_basic_info().await.unwrap();
+    ///
+    ///     if let Some(tile) = info.storyboard_tile_at(90_000) {
+    ///         println!("{} crop {}x{} at ({}, {})", tile.url, tile.width, tile.height, tile.x, tile.y);
+    ///     }
+    /// ```
+    pub fn storyboard_tile_at(&self, time_ms: u64) -> Option<StoryboardTile> {
+        let length_ms = self
+            .video_details
+            .length_seconds
+            .parse::<u64>()
+            .unwrap_or(0)
+            * 1000;
+
+        self.video_details
+            .storyboards
+            .iter()
+            .max_by_key(|x| x.thumbnail_width * x.thumbnail_height)?
+            .tile_at(time_ms, length_ms)
+    }
+
+    /// Get formats which video codec starts with the given codec (e.g. `avc1`, `vp9`, `av01`)
+    pub fn formats_with_video_codec(&self, codec: &str) -> Vec<VideoFormat> {
+        self.formats
+            .iter()
+            .filter(|x| {
+                x.mime_type
+                    .video_codec
+                    .as_ref()
+                    .is_some_and(|x| x.starts_with(codec))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Get formats which audio codec starts with the given codec (e.g. `opus`, `mp4a`)
+    pub fn formats_with_audio_codec(&self, codec: &str) -> Vec<VideoFormat> {
+        self.formats
+            .iter()
+            .filter(|x| {
+                x.mime_type
+                    .audio_codec
+                    .as_ref()
+                    .is_some_and(|x| x.starts_with(codec))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Get formats which audio track is in the given language. Matches the exact language code (e.g. `en-US`)
+    /// or its primary language (e.g. `en`)
+    pub fn formats_with_audio_language(&self, language: &str) -> Vec<VideoFormat> {
+        self.formats
+            .iter()
+            .filter(|x| {
+                x.audio_track.as_ref().is_some_and(|track| {
+                    let track_language = track.language();
+
+                    track_language.eq_ignore_ascii_case(language)
+                        || track_language
+                            .split('-')
+                            .next()
+                            .is_some_and(|x| x.eq_ignore_ascii_case(language))
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Get progressive formats which contain both video and audio
+    pub fn progressive_formats(&self) -> Vec<VideoFormat> {
+        self.formats
+            .iter()
+            .filter(|x| x.has_video && x.has_audio)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Serialized in camelCase (e.g. `"videoAudio"`), [`VideoSearchOptions::Custom`] can not be serialized or deserialized
+#[derive(Clone, derive_more::Display, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum VideoSearchOptions {
     /// Video & Audio
     #[display("Video & Audio")]
@@ -38,6 +163,7 @@ pub enum VideoSearchOptions {
     Audio,
     /// Custom filter
     #[display("Custom")]
+    #[serde(skip)]
     Custom(Arc<dyn Fn(&VideoFormat) -> bool + Sync + Send + 'static>),
 }
 
@@ -70,7 +196,9 @@ impl PartialEq for VideoSearchOptions {
 type CustomVideoQualityComparator =
     Arc<dyn Fn(&VideoFormat, &VideoFormat) -> Ordering + Sync + Send + 'static>;
 
-#[derive(Clone, derive_more::Display)]
+/// Serialized in camelCase (e.g. `"highestAudio"`, `{"itag": 18}`), [`VideoQuality::Custom`] can not be serialized or deserialized
+#[derive(Clone, derive_more::Display, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum VideoQuality {
     /// Highest Video & Audio
     #[display("Highest")]
@@ -90,8 +218,21 @@ pub enum VideoQuality {
     /// Only Lowest Video
     #[display("Lowest Video")]
     LowestVideo,
+    /// Exact format by itag number, filter is ignored
+    #[display("Itag({_0})")]
+    Itag(u64),
+    /// Format which height (e.g. `720` for `720p`) is closest to the given resolution, preferably at or below it.
+    /// Ties are broken by bitrate. Formats without a resolution (audio only) are skipped,
+    /// so use it with [`VideoSearchOptions::Video`] or [`VideoSearchOptions::VideoAudio`] filter
+    #[display("Resolution({_0})")]
+    Resolution(u32),
+    /// Highest quality format which fits into the given size in bytes. The size is the content length of the format
+    /// or estimated from its bitrate and duration. If no format fits, the smallest one is selected
+    #[display("MaxFileSize({_0})")]
+    MaxFileSize(u64),
     /// Custom ranking function and filter
     #[display("Custom")]
+    #[serde(skip)]
     Custom(VideoSearchOptions, CustomVideoQualityComparator),
 }
 
@@ -104,6 +245,9 @@ impl Debug for VideoQuality {
             VideoQuality::LowestAudio => write!(f, "LowestAudio"),
             VideoQuality::HighestVideo => write!(f, "HighestVideo"),
             VideoQuality::LowestVideo => write!(f, "LowestVideo"),
+            VideoQuality::Itag(itag) => write!(f, "Itag({itag})"),
+            VideoQuality::Resolution(height) => write!(f, "Resolution({height})"),
+            VideoQuality::MaxFileSize(size) => write!(f, "MaxFileSize({size})"),
             VideoQuality::Custom(filter, _) => write!(f, "Custom({filter:?})"),
         }
     }
@@ -118,6 +262,9 @@ impl PartialEq for VideoQuality {
             (VideoQuality::LowestAudio, VideoQuality::LowestAudio) => true,
             (VideoQuality::HighestVideo, VideoQuality::HighestVideo) => true,
             (VideoQuality::LowestVideo, VideoQuality::LowestVideo) => true,
+            (VideoQuality::Itag(a), VideoQuality::Itag(b)) => a == b,
+            (VideoQuality::Resolution(a), VideoQuality::Resolution(b)) => a == b,
+            (VideoQuality::MaxFileSize(a), VideoQuality::MaxFileSize(b)) => a == b,
             (VideoQuality::Custom(i, a), VideoQuality::Custom(j, b)) => {
                 // Compare the function pointer
                 Arc::ptr_eq(a, b) && i == j
@@ -137,6 +284,20 @@ pub struct VideoOptions {
     pub download_options: DownloadOptions,
     #[derivative(PartialEq = "ignore")]
     pub request_options: RequestOptions,
+    /// Codec family ranked before the others regardless of the quality, e.g. `avc1` (H.264) for hardware decoding
+    /// or video editors, `vp9`, `av01`, `mp4a`, `opus`. Matched case-insensitively against the start of the format codecs.
+    /// Formats of other codecs are still chosen if no format of the codec matches the options.
+    ///
+    /// # Example
+    /// ```ignore
+    ///     let video_options = VideoOptions {
+    ///          quality: VideoQuality::HighestVideo,
+    ///          filter: VideoSearchOptions::Video,
+    ///          prefer_codec: Some("avc1".to_string()),
+    ///          ..Default::default()
+    ///     };
+    /// ```
+    pub prefer_codec: Option<String>,
 }
 
 impl Default for VideoOptions {
@@ -146,6 +307,7 @@ impl Default for VideoOptions {
             filter: VideoSearchOptions::Audio,
             download_options: DownloadOptions::default(),
             request_options: RequestOptions::default(),
+            prefer_codec: None,
         }
     }
 }
@@ -162,12 +324,213 @@ impl From<VideoOptions> for Cow<'static, VideoOptions> {
     }
 }
 
+impl VideoOptions {
+    /// Create a [`VideoOptionsBuilder`] starting from the default options
+    ///
+    /// # Example
+    /// ```ignore
+    ///     let video_options = VideoOptions::builder()
+    ///         .quality(VideoQuality::Lowest)
+    ///         .filter(VideoSearchOptions::Audio)
+    ///         .request_options(RequestOptions::builder().max_retries(5).build()?)
+    ///         .build();
+    /// ```
+    pub fn builder() -> VideoOptionsBuilder {
+        VideoOptionsBuilder::default()
+    }
+}
+
+/// Fluent builder of [`VideoOptions`]
+#[derive(Clone, Default)]
+pub struct VideoOptionsBuilder(VideoOptions);
+
+impl VideoOptionsBuilder {
+    pub fn quality(mut self, quality: VideoQuality) -> Self {
+        self.0.quality = quality;
+        self
+    }
+
+    pub fn filter(mut self, filter: VideoSearchOptions) -> Self {
+        self.0.filter = filter;
+        self
+    }
+
+    pub fn download_options(mut self, download_options: DownloadOptions) -> Self {
+        self.0.download_options = download_options;
+        self
+    }
+
+    pub fn request_options(mut self, request_options: RequestOptions) -> Self {
+        self.0.request_options = request_options;
+        self
+    }
+
+    pub fn prefer_codec(mut self, prefer_codec: impl Into<String>) -> Self {
+        self.0.prefer_codec = Some(prefer_codec.into());
+        self
+    }
+
+    pub fn build(self) -> VideoOptions {
+        self.0
+    }
+}
+
+type ProgressCallback = Arc<dyn Fn(u64, u64) + Sync + Send + 'static>;
+
 /// Video download options
-#[derive(Clone, PartialEq, Debug, Default, derive_more::Display)]
-#[display("DownloadOptions(download chunk size: {dl_chunk_size:?})")]
+#[derive(Clone, Default, derive_more::Display, derivative::Derivative)]
+#[display(
+    "DownloadOptions(download chunk size: {dl_chunk_size:?}, start offset: {start_offset:?}, parallel connections: {parallel_connections:?}, max bytes per second: {max_bytes_per_second:?}, verify length: {verify_length})"
+)]
+#[derivative(Debug, PartialEq)]
 pub struct DownloadOptions {
-    /// Maximum chunk size on per request
+    /// Maximum chunk size on per request. Defaults to 10MB, which is also the maximum since Youtube throttles bigger ranges.
+    /// Values below 64KB (including `0`) are raised to 64KB and values above 10MB are lowered to 10MB
     pub dl_chunk_size: Option<u64>,
+    /// Byte offset to start the download from. Useful to resume an interrupted download.
+    /// If the offset is beyond the content length, the stream will be empty.
+    ///
+    /// # Example
+    /// ```ignore
+    ///     // Resume after the first 1MB already written to disk
+    ///     let video_options = VideoOptions {
+    ///          download_options: DownloadOptions {
+    ///               start_offset: Some(1048576),
+    ///                ..Default::default()
+    ///          },
+    ///          ..Default::default()
+    ///     };
+    /// ```
+    pub start_offset: Option<u64>,
+    /// Progress callback invoked after each downloaded chunk with `(downloaded_so_far, total_content_length)`.
+    /// Not invoked for live streams, their total content length is unknown.
+    ///
+    /// # Example
+    /// ```ignore
+    ///     let video_options = VideoOptions {
+    ///          download_options: DownloadOptions {
+    ///               on_progress: Some(Arc::new(|downloaded, total| {
+    ///                   println!("{downloaded}/{total}");
+    ///               })),
+    ///                ..Default::default()
+    ///          },
+    ///          ..Default::default()
+    ///     };
+    /// ```
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    pub on_progress: Option<ProgressCallback>,
+    /// Number of connections to download ranges of the source concurrently. `None` or `1` downloads sequentially.
+    /// Chunks are still yielded in order, at most `parallel_connections` chunks are buffered in memory at once.
+    /// Ignored for live streams and ffmpeg streams.
+    ///
+    /// # Example
+    /// ```ignore
+    ///     let video_options = VideoOptions {
+    ///          download_options: DownloadOptions {
+    ///               parallel_connections: Some(4),
+    ///                ..Default::default()
+    ///          },
+    ///          ..Default::default()
+    ///     };
+    /// ```
+    pub parallel_connections: Option<u8>,
+    /// Maximum average download rate in bytes per second. `None` (or `0`) disables throttling.
+    /// Ignored for live streams and ffmpeg streams.
+    ///
+    /// # Example
+    /// ```ignore
+    ///     // Limit to 1MB/s
+    ///     let video_options = VideoOptions {
+    ///          download_options: DownloadOptions {
+    ///               max_bytes_per_second: Some(1048576),
+    ///                ..Default::default()
+    ///          },
+    ///          ..Default::default()
+    ///     };
+    /// ```
+    pub max_bytes_per_second: Option<u64>,
+    /// Token to abort the download from another task. After [`CancellationToken::cancel`] is called,
+    /// the in-flight request is dropped and `chunk()` returns [`VideoError::Cancelled`].
+    /// FFmpeg child process of the stream (if any) is killed.
+    ///
+    /// # Example
+    /// ```ignore
+    ///     let token = CancellationToken::new();
+    ///     let video_options = VideoOptions {
+    ///          download_options: DownloadOptions {
+    ///               cancellation: Some(token.clone()),
+    ///                ..Default::default()
+    ///          },
+    ///          ..Default::default()
+    ///     };
+    ///
+    ///     // Somewhere else, e.g. when user hits "stop"
+    ///     token.cancel();
+    /// ```
+    #[derivative(PartialEq = "ignore")]
+    pub cancellation: Option<CancellationToken>,
+    /// Return [`VideoError::DownloadError`] at the end of the stream if fewer bytes than the content length were received,
+    /// e.g. the connection was dropped in the middle of a chunk. Ignored for live streams and ffmpeg streams.
+    ///
+    /// # Example
+    /// ```ignore
+    ///     let video_options = VideoOptions {
+    ///          download_options: DownloadOptions {
+    ///               verify_length: true,
+    ///                ..Default::default()
+    ///          },
+    ///          ..Default::default()
+    ///     };
+    /// ```
+    pub verify_length: bool,
+}
+
+impl DownloadOptions {
+    /// Clamped [`DownloadOptions::dl_chunk_size`], falls back to the default chunk size if not set
+    pub(crate) fn chunk_size(&self) -> u64 {
+        self.dl_chunk_size
+            .map(crate::utils::clamp_chunk_size)
+            .unwrap_or(crate::constants::DEFAULT_DL_CHUNK_SIZE)
+    }
+}
+
+/// Cloneable handle to signal cancellation of the downloads it is passed to
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<CancellationState>);
+
+#[derive(Debug, Default)]
+struct CancellationState {
+    cancelled: AtomicBool,
+    notify: tokio::sync::Notify,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel all downloads using this token
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, AtomicOrdering::SeqCst);
+        self.0.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(AtomicOrdering::SeqCst)
+    }
+
+    /// Wait until the token is cancelled
+    pub(crate) async fn cancelled(&self) {
+        let mut notified = std::pin::pin!(self.0.notify.notified());
+        // Register before checking the flag to not miss a cancel in between
+        notified.as_mut().enable();
+
+        if self.is_cancelled() {
+            return;
+        }
+
+        notified.await;
+    }
 }
 
 #[derive(Clone, Debug, Default, derive_more::Display)]
@@ -209,7 +572,27 @@ pub struct RequestOptions {
     ///         ..Default::default()
     ///     };
     /// ```
+    #[cfg(not(target_arch = "wasm32"))]
     pub proxy: Option<reqwest::Proxy>,
+    /// Pool of [`reqwest::Proxy`] to rotate. A random proxy is picked every time a client is built
+    /// (e.g. for every [`crate::Video`]), so info and download requests of the same video go through the same proxy
+    /// since download links are tied to the IP address. `proxy` is ignored if the pool is not empty
+    ///
+    /// # Example
+    /// ```ignore
+    ///     let video_options = VideoOptions {
+    ///         request_options: RequestOptions {
+    ///              proxies: Some(vec![
+    ///                   reqwest::Proxy::all("http://first.prox").unwrap(),
+    ///                   reqwest::Proxy::all("http://second.prox").unwrap(),
+    ///              ]),
+    ///              ..Default::default()
+    ///         },
+    ///         ..Default::default()
+    ///     };
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub proxies: Option<Vec<reqwest::Proxy>>,
     /// Cookies String
     ///
     /// # Example
@@ -233,6 +616,19 @@ pub struct RequestOptions {
     ///     };
     /// ```
     pub ipv6_block: Option<String>,
+    /// Routed IPv4 block (CIDR) to pick a random source address from, ignored if `ipv6_block` is set
+    ///
+    /// # Example
+    /// ```ignore
+    ///     let video_options = VideoOptions {
+    ///          request_options: RequestOptions {
+    ///               ipv4_block: Some("203.0.113.0/24".to_string()),
+    ///                ..Default::default()
+    ///          },
+    ///          ..Default::default()
+    ///     };
+    /// ```
+    pub ipv4_block: Option<String>,
     /// Number of retries to allow per web request (ie, per chunk downloaded)
     /// Default is [`crate::constants::DEFAULT_MAX_RETRIES`].
     ///
@@ -251,6 +647,362 @@ pub struct RequestOptions {
     /// Supply a YouTube Proof of Origin token. Use at your own risk.
     /// See https://github.com/yt-dlp/yt-dlp/wiki/Extractors#po-token-guide for more information.
     pub po_token: Option<String>,
+    /// Visitor data bound to the [`RequestOptions::po_token`]. If not provided, visitor data of the watch page is used
+    pub visitor_data: Option<String>,
+    /// Innertube client to use on the player request instead of the default client fallback sequence.
+    /// Formats of some clients (`Web`, `WebCreator`, `TvEmbedded`) need the n-sig and signature deciphering,
+    /// if the player script cannot be deciphered their formats may be throttled or fail with 403
+    ///
+    /// # Example
+    /// ```ignore
+    ///     let video_options = VideoOptions {
+    ///          request_options: RequestOptions {
+    ///               innertube_client: Some(InnerTubeClient::TvEmbedded),
+    ///                ..Default::default()
+    ///          },
+    ///          ..Default::default()
+    ///     };
+    /// ```
+    pub innertube_client: Option<InnerTubeClient>,
+    /// Re-resolve the format link when a download request is rejected with `403 Forbidden` (e.g. expired signature)
+    /// and continue the download from the last byte offset. Default is `false`
+    pub refresh_on_403: bool,
+    /// Reuse the result of [`crate::Video::get_info`] and [`crate::Video::get_basic_info`] for this duration
+    /// instead of fetching and deciphering again. Cached info is refetched earlier if its format URLs are about to expire.
+    /// Default is `None` (no caching)
+    ///
+    /// # Example
+    /// ```ignore
+    ///     let video_options = VideoOptions {
+    ///          request_options: RequestOptions {
+    ///               info_cache_ttl: Some(std::time::Duration::from_secs(600)),
+    ///                ..Default::default()
+    ///          },
+    ///          ..Default::default()
+    ///     };
+    /// ```
+    pub info_cache_ttl: Option<std::time::Duration>,
+    /// File to cache the extracted decipher and n transform functions of the player script across processes.
+    /// The cache is refreshed when YouTube serves a new player script. Ignored on `wasm32`. Default is `None` (only cached in memory)
+    ///
+    /// # Example
+    /// ```ignore
+    ///     let video_options = VideoOptions {
+    ///          request_options: RequestOptions {
+    ///               player_cache_path: Some(std::env::temp_dir().join("rusty_ytdl_player.json")),
+    ///                ..Default::default()
+    ///          },
+    ///          ..Default::default()
+    ///     };
+    /// ```
+    pub player_cache_path: Option<std::path::PathBuf>,
+    /// Timeout of each request, from connecting until the response body has finished.
+    /// Applies to info and download requests (per chunk). Ignored if [`RequestOptions::client`] is provided.
+    /// Default is `None` (no timeout)
+    ///
+    /// # Example
+    /// ```ignore
+    ///     let video_options = VideoOptions {
+    ///          request_options: RequestOptions {
+    ///               timeout: Some(std::time::Duration::from_secs(30)),
+    ///                ..Default::default()
+    ///          },
+    ///          ..Default::default()
+    ///     };
+    /// ```
+    pub timeout: Option<std::time::Duration>,
+    /// Timeout of establishing the connection, so a dead proxy or IPv6 exit fails fast and the request is retried
+    /// instead of consuming the whole [`RequestOptions::timeout`]. Ignored if [`RequestOptions::client`] is provided.
+    /// Default is `None` (no timeout)
+    ///
+    /// # Example
+    /// ```ignore
+    ///     let video_options = VideoOptions {
+    ///          request_options: RequestOptions {
+    ///               connect_timeout: Some(std::time::Duration::from_secs(5)),
+    ///               read_timeout: Some(std::time::Duration::from_secs(15)),
+    ///                ..Default::default()
+    ///          },
+    ///          ..Default::default()
+    ///     };
+    /// ```
+    pub connect_timeout: Option<std::time::Duration>,
+    /// Timeout of each read of the response, reset after every received chunk, so only stalled responses fail
+    /// and long downloads are not limited. Ignored if [`RequestOptions::client`] is provided. Default is `None` (no timeout)
+    pub read_timeout: Option<std::time::Duration>,
+    /// Keep the raw `signatureCipher`/`cipher` strings and untransformed `n` param in [`VideoFormat::url`]
+    /// instead of deciphering them, to debug signature issues. Such URLs can not be downloaded directly. Default is `false`
+    pub skip_url_decryption: bool,
+    /// Headers sent on every info, download and search request. They override the default headers of the library
+    /// (e.g. `User-Agent`, `Accept-Language`) and the [`RequestOptions::cookies`] if they contain a `Cookie` header.
+    /// Also applied if [`RequestOptions::client`] is provided
+    ///
+    /// # Example
+    /// ```ignore
+    ///     let mut headers = reqwest::header::HeaderMap::new();
+    ///     headers.insert(reqwest::header::ACCEPT_LANGUAGE, "de-DE,de;q=0.9".parse().unwrap());
+    ///
+    ///     let video_options = VideoOptions {
+    ///          request_options: RequestOptions {
+    ///               headers: Some(headers),
+    ///                ..Default::default()
+    ///          },
+    ///          ..Default::default()
+    ///     };
+    /// ```
+    pub headers: Option<reqwest::header::HeaderMap>,
+}
+
+impl RequestOptions {
+    /// Create a [`RequestOptionsBuilder`] starting from the default options
+    ///
+    /// # Example
+    /// ```ignore
+    ///     let request_options = RequestOptions::builder()
+    ///         .cookies("key1=value1; key2=value2")
+    ///         .ipv6_block("2001:4::/48")
+    ///         .max_retries(5)
+    ///         .build()?;
+    /// ```
+    pub fn builder() -> RequestOptionsBuilder {
+        RequestOptionsBuilder::default()
+    }
+
+    /// Build the client used on the info, download and search requests.
+    /// Proxy (or a random one of the proxy pool), IPv6 block and cookies are applied unless a [`RequestOptions::client`] is provided,
+    /// transient errors are retried up to [`RequestOptions::max_retries`] times in both cases.
+    /// On `wasm32` the browser's fetch is used, so IPv6 block and timeouts are ignored
+    pub fn build_client(&self) -> Result<reqwest_middleware::ClientWithMiddleware, VideoError> {
+        let client = match self.client.clone() {
+            Some(client) => client,
+            None => {
+                let mut client_builder = reqwest::Client::builder();
+
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    use rand::seq::SliceRandom;
+
+                    let proxy = match self.proxies.as_ref().filter(|x| !x.is_empty()) {
+                        Some(proxies) => proxies.choose(&mut rand::thread_rng()),
+                        None => self.proxy.as_ref(),
+                    };
+
+                    if let Some(proxy) = proxy {
+                        client_builder = client_builder.proxy(proxy.clone());
+                    }
+
+                    if let Some(ipv6_block) = &self.ipv6_block {
+                        let ipv6 = crate::utils::get_random_v6_ip(ipv6_block)?;
+                        client_builder = client_builder.local_address(ipv6);
+                    } else if let Some(ipv4_block) = &self.ipv4_block {
+                        let ipv4 = crate::utils::get_random_v4_ip(ipv4_block)?;
+                        client_builder = client_builder.local_address(ipv4);
+                    }
+
+                    if let Some(timeout) = self.timeout {
+                        client_builder = client_builder.timeout(timeout);
+                    }
+
+                    if let Some(connect_timeout) = self.connect_timeout {
+                        client_builder = client_builder.connect_timeout(connect_timeout);
+                    }
+
+                    if let Some(read_timeout) = self.read_timeout {
+                        client_builder = client_builder.read_timeout(read_timeout);
+                    }
+                }
+
+                if let Some(cookie) = &self.cookies {
+                    let mut headers = reqwest::header::HeaderMap::new();
+                    headers.insert(
+                        reqwest::header::COOKIE,
+                        reqwest::header::HeaderValue::from_str(cookie)
+                            .map_err(|_x| VideoError::CookieError)?,
+                    );
+
+                    client_builder = client_builder.default_headers(headers)
+                }
+
+                client_builder.build().map_err(VideoError::Reqwest)?
+            }
+        };
+
+        let max_retries = self
+            .max_retries
+            .unwrap_or(crate::constants::DEFAULT_MAX_RETRIES);
+
+        let retry_policy = reqwest_retry::policies::ExponentialBackoff::builder()
+            .retry_bounds(
+                std::time::Duration::from_millis(1000),
+                std::time::Duration::from_millis(30000),
+            )
+            .build_with_max_retries(max_retries);
+
+        let mut client_builder = reqwest_middleware::ClientBuilder::new(client);
+
+        if let Some(headers) = self.headers.clone().filter(|x| !x.is_empty()) {
+            client_builder = client_builder.with(HeadersMiddleware { headers });
+        }
+
+        Ok(client_builder
+            .with(RetryAfterMiddleware { max_retries })
+            .with(
+                reqwest_retry::RetryTransientMiddleware::new_with_policy_and_strategy(
+                    retry_policy,
+                    CustomRetryableStrategy,
+                ),
+            )
+            .build())
+    }
+}
+
+/// Fluent builder of [`RequestOptions`], [`RequestOptionsBuilder::build`] validates the options
+#[derive(Clone, Default)]
+pub struct RequestOptionsBuilder(RequestOptions);
+
+impl RequestOptionsBuilder {
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.0.client = Some(client);
+        self
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.0.proxy = Some(proxy);
+        self
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn proxies(mut self, proxies: Vec<reqwest::Proxy>) -> Self {
+        self.0.proxies = Some(proxies);
+        self
+    }
+
+    pub fn cookies(mut self, cookies: impl Into<String>) -> Self {
+        self.0.cookies = Some(cookies.into());
+        self
+    }
+
+    pub fn ipv6_block(mut self, ipv6_block: impl Into<String>) -> Self {
+        self.0.ipv6_block = Some(ipv6_block.into());
+        self
+    }
+
+    pub fn ipv4_block(mut self, ipv4_block: impl Into<String>) -> Self {
+        self.0.ipv4_block = Some(ipv4_block.into());
+        self
+    }
+
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.0.max_retries = Some(max_retries);
+        self
+    }
+
+    pub fn po_token(mut self, po_token: impl Into<String>) -> Self {
+        self.0.po_token = Some(po_token.into());
+        self
+    }
+
+    pub fn visitor_data(mut self, visitor_data: impl Into<String>) -> Self {
+        self.0.visitor_data = Some(visitor_data.into());
+        self
+    }
+
+    pub fn innertube_client(mut self, innertube_client: InnerTubeClient) -> Self {
+        self.0.innertube_client = Some(innertube_client);
+        self
+    }
+
+    pub fn refresh_on_403(mut self, refresh_on_403: bool) -> Self {
+        self.0.refresh_on_403 = refresh_on_403;
+        self
+    }
+
+    pub fn info_cache_ttl(mut self, info_cache_ttl: std::time::Duration) -> Self {
+        self.0.info_cache_ttl = Some(info_cache_ttl);
+        self
+    }
+
+    pub fn player_cache_path(mut self, player_cache_path: impl Into<std::path::PathBuf>) -> Self {
+        self.0.player_cache_path = Some(player_cache_path.into());
+        self
+    }
+
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.0.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, connect_timeout: std::time::Duration) -> Self {
+        self.0.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    pub fn read_timeout(mut self, read_timeout: std::time::Duration) -> Self {
+        self.0.read_timeout = Some(read_timeout);
+        self
+    }
+
+    pub fn skip_url_decryption(mut self, skip_url_decryption: bool) -> Self {
+        self.0.skip_url_decryption = skip_url_decryption;
+        self
+    }
+
+    pub fn headers(mut self, headers: reqwest::header::HeaderMap) -> Self {
+        self.0.headers = Some(headers);
+        self
+    }
+
+    /// Validate and build the [`RequestOptions`]
+    /// - Cookies must be a valid header value ([`VideoError::CookieError`])
+    /// - IPv6 block must be a valid IPv6 subnet ([`VideoError::InvalidIPv6Format`], [`VideoError::InvalidIPv6Subnet`])
+    /// - IPv4 block must be a valid IPv4 subnet ([`VideoError::InvalidIPv4Format`], [`VideoError::InvalidIPv4Subnet`])
+    pub fn build(self) -> Result<RequestOptions, VideoError> {
+        if let Some(cookies) = self.0.cookies.as_ref() {
+            reqwest::header::HeaderValue::from_str(cookies)
+                .map_err(|_x| VideoError::CookieError)?;
+        }
+
+        if let Some(ipv6_block) = self.0.ipv6_block.as_ref() {
+            crate::utils::get_random_v6_ip(ipv6_block)?;
+        }
+
+        if let Some(ipv4_block) = self.0.ipv4_block.as_ref() {
+            crate::utils::get_random_v4_ip(ipv4_block)?;
+        }
+
+        Ok(self.0)
+    }
+}
+
+/// Innertube clients used on the player request, mirrors [`crate::constants::INNERTUBE_CLIENT`] keys
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InnerTubeClient {
+    Web,
+    Ios,
+    Android,
+    WebCreator,
+    /// Can access age restricted videos (unless the uploader has disabled the 'allow embedding' option)
+    TvEmbedded,
+}
+
+impl InnerTubeClient {
+    pub(crate) fn key(&self) -> &'static str {
+        match self {
+            InnerTubeClient::Web => "web",
+            InnerTubeClient::Ios => "ios",
+            InnerTubeClient::Android => "android",
+            InnerTubeClient::WebCreator => "web_creator",
+            InnerTubeClient::TvEmbedded => "tv_embedded",
+        }
+    }
+}
+
+impl std::fmt::Display for InnerTubeClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.key())
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -288,6 +1040,12 @@ pub enum VideoError {
     /// Invalid IPv6 subnet
     #[error("Invalid IPv6 subnet")]
     InvalidIPv6Subnet,
+    /// Invalid IPv4 format
+    #[error("Invalid IPv4 format")]
+    InvalidIPv4Format,
+    /// Invalid IPv4 subnet
+    #[error("Invalid IPv4 subnet")]
+    InvalidIPv4Subnet,
     /// M3U8 parse error
     #[error("M3U8 Parse Error: {0}")]
     M3U8ParseError(String),
@@ -297,6 +1055,21 @@ pub enum VideoError {
     /// Playlist body cannot parsed
     #[error("Playlist body cannot parsed")]
     PlaylistBodyCannotParsed,
+    /// Playlist does not exist
+    #[error("Playlist not found: {0}")]
+    PlaylistNotFound(String),
+    /// Playlist is private
+    #[error("Playlist is private: {0}")]
+    PlaylistIsPrivate(String),
+    /// Playlist can not be viewed, the alert message of YouTube is included (e.g. `This playlist type is unviewable.`)
+    #[error("Playlist is unavailable: {0}")]
+    PlaylistUnavailable(String),
+    /// URL is not channel
+    #[error("{0} is not a channel URL")]
+    IsNotChannel(String),
+    /// Channel body cannot parsed
+    #[error("Channel body cannot parsed")]
+    ChannelBodyCannotParsed,
     /// Download error
     #[error("Download Error: {0}")]
     DownloadError(String),
@@ -306,9 +1079,15 @@ pub enum VideoError {
     /// Decryption error
     #[error("Decryption Error: {0}")]
     DecryptionError(String),
+    /// Remux error
+    #[error("Remux Error: {0}")]
+    RemuxError(String),
     /// Hex encdode error
     #[error(transparent)]
     HexError(#[from] hex::FromHexError),
+    /// JSON serialization error
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
     /// Child process error
     #[error("Process Error: {0}")]
     ChildProcessError(String),
@@ -318,12 +1097,117 @@ pub enum VideoError {
     /// Provided cookie contains invalid header value characters, an error is returned. Only visible ASCII characters (32-127) are permitted.
     #[error("Provided cookie contains invalid header value characters, an error is returned. Only visible ASCII characters (32-127) are permitted")]
     CookieError,
+    /// Caption track not found for the language
+    #[error("Caption not found for language: {0}")]
+    CaptionNotFound(String),
+    /// Video has no transcript
+    #[error("Transcript not found")]
+    TranscriptNotFound,
+    /// Live stream is offline
+    #[error("Live stream is offline")]
+    LiveStreamOffline,
+    /// Live stream or premiere is scheduled but has not started yet
+    #[error("Live stream has not started yet: {0}")]
+    LiveStreamNotStarted(String),
+    /// Video is available to the channel members only
+    #[error("Video is available to channel members only")]
+    MembersOnly,
+    /// Video has been removed by the uploader or YouTube
+    #[error("Video has been removed: {0}")]
+    Removed(String),
+    /// Video is not available in the country of the client. `available_countries` lists ISO 3166 country codes
+    /// the video is available in (empty if unknown), a proxy in one of them may help
+    #[error(
+        "Video is not available in your country, available countries: {available_countries:?}"
+    )]
+    GeoRestricted { available_countries: Vec<String> },
+    /// Download cancelled through the [`CancellationToken`]
+    #[error("Download cancelled")]
+    Cancelled,
     /// FFmpeg command error
     #[error("FFmpeg command error: {0}")]
     #[cfg(feature = "ffmpeg")]
     FFmpeg(String),
 }
 
+impl VideoError {
+    /// Whether the error is transient and the same request may succeed when retried:
+    /// timeouts, connection errors, `5XX`, `429 Too Many Requests` and `403 Forbidden` on `videoplayback` links.
+    /// Errors of the video itself (e.g. [`VideoError::VideoIsPrivate`]) or invalid input
+    /// (e.g. [`VideoError::FormatNotFound`], [`VideoError::InvalidIPv6Format`]) are not retryable
+    /// # Example
+    /// ```ignore
+    ///     let info = loop {
+    ///         match video.get_info().await {
+    ///             Err(err) if err.is_retryable() => tokio::time::sleep(Duration::from_secs(5)).await,
+    ///             res => break res,
+    ///         }
+    ///     };
+    /// ```
+    pub fn is_retryable(&self) -> bool {
+        let Some(error) = self.reqwest_error() else {
+            return false;
+        };
+
+        if error.is_timeout() {
+            return true;
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if error.is_connect() {
+            return true;
+        }
+
+        match error.status() {
+            Some(status) if status == reqwest::StatusCode::FORBIDDEN => error
+                .url()
+                .is_some_and(|url| url.path().contains("videoplayback")),
+            Some(status) => {
+                status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            // Body or decode errors during the transfer
+            None => error.is_body() || error.is_decode(),
+        }
+    }
+
+    /// HTTP status code of the failed request (e.g. `403`, `429`) if the error is one of the reqwest based variants.
+    /// Errors raised by middlewares are searched for the wrapped [`reqwest::Error`] too
+    /// # Example
+    /// ```ignore
+    ///     if let Err(err) = video.get_info().await {
+    ///         if err.status_code() == Some(429) {
+    ///             println!("Rate limited");
+    ///         }
+    ///     }
+    /// ```
+    pub fn status_code(&self) -> Option<u16> {
+        self.reqwest_error()?.status().map(|status| status.as_u16())
+    }
+
+    /// Wrapped [`reqwest::Error`] of the reqwest based variants
+    fn reqwest_error(&self) -> Option<&reqwest::Error> {
+        match self {
+            VideoError::Reqwest(error) => Some(error),
+            VideoError::ReqwestMiddleware(error) => middleware_reqwest_error(error),
+            _ => None,
+        }
+    }
+}
+
+/// Wrapped [`reqwest::Error`] of the middleware error, searched in the source chain if a middleware raised it
+fn middleware_reqwest_error(error: &reqwest_middleware::Error) -> Option<&reqwest::Error> {
+    match error {
+        reqwest_middleware::Error::Reqwest(error) => Some(error),
+        reqwest_middleware::Error::Middleware(error) => error.chain().find_map(|source| {
+            source.downcast_ref::<reqwest::Error>().or_else(|| {
+                source
+                    .downcast_ref::<reqwest_middleware::Error>()
+                    .and_then(middleware_reqwest_error)
+            })
+        }),
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VideoFormat {
     /// Video format itag number
@@ -368,6 +1252,9 @@ pub struct VideoFormat {
     pub audio_bitrate: Option<u64>, // LIVE HLS VIDEO ONLY
     #[serde(rename = "loudnessDb")]
     pub loudness_db: Option<f64>, // AUDIO ONLY
+    /// Audio language track of the format, only on videos with multiple audio tracks (original + dubs)
+    #[serde(rename = "audioTrack")]
+    pub audio_track: Option<AudioTrack>, // AUDIO ONLY
     /// Video format URL
     pub url: String,
     /// Video format has video or not
@@ -387,6 +1274,35 @@ pub struct VideoFormat {
     pub is_dash_mpd: bool,
 }
 
+impl VideoFormat {
+    /// Size of the format in bytes from the content length, or estimated from the bitrate and duration.
+    /// `length_seconds` (e.g. [`VideoDetails::length_seconds`]) is used if the format has no approximate duration
+    /// # Example
+    /// ```ignore
+    ///     let length_seconds = info.video_details.length_seconds.parse::<u64>().ok();
+    ///
+    ///     for format in &info.formats {
+    ///         println!("{}: {:?}", format.itag, format.estimated_file_size(length_seconds));
+    ///     }
+    /// ```
+    pub fn estimated_file_size(&self, length_seconds: Option<u64>) -> Option<u64> {
+        self.content_length
+            .as_ref()
+            .and_then(|x| x.parse::<u64>().ok())
+            .filter(|x| *x > 0)
+            .or_else(|| {
+                let duration_ms = self
+                    .approx_duration_ms
+                    .as_ref()
+                    .and_then(|x| x.parse::<u64>().ok())
+                    .or(length_seconds.map(|x| x * 1000))?;
+                let bitrate = self.average_bitrate.unwrap_or(self.bitrate);
+
+                Some(bitrate * duration_ms / 8000).filter(|x| *x > 0)
+            })
+    }
+}
+
 impl From<StreamingDataFormat> for VideoFormat {
     fn from(value: StreamingDataFormat) -> Self {
         Self {
@@ -416,6 +1332,7 @@ impl From<StreamingDataFormat> for VideoFormat {
             audio_channels: value.audio_channels,
             audio_bitrate: value.audio_bitrate,
             loudness_db: value.loudness_db,
+            audio_track: value.audio_track.clone(),
             url: value.url.clone().unwrap_or_default(),
             has_video: false,
             has_audio: false,
@@ -426,6 +1343,17 @@ impl From<StreamingDataFormat> for VideoFormat {
     }
 }
 
+/// Variant stream of the HLS master playlist
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct HlsVariant {
+    pub itag: String,
+    pub url: String,
+    pub bandwidth: Option<u64>,
+    /// `(width, height)`
+    pub resolution: Option<(u64, u64)>,
+    pub frame_rate: Option<f64>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RangeObject {
     #[serde(rename = "start")]
@@ -443,6 +1371,26 @@ pub struct ColorInfo {
     pub matrix_coefficients: String,
 }
 
+/// Audio language track of a format
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AudioTrack {
+    /// Track id, language code followed by the track number (e.g. `en-US.4`)
+    pub id: String,
+    /// Display name of the track (e.g. `English (United States) original`)
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    /// Whether the track is the default (original) audio of the video
+    #[serde(rename = "audioIsDefault", default)]
+    pub is_default: bool,
+}
+
+impl AudioTrack {
+    /// Language code of the track (e.g. `en-US`)
+    pub fn language(&self) -> &str {
+        self.id.split('.').next().unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct VideoDetails {
     pub author: Option<Author>,
@@ -497,6 +1445,9 @@ pub struct VideoDetails {
     #[serde(rename = "isLiveContent")]
     pub is_live_content: bool,
     pub thumbnails: Vec<Thumbnail>,
+    /// Clipped portion of the video, set if the video is created from a clip with [`crate::Video::from_clip`]
+    #[serde(default)]
+    pub clip: Option<Clip>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -533,6 +1484,28 @@ pub struct Author {
     pub subscriber_count: u64,
 }
 
+/// YouTube Clip (`youtube.com/clip/...`), a portion of its parent video
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Clip {
+    pub id: String,
+    #[serde(rename = "startTimeMs")]
+    pub start_time_ms: u64,
+    #[serde(rename = "endTimeMs")]
+    pub end_time_ms: u64,
+}
+
+impl Clip {
+    /// Start of the clip in the parent video, e.g. for `FFmpegArgs::start`
+    pub fn start(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.start_time_ms)
+    }
+
+    /// End of the clip in the parent video, e.g. for `FFmpegArgs::end`
+    pub fn end(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.end_time_ms)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Chapter {
     pub title: String,
@@ -540,6 +1513,132 @@ pub struct Chapter {
     pub start_time: i32,
 }
 
+/// Segment of the "most replayed" graph
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeatMarker {
+    #[serde(rename = "startMillis")]
+    pub start_millis: u64,
+    #[serde(rename = "durationMillis")]
+    pub duration_millis: u64,
+    /// Normalized replay intensity between `0` and `1`
+    #[serde(rename = "intensityScore")]
+    pub intensity_score: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaptionTrack {
+    #[serde(rename = "languageCode")]
+    pub language_code: String,
+    pub name: String,
+    #[serde(rename = "baseUrl")]
+    pub base_url: String,
+    /// Caption track is auto generated (ASR) or not
+    #[serde(rename = "isAutoGenerated")]
+    pub is_auto_generated: bool,
+}
+
+/// Availability of a video, see [`crate::Video::check_availability`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Availability {
+    /// Video is playable
+    Available,
+    /// Video is private
+    Private,
+    /// Video has been removed or does not exist
+    Removed,
+    /// Video requires sign in to confirm the age
+    AgeRestricted,
+    /// Video is available to the channel members only
+    MembersOnly,
+    /// Live stream or premiere has not started yet
+    LiveUpcoming,
+    /// Live stream has ended or is offline without a scheduled start
+    LiveOffline,
+    /// Video is not available in the country of the client, see [`VideoError::GeoRestricted`]
+    GeoRestricted { available_countries: Vec<String> },
+    /// Video is not playable for another reason (bot check, rental, ...) with the reason of YouTube
+    Unavailable(String),
+}
+
+/// Comment sort order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, derive_more::Display)]
+pub enum CommentSortBy {
+    /// Top comments
+    #[default]
+    #[display("Top")]
+    Top,
+    /// Newest comments first
+    #[display("Newest")]
+    Newest,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+#[display("CommentOptions(limit: {limit}, sort_by: {sort_by})")]
+pub struct CommentOptions {
+    /// Maximum number of top level comments to fetch
+    pub limit: u64,
+    pub sort_by: CommentSortBy,
+}
+
+impl Default for CommentOptions {
+    fn default() -> Self {
+        Self {
+            limit: 100,
+            sort_by: CommentSortBy::Top,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: String,
+    pub author: String,
+    #[serde(rename = "authorChannelId")]
+    pub author_channel_id: String,
+    pub text: String,
+    #[serde(rename = "publishedTime")]
+    pub published_time: String,
+    #[serde(rename = "likeCount")]
+    pub like_count: u64,
+    #[serde(rename = "replyCount")]
+    pub reply_count: u64,
+}
+
+/// Line of the transcript panel of a video, see [`crate::Video::get_transcript`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    #[serde(rename = "startMs")]
+    pub start_ms: u64,
+    #[serde(rename = "durationMs")]
+    pub duration_ms: u64,
+    pub text: String,
+}
+
+/// Decipher diagnostics of the player script, useful to investigate `403` download errors
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct DecipherDiagnostics {
+    /// Player script URL which the functions extracted from
+    #[serde(rename = "playerUrl")]
+    pub player_url: String,
+    /// `(name, body)` of the extracted functions the formats are deciphered with,
+    /// the signature decipher function first and the n transform function second. Missing ones are not listed
+    pub functions: Vec<(String, String)>,
+}
+
+/// Caption download formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display)]
+pub enum CaptionFormat {
+    /// YouTube timed text XML format
+    #[display("srv3")]
+    Srv3,
+    /// WebVTT format
+    #[display("vtt")]
+    Vtt,
+    /// SubRip format, converted from WebVTT
+    #[display("srt")]
+    Srt,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StoryBoard {
     #[serde(rename = "templateUrl")]
@@ -557,6 +1656,51 @@ pub struct StoryBoard {
     pub storyboard_count: i32,
 }
 
+impl StoryBoard {
+    /// Tile of the thumbnail shown at `time_ms` with the sprite sheet URL and its crop rectangle.
+    /// `length_ms` is the video length, used when the storyboard has no interval (thumbnails are spread evenly)
+    pub fn tile_at(&self, time_ms: u64, length_ms: u64) -> Option<StoryboardTile> {
+        if self.thumbnail_count <= 0 || self.columns <= 0 || self.rows <= 0 {
+            return None;
+        }
+
+        let thumbnail_count = self.thumbnail_count as u64;
+        let columns = self.columns as u64;
+        let interval = if self.interval > 0 {
+            self.interval as u64
+        } else {
+            length_ms / thumbnail_count
+        };
+
+        let index = time_ms
+            .checked_div(interval)
+            .unwrap_or(0)
+            .min(thumbnail_count - 1);
+        let per_sheet = columns * self.rows as u64;
+        let position = index % per_sheet;
+
+        Some(StoryboardTile {
+            url: self
+                .template_url
+                .replace("$M", &(index / per_sheet).to_string()),
+            x: (position % columns) as u32 * self.thumbnail_width as u32,
+            y: (position / columns) as u32 * self.thumbnail_height as u32,
+            width: self.thumbnail_width as u32,
+            height: self.thumbnail_height as u32,
+        })
+    }
+}
+
+/// Thumbnail of a [`StoryBoard`], the rectangle at `x`, `y` with `width` and `height` of the sprite sheet image at `url`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoryboardTile {
+    pub url: String,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Thumbnail {
     pub width: u64,
@@ -564,6 +1708,21 @@ pub struct Thumbnail {
     pub url: String,
 }
 
+/// Thumbnail selection options
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, derive_more::Display)]
+pub enum ThumbnailOptions {
+    /// Highest resolution thumbnail
+    #[default]
+    #[display("Highest")]
+    Highest,
+    /// Lowest resolution thumbnail
+    #[display("Lowest")]
+    Lowest,
+    /// Thumbnail with the nearest width to the given width
+    #[display("Width({_0})")]
+    Width(u64),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Embed {
     #[serde(rename = "flashSecureUrl")]
@@ -705,12 +1864,17 @@ impl Serialize for MimeType {
     where
         S: Serializer,
     {
-        let s = format!(
-            r#"{}/{}; codecs="{}""#,
-            self.mime.type_(),
-            self.mime.subtype(),
-            self.codecs.join(", "),
-        );
+        // Without codecs param, an empty one would be parsed back as an empty codec
+        let s = if self.codecs.is_empty() {
+            format!("{}/{}", self.mime.type_(), self.mime.subtype())
+        } else {
+            format!(
+                r#"{}/{}; codecs="{}""#,
+                self.mime.type_(),
+                self.mime.subtype(),
+                self.codecs.join(", "),
+            )
+        };
 
         s.serialize(serializer)
     }
@@ -723,52 +1887,135 @@ impl<'de> Deserialize<'de> for MimeType {
     {
         let s = String::deserialize(deserializer)?;
 
-        let mime: Mime = Mime::from_str(&s).map_err(|_| {
-            D::Error::invalid_value(
-                Unexpected::Str(&s),
-                &r#"valid mime type format must be `(\w+/\w+);\scodecs="([a-zA-Z-0-9.,\s]*)"`"#,
-            )
-        })?;
+        Ok(MimeType::parse(&s))
+    }
+}
 
-        let codecs: Vec<String> = mime
-            .get_param("codecs")
-            .map(|x| x.as_str().split(", ").map(|x| x.to_string()).collect())
-            .unwrap_or_default();
+impl MimeType {
+    /// Parse mime type like `video/mp4; codecs="avc1.4d401f, mp4a.40.2"`.
+    /// Unknown codec labels must not fail the whole response, so on an invalid mime type the parts are parsed best-effort
+    /// and [`MimeType::mime`] falls back to the essence (e.g. `video/mp4`) or `application/octet-stream`
+    fn parse(s: &str) -> MimeType {
+        let essence = s.split(';').next().unwrap_or_default().trim();
+        let (type_, subtype) = essence.split_once('/').unwrap_or((essence, ""));
+
+        let (mime, codecs): (Mime, Vec<String>) = match Mime::from_str(s) {
+            Ok(mime) => {
+                let codecs = mime
+                    .get_param("codecs")
+                    .map(|x| x.as_str().split(", ").map(|x| x.to_string()).collect())
+                    .unwrap_or_default();
+
+                (mime, codecs)
+            }
+            Err(_) => {
+                let codecs = s
+                    .split_once("codecs=")
+                    .and_then(|(_, codecs)| {
+                        codecs
+                            .trim_start_matches(|c: char| c == '"' || c.is_whitespace())
+                            .split(['"', ';'])
+                            .next()
+                    })
+                    .map(|codecs| {
+                        codecs
+                            .split(',')
+                            .map(|x| x.trim().to_string())
+                            .filter(|x| !x.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                let mime = Mime::from_str(essence).unwrap_or(mime::APPLICATION_OCTET_STREAM);
+
+                (mime, codecs)
+            }
+        };
 
-        let container: String = mime.subtype().to_string();
+        let container: String = if mime == mime::APPLICATION_OCTET_STREAM {
+            subtype.to_lowercase()
+        } else {
+            mime.subtype().to_string()
+        };
 
-        let video_codec = if mime.type_() == mime::VIDEO {
+        let video_codec = if type_.eq_ignore_ascii_case("video") {
             codecs.first().cloned()
         } else {
             None
         };
 
-        let audio_codec = if mime.type_() == mime::AUDIO {
+        let audio_codec = if type_.eq_ignore_ascii_case("audio") {
             codecs.first().cloned()
         } else {
             codecs.get(1).cloned()
         };
 
-        Ok(MimeType {
+        MimeType {
             mime,
             container,
             codecs,
             video_codec,
             audio_codec,
-        })
+        }
     }
 }
 
 #[cfg(feature = "ffmpeg")]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct FFmpegArgs {
     pub format: Option<String>,
     pub audio_filter: Option<String>,
     pub video_filter: Option<String>,
+    /// Audio encoder like `libmp3lame` or `aac`, `copy` keeps the source audio
+    pub audio_codec: Option<String>,
+    /// Audio bitrate in kbps
+    pub audio_bitrate: Option<u32>,
+    /// Drop the video of the output
+    pub no_video: bool,
+    /// Start position of the output in the media, everything before is trimmed
+    pub start: Option<std::time::Duration>,
+    /// End position of the output in the media (not the length of the output), everything after is trimmed
+    pub end: Option<std::time::Duration>,
+    /// Maximum duration of the FFmpeg process from the start of the download. If it is exceeded (e.g. stalled pipe),
+    /// the process is killed and the stream returns [`VideoError::FFmpeg`]. `None` waits until FFmpeg exits
+    pub timeout: Option<std::time::Duration>,
 }
 
 #[cfg(feature = "ffmpeg")]
 impl FFmpegArgs {
+    /// Transcode the audio to MP3 with the bitrate in kbps (e.g. `192`)
+    pub fn to_mp3(bitrate: u32) -> Self {
+        Self {
+            format: Some("mp3".to_string()),
+            audio_codec: Some("libmp3lame".to_string()),
+            audio_bitrate: Some(bitrate),
+            no_video: true,
+            ..Default::default()
+        }
+    }
+
+    /// Transcode the audio to AAC with the bitrate in kbps (e.g. `128`).
+    /// Output is raw ADTS (`.aac`) because MP4 can not be written to a pipe
+    pub fn to_aac(bitrate: u32) -> Self {
+        Self {
+            format: Some("adts".to_string()),
+            audio_codec: Some("aac".to_string()),
+            audio_bitrate: Some(bitrate),
+            no_video: true,
+            ..Default::default()
+        }
+    }
+
+    /// Copy the audio without transcoding into Matroska audio (`.mka`), which accepts any source codec
+    pub fn extract_audio() -> Self {
+        Self {
+            format: Some("matroska".to_string()),
+            audio_codec: Some("copy".to_string()),
+            no_video: true,
+            ..Default::default()
+        }
+    }
+
     pub fn build(&self) -> Vec<String> {
         let mut args: Vec<String> = vec![];
 
@@ -777,6 +2024,20 @@ impl FFmpegArgs {
             args.push(format.to_string());
         }
 
+        if self.no_video {
+            args.push("-vn".to_string());
+        }
+
+        if let Some(audio_codec) = &self.audio_codec {
+            args.push("-c:a".to_string());
+            args.push(audio_codec.to_string());
+        }
+
+        if let Some(audio_bitrate) = self.audio_bitrate {
+            args.push("-b:a".to_string());
+            args.push(format!("{audio_bitrate}k"));
+        }
+
         if let Some(audio_filter) = &self.audio_filter {
             args.push("-af".to_string());
             args.push(audio_filter.to_string());
@@ -787,19 +2048,43 @@ impl FFmpegArgs {
             args.push(video_filter.to_string());
         }
 
-        if self.format.is_some() || self.audio_filter.is_some() || self.video_filter.is_some() {
+        let has_args = self.format.is_some()
+            || self.audio_filter.is_some()
+            || self.video_filter.is_some()
+            || self.audio_codec.is_some()
+            || self.audio_bitrate.is_some()
+            || self.no_video
+            || self.start.is_some()
+            || self.end.is_some();
+
+        if has_args {
+            // Input is a pipe which is not seekable, so trimming is applied as output option (after `-i`)
+            // that decodes and discards until the position. Also frame accurate unlike input seeking
+            let mut trim_args: Vec<String> = vec![];
+
+            if let Some(start) = self.start {
+                trim_args.push("-ss".to_string());
+                trim_args.push(format!("{:.3}", start.as_secs_f64()));
+            }
+
+            if let Some(end) = self.end {
+                trim_args.push("-to".to_string());
+                trim_args.push(format!("{:.3}", end.as_secs_f64()));
+            }
+
             args = [
                 vec![
                     // input as stdin
                     "-i".to_string(),
                     // aliases of pipe:0
                     "-".to_string(),
-                    // loggers
+                    // loggers, errors are printed to stderr which is reported on a failed exit
                     "-analyzeduration".to_string(),
                     "0".to_string(),
                     "-loglevel".to_string(),
-                    "0".to_string(),
+                    "error".to_string(),
                 ],
+                trim_args,
                 args,
             ]
             .concat();
@@ -824,6 +2109,30 @@ pub struct PlayerResponse {
     pub video_details: Option<PlayerResponseVideoDetails>,
     #[serde(rename = "storyboards")]
     pub storyboards: Option<PlayerResponseStoryboards>,
+    #[serde(rename = "captions")]
+    pub captions: Option<PlayerResponseCaptions>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PlayerResponseCaptions {
+    #[serde(rename = "playerCaptionsTracklistRenderer")]
+    pub player_captions_tracklist_renderer: Option<PlayerCaptionsTracklistRenderer>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PlayerCaptionsTracklistRenderer {
+    #[serde(rename = "captionTracks")]
+    pub caption_tracks: Option<Vec<PlayerCaptionTrack>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PlayerCaptionTrack {
+    #[serde(rename = "baseUrl")]
+    pub base_url: Option<String>,
+    pub name: Option<serde_json::Value>,
+    #[serde(rename = "languageCode")]
+    pub language_code: Option<String>,
+    pub kind: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -994,6 +2303,8 @@ pub struct StreamingDataFormat {
     pub audio_bitrate: Option<u64>, // LIVE HLS VIDEO ONLY
     #[serde(rename = "loudnessDb")]
     pub loudness_db: Option<f64>, // AUDIO ONLY
+    #[serde(rename = "audioTrack")]
+    pub audio_track: Option<AudioTrack>, // AUDIO ONLY
     /// Video format URL
     pub url: Option<String>,
     #[serde(rename = "signatureCipher")]
@@ -1017,6 +2328,8 @@ pub struct PlayabilityStatus {
     pub reason: Option<String>,
     #[serde(rename = "errorScreen")]
     pub error_screen: Option<ErrorScreen>,
+    #[serde(rename = "liveStreamability")]
+    pub live_streamability: Option<serde_json::Value>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -1031,6 +2344,8 @@ pub struct YTConfig {
     pub sts: Option<u64>,
     #[serde(rename = "WEB_PLAYER_CONTEXT_CONFIGS")]
     pub web_player_context_configs: Option<serde_json::Value>,
+    #[serde(rename = "VISITOR_DATA")]
+    pub visitor_data: Option<String>,
 }
 
 pub struct CustomRetryableStrategy;
@@ -1048,16 +2363,96 @@ impl reqwest_retry::RetryableStrategy for CustomRetryableStrategy {
     }
 }
 
+/// Override the headers of every request with [`RequestOptions::headers`]
+pub(crate) struct HeadersMiddleware {
+    pub headers: reqwest::header::HeaderMap,
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl reqwest_middleware::Middleware for HeadersMiddleware {
+    async fn handle(
+        &self,
+        mut req: reqwest::Request,
+        extensions: &mut http::Extensions,
+        next: reqwest_middleware::Next<'_>,
+    ) -> reqwest_middleware::Result<reqwest::Response> {
+        let headers = req.headers_mut();
+
+        // Replace all values of the overridden headers, names with multiple values are kept as multiple values
+        for name in self.headers.keys() {
+            headers.remove(name);
+        }
+
+        for (name, value) in self.headers.iter() {
+            headers.append(name, value.clone());
+        }
+
+        next.run(req, extensions).await
+    }
+}
+
+/// Retry `429 Too Many Requests` and `503 Service Unavailable` responses after the delay of their `Retry-After` header,
+/// capped at [`crate::constants::MAX_RETRY_AFTER`]. Responses without the header are retried by [`CustomRetryableStrategy`]
+pub(crate) struct RetryAfterMiddleware {
+    pub max_retries: u32,
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl reqwest_middleware::Middleware for RetryAfterMiddleware {
+    async fn handle(
+        &self,
+        req: reqwest::Request,
+        extensions: &mut http::Extensions,
+        next: reqwest_middleware::Next<'_>,
+    ) -> reqwest_middleware::Result<reqwest::Response> {
+        let mut retries = 0;
+
+        loop {
+            // Streaming bodies can not be cloned, so they are not retried
+            let Some(duplicate_req) = req.try_clone() else {
+                return next.run(req, extensions).await;
+            };
+
+            let response = next.clone().run(duplicate_req, extensions).await?;
+
+            match crate::utils::get_retry_after(&response) {
+                Some(delay) if retries < self.max_retries => {
+                    retries += 1;
+
+                    let delay = delay.min(crate::constants::MAX_RETRY_AFTER);
+                    trace_warn!(
+                        status = response.status().as_u16(),
+                        retry = retries,
+                        delay_ms = delay.as_millis() as u64,
+                        "Retrying after the delay requested by the server"
+                    );
+                    #[cfg(not(target_arch = "wasm32"))]
+                    tokio::time::sleep(delay).await;
+                    #[cfg(target_arch = "wasm32")]
+                    wasmtimer::tokio::sleep(delay).await;
+                }
+                _ => return Ok(response),
+            }
+        }
+    }
+}
+
 /// Custom request success retry strategy.
 ///
 /// Will only retry if:
 /// * The status was 5XX (server error)
 /// * The status was 4XX (client error)
+/// * The response has no `Retry-After` header, see [`RetryAfterMiddleware`]
 ///
 /// Note that success here means that the request finished without interruption, not that it was logically OK.
 fn custom_on_request_success(success: &reqwest::Response) -> Option<reqwest_retry::Retryable> {
     let status = success.status();
-    if status.is_server_error() || status.is_client_error() {
+    if crate::utils::get_retry_after(success).is_some() {
+        // Retried by the `RetryAfterMiddleware` with the delay requested by the server
+        None
+    } else if status.is_server_error() || status.is_client_error() {
         Some(reqwest_retry::Retryable::Transient)
     } else if status.is_success() {
         None
@@ -8,20 +8,26 @@ use std::{
     borrow::Cow,
     cmp::{min, Ordering},
     collections::HashMap,
-    net::IpAddr,
+    net::{IpAddr, Ipv4Addr},
+    path::Path,
 };
 use tokio::sync::RwLock;
 use urlencoding::decode;
 
 use crate::{
     constants::{
-        AGE_RESTRICTED_URLS, AUDIO_ENCODING_RANKS, BASE_URL, FORMATS, IPV6_REGEX, PARSE_INT_REGEX,
-        VALID_QUERY_DOMAINS, VIDEO_ENCODING_RANKS,
+        AGE_RESTRICTED_URLS, AUDIO_ENCODING_RANKS, BASE_URL, DEFAULT_HEADERS, FORMATS,
+        INNERTUBE_CLIENT, IPV6_REGEX, JS_LOOP_ITERATION_LIMIT, JS_RECURSION_LIMIT,
+        MAX_DL_CHUNK_SIZE, MIN_DL_CHUNK_SIZE, PARSE_INT_REGEX, VALID_QUERY_DOMAINS,
+        VIDEO_ENCODING_RANKS,
+    },
+    info_extras::{
+        get_author, get_category, get_chapters, get_dislikes, get_likes, get_storyboards, get_tags,
     },
-    info_extras::{get_author, get_chapters, get_dislikes, get_likes, get_storyboards},
     structs::{
-        Embed, PlayerResponse, StreamingDataFormat, StringUtils, VideoDetails, VideoError,
-        VideoFormat, VideoOptions, VideoQuality, VideoSearchOptions, YTConfig,
+        Availability, Clip, Embed, HlsVariant, PlayerResponse, StreamingDataFormat, StringUtils,
+        VideoDetails, VideoError, VideoFormat, VideoOptions, VideoQuality, VideoSearchOptions,
+        YTConfig,
     },
 };
 
@@ -37,10 +43,12 @@ pub fn get_html5player(body: &str) -> Option<String> {
         .map(|cap| cap.as_str().to_string())
 }
 
+/// Parse formats of the player response. If `format_functions` is `None`, URLs are not deciphered
+/// and the raw URL or signature cipher is kept
 #[cfg_attr(feature = "performance_analysis", flamer::flame)]
 pub fn parse_video_formats(
     info: &PlayerResponse,
-    format_functions: Vec<(String, String)>,
+    format_functions: Option<Vec<(String, String)>>,
 ) -> Option<Vec<VideoFormat>> {
     if let Some(streaming_data) = info.streaming_data.as_ref() {
         let formats = streaming_data.formats.clone().unwrap_or_default();
@@ -49,7 +57,9 @@ pub fn parse_video_formats(
         let mut formats: Vec<StreamingDataFormat> =
             formats.into_iter().chain(adaptive_formats).collect();
 
+        // JS contexts are built once and reused for all formats
         let mut n_transform_cache: HashMap<String, String> = HashMap::new();
+        let mut n_transform_context: Option<(String, Context)> = None;
         let mut cipher_cache: Option<(String, Context)> = None;
 
         let well_formated_formats: Vec<VideoFormat> = formats
@@ -57,12 +67,21 @@ pub fn parse_video_formats(
             .filter(|format| format.mime_type.is_some())
             .map(|format| {
                 let mut video_format = VideoFormat::from(format.clone());
-                video_format.url = set_download_url(
-                    format,
-                    format_functions.clone(),
-                    &mut n_transform_cache,
-                    &mut cipher_cache,
-                );
+                video_format.url = match format_functions.as_ref() {
+                    Some(format_functions) => set_download_url(
+                        format,
+                        format_functions,
+                        &mut n_transform_cache,
+                        &mut n_transform_context,
+                        &mut cipher_cache,
+                    ),
+                    None => format
+                        .url
+                        .clone()
+                        .or(format.signature_cipher.clone())
+                        .or(format.cipher.clone())
+                        .unwrap_or_default(),
+                };
                 add_format_meta(&mut video_format);
                 video_format
             })
@@ -75,18 +94,21 @@ pub fn parse_video_formats(
 }
 
 #[cfg_attr(feature = "performance_analysis", flamer::flame)]
-pub fn parse_live_video_formats(unformated_formats: Vec<(String, String)>) -> Vec<VideoFormat> {
-    let formats: Vec<VideoFormat> = unformated_formats
+pub fn parse_live_video_formats(variants: Vec<HlsVariant>) -> Vec<VideoFormat> {
+    let formats: Vec<VideoFormat> = variants
         .into_iter()
-        .filter_map(|(itag, url)| {
-            FORMATS.get(&itag as &str).map(|static_format| {
+        .filter_map(|variant| {
+            FORMATS.get(&variant.itag as &str).map(|static_format| {
                 let streaming_data_format = StreamingDataFormat {
-                    itag: Some(itag.parse::<u64>().unwrap_or(0)),
+                    itag: Some(variant.itag.parse::<u64>().unwrap_or(0)),
                     mime_type: Some(static_format.mime_type.clone()),
-                    bitrate: static_format.bitrate,
+                    bitrate: variant.bandwidth.or(static_format.bitrate),
+                    width: variant.resolution.map(|x| x.0),
+                    height: variant.resolution.map(|x| x.1),
+                    fps: variant.frame_rate.map(|x| x.round() as u64),
                     quality_label: static_format.quality_label.clone(),
                     audio_bitrate: static_format.audio_bitrate,
-                    url: Some(url),
+                    url: Some(variant.url),
                     ..Default::default()
                 };
 
@@ -100,6 +122,87 @@ pub fn parse_live_video_formats(unformated_formats: Vec<(String, String)>) -> Ve
     formats
 }
 
+#[cfg_attr(feature = "performance_analysis", flamer::flame)]
+pub fn parse_dash_video_formats(body: &str) -> Vec<VideoFormat> {
+    // HTML parser lowercases tag and attribute names, so MPD `<Representation mimeType="...">` becomes `representation[mimetype]`
+    let document = Html::parse_fragment(body);
+    let adaptation_set_selector = Selector::parse("adaptationset").unwrap();
+    let representation_selector = Selector::parse("representation").unwrap();
+    let base_url_selector = Selector::parse("baseurl").unwrap();
+
+    let mut formats: Vec<VideoFormat> = vec![];
+
+    for adaptation_set in document.select(&adaptation_set_selector) {
+        let adaptation_set_mime_type = adaptation_set.value().attr("mimetype");
+
+        for representation in adaptation_set.select(&representation_selector) {
+            let attr = |name: &str| representation.value().attr(name);
+
+            let itag = match attr("id").and_then(|x| x.parse::<u64>().ok()) {
+                Some(itag) => itag,
+                None => continue,
+            };
+
+            let url = representation
+                .select(&base_url_selector)
+                .next()
+                .map(|x| x.text().collect::<String>().trim().to_string())
+                .unwrap_or_default();
+
+            if url.is_empty() {
+                continue;
+            }
+
+            let static_format = FORMATS.get(itag.to_string().as_str());
+
+            let mime_type = attr("mimetype")
+                .or(adaptation_set_mime_type)
+                .and_then(|mime| {
+                    let mime = match attr("codecs") {
+                        Some(codecs) => format!(r#"{mime}; codecs="{codecs}""#),
+                        None => mime.to_string(),
+                    };
+                    serde_json::from_value(serde_json::Value::String(mime)).ok()
+                })
+                .or_else(|| static_format.map(|x| x.mime_type.clone()));
+
+            let Some(mime_type) = mime_type else {
+                continue;
+            };
+
+            let height = attr("height").and_then(|x| x.parse::<u64>().ok());
+
+            let streaming_data_format = StreamingDataFormat {
+                itag: Some(itag),
+                mime_type: Some(mime_type),
+                bitrate: attr("bandwidth")
+                    .and_then(|x| x.parse::<u64>().ok())
+                    .or(static_format.and_then(|x| x.bitrate)),
+                width: attr("width").and_then(|x| x.parse::<u64>().ok()),
+                height,
+                fps: attr("framerate").and_then(|x| x.parse::<u64>().ok()),
+                quality_label: static_format
+                    .and_then(|x| x.quality_label.clone())
+                    .or(height.map(|x| format!("{x}p"))),
+                audio_bitrate: static_format.and_then(|x| x.audio_bitrate),
+                audio_sample_rate: attr("audiosamplingrate").map(|x| x.to_string()),
+                url: Some(url),
+                ..Default::default()
+            };
+
+            let mut format = VideoFormat::from(streaming_data_format);
+            add_format_meta(&mut format);
+            format.has_video |= format.mime_type.video_codec.is_some();
+            format.has_audio |= format.mime_type.audio_codec.is_some();
+            format.is_dash_mpd = true;
+
+            formats.push(format);
+        }
+    }
+
+    formats
+}
+
 #[cfg_attr(feature = "performance_analysis", flamer::flame)]
 pub fn add_format_meta(format: &mut VideoFormat) {
     static REGEX_IS_LIVE: Lazy<Regex> =
@@ -153,7 +256,20 @@ pub fn choose_format<'a>(
     formats: &'a [VideoFormat],
     options: &'a VideoOptions,
 ) -> Result<VideoFormat, VideoError> {
+    candidate_formats(formats, options)
+        .into_iter()
+        .next()
+        .ok_or(VideoError::FormatNotFound)
+}
+
+/// Formats matching the [`VideoOptions`] filter, ordered from the best candidate. The first one is the format [`choose_format`] returns
+#[cfg_attr(feature = "performance_analysis", flamer::flame)]
+pub(crate) fn candidate_formats(
+    formats: &[VideoFormat],
+    options: &VideoOptions,
+) -> Vec<VideoFormat> {
     let filter = &options.filter;
+    let all_formats = formats;
     let mut formats = formats.to_owned();
 
     filter_formats(&mut formats, filter);
@@ -166,62 +282,113 @@ pub fn choose_format<'a>(
     match &options.quality {
         VideoQuality::Highest => {
             filter_formats(&mut formats, filter);
-
-            let return_format = formats.first().ok_or(VideoError::FormatNotFound)?;
-
-            Ok(return_format.clone())
         }
         VideoQuality::Lowest => {
             filter_formats(&mut formats, filter);
 
-            let return_format = formats.last().ok_or(VideoError::FormatNotFound)?;
-
-            Ok(return_format.clone())
+            formats.reverse();
         }
         VideoQuality::HighestAudio => {
             filter_formats(&mut formats, &VideoSearchOptions::Audio);
             formats.sort_by(sort_formats_by_audio);
-
-            let return_format = formats.first().ok_or(VideoError::FormatNotFound)?;
-
-            Ok(return_format.clone())
         }
         VideoQuality::LowestAudio => {
             filter_formats(&mut formats, &VideoSearchOptions::Audio);
 
             formats.sort_by(sort_formats_by_audio);
-
-            let return_format = formats.last().ok_or(VideoError::FormatNotFound)?;
-
-            Ok(return_format.clone())
+            formats.reverse();
         }
         VideoQuality::HighestVideo => {
             filter_formats(&mut formats, &VideoSearchOptions::Video);
             formats.sort_by(sort_formats_by_video);
-
-            let return_format = formats.first().ok_or(VideoError::FormatNotFound)?;
-
-            Ok(return_format.clone())
         }
         VideoQuality::LowestVideo => {
             filter_formats(&mut formats, &VideoSearchOptions::Video);
 
             formats.sort_by(sort_formats_by_video);
+            formats.reverse();
+        }
+        VideoQuality::Itag(itag) => {
+            // Exact itag selection bypasses the filter and sorting
+            formats = all_formats
+                .iter()
+                .filter(|x| x.itag == *itag)
+                .take(1)
+                .cloned()
+                .collect();
+        }
+        VideoQuality::Resolution(target) => {
+            let target = *target;
+            let rank = |height: u32| {
+                if height <= target {
+                    (1, height as i64)
+                } else {
+                    (0, -(height as i64))
+                }
+            };
+
+            // Prefer the highest format at or below the target, otherwise the lowest above it
+            formats.retain(|x| format_height(x).is_some());
+            formats.sort_by(|a, b| {
+                rank(format_height(a).unwrap_or(0))
+                    .cmp(&rank(format_height(b).unwrap_or(0)))
+                    .then(a.bitrate.cmp(&b.bitrate))
+            });
+            formats.reverse();
+        }
+        VideoQuality::MaxFileSize(max_size) => {
+            // Rank by resolution and bitrate only, availability of the content length must not affect the quality.
+            // Formats with unknown size (HLS) are skipped
+            formats.sort_by(sort_formats_by_video);
+            formats.retain(|x| x.estimated_file_size(None).is_some());
+
+            // Formats that fit come first, otherwise the smallest ones
+            let (mut fitting, mut oversized): (Vec<VideoFormat>, Vec<VideoFormat>) =
+                formats.into_iter().partition(|x| {
+                    x.estimated_file_size(None)
+                        .is_some_and(|size| size <= *max_size)
+                });
 
-            let return_format = formats.last().ok_or(VideoError::FormatNotFound)?;
+            oversized.sort_by_key(|x| x.estimated_file_size(None));
+            fitting.append(&mut oversized);
 
-            Ok(return_format.clone())
+            formats = fitting;
         }
         VideoQuality::Custom(filter, func) => {
             filter_formats(&mut formats, filter);
 
             formats.sort_by(|x, y| func(x, y));
-
-            let return_format = formats.first().ok_or(VideoError::FormatNotFound)?;
-
-            Ok(return_format.clone())
         }
     }
+
+    // Stable sort keeps the quality order within the preferred and the other codecs
+    if let Some(prefer_codec) = options.prefer_codec.as_ref() {
+        formats.sort_by_key(|x| !has_codec(x, prefer_codec));
+    }
+
+    formats
+}
+
+/// Whether any codec of the format starts with the given codec family (case-insensitive)
+fn has_codec(format: &VideoFormat, codec: &str) -> bool {
+    let codec = codec.to_lowercase();
+
+    format
+        .mime_type
+        .codecs
+        .iter()
+        .any(|x| x.trim().to_lowercase().starts_with(&codec))
+}
+
+/// Pixel height of the format from the quality label (e.g. `720p60` => `720`), falls back to the height field
+fn format_height(format: &VideoFormat) -> Option<u32> {
+    format
+        .quality_label
+        .as_ref()
+        .and_then(|x| PARSE_INT_REGEX.captures(x))
+        .and_then(|x| x.get(0))
+        .and_then(|x| x.as_str().parse::<u32>().ok())
+        .or(format.height.map(|x| x as u32))
 }
 
 #[cfg_attr(feature = "performance_analysis", flamer::flame)]
@@ -354,8 +521,9 @@ pub fn sort_formats(a: &VideoFormat, b: &VideoFormat) -> Ordering {
 #[cfg_attr(feature = "performance_analysis", flamer::flame)]
 pub fn set_download_url(
     format: &mut StreamingDataFormat,
-    functions: Vec<(String, String)>,
+    functions: &[(String, String)],
     n_transform_cache: &mut HashMap<String, String>,
+    n_transform_context: &mut Option<(String, Context)>,
     cipher_cache: &mut Option<(String, Context)>,
 ) -> String {
     #[derive(Debug, Deserialize, PartialEq, Serialize)]
@@ -377,7 +545,12 @@ pub fn set_download_url(
         .unwrap_or(empty_script);
 
     if let Some(url) = format.url.as_ref() {
-        format.url = Some(ncode(url, n_transform_script_string, n_transform_cache));
+        format.url = Some(ncode(
+            url,
+            n_transform_script_string,
+            n_transform_cache,
+            n_transform_context,
+        ));
     } else {
         let url = format
             .signature_cipher
@@ -388,12 +561,25 @@ pub fn set_download_url(
             decipher(&url, decipher_script_string, cipher_cache).as_str(),
             n_transform_script_string,
             n_transform_cache,
+            n_transform_context,
         ));
     }
 
     format.url.clone().unwrap_or("".to_string())
 }
 
+/// JS context with runtime limits, so the player script cannot run indefinitely
+fn new_js_context() -> Context {
+    let mut context = Context::default();
+    context
+        .runtime_limits_mut()
+        .set_loop_iteration_limit(JS_LOOP_ITERATION_LIMIT);
+    context
+        .runtime_limits_mut()
+        .set_recursion_limit(JS_RECURSION_LIMIT);
+    context
+}
+
 #[cfg_attr(feature = "performance_analysis", flamer::flame)]
 fn decipher(
     url: &str,
@@ -422,7 +608,7 @@ fn decipher(
         _ => {
             #[cfg(feature = "performance_analysis")]
             let _guard = flame::start_guard("build engine");
-            let mut context = Context::default();
+            let mut context = new_js_context();
             if context
                 .eval(Source::from_bytes(decipher_script_string.1))
                 .is_err()
@@ -493,6 +679,7 @@ fn ncode(
     url: &str,
     n_transform_script_string: (&str, &str),
     n_transfrom_cache: &mut HashMap<String, String>,
+    n_transform_context: &mut Option<(String, Context)>,
 ) -> String {
     let components: serde_json::value::Map<String, serde_json::Value> =
         serde_qs::from_str(&decode(url).unwrap_or(Cow::Borrowed(url))).unwrap_or_default();
@@ -508,7 +695,7 @@ fn ncode(
 
     #[cfg_attr(feature = "performance_analysis", flamer::flame)]
     fn create_transform_script(script: &str) -> Option<Context> {
-        let mut context = Context::default();
+        let mut context = new_js_context();
         context.eval(Source::from_bytes(script)).ok()?;
         Some(context)
     }
@@ -533,19 +720,23 @@ fn ncode(
             })
     }
 
-    let mut context = match create_transform_script(n_transform_script_string.1) {
-        Some(res) => res,
-        None => return url.to_string(),
+    let context = match n_transform_context {
+        Some((cache_key, context)) if cache_key == n_transform_script_string.1 => context,
+        _ => {
+            let context = match create_transform_script(n_transform_script_string.1) {
+                Some(res) => res,
+                None => return url.to_string(),
+            };
+            *n_transform_context = Some((n_transform_script_string.1.to_string(), context));
+            &mut n_transform_context.as_mut().unwrap().1
+        }
     };
 
-    let result = match execute_transform_script(
-        &mut context,
-        n_transform_script_string.0,
-        n_transform_value,
-    ) {
-        Some(res) => res,
-        None => return url.to_string(),
-    };
+    let result =
+        match execute_transform_script(context, n_transform_script_string.0, n_transform_value) {
+            Some(res) => res,
+            None => return url.to_string(),
+        };
 
     n_transfrom_cache.insert(n_transform_value.to_owned(), result.clone());
 
@@ -577,7 +768,7 @@ fn ncode(
 /// Excavate video id from URLs or id with Regex
 #[cfg_attr(feature = "performance_analysis", flamer::flame)]
 pub fn get_video_id(url: &str) -> Option<String> {
-    static URL_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^https?://").unwrap());
+    static URL_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)^https?://").unwrap());
 
     if validate_id(url.to_string()) {
         Some(url.to_string())
@@ -588,6 +779,55 @@ pub fn get_video_id(url: &str) -> Option<String> {
     }
 }
 
+/// Get the clip id of a clip URL (e.g. `https://www.youtube.com/clip/Ugkx...`).
+/// Clips resolve to their parent video only with a request, see [`crate::Video::from_clip`]
+pub fn get_clip_id(url: &str) -> Option<String> {
+    static CLIP_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r"(?i)^(?:https?://)?(?:www\.|m\.)?youtube\.com/clip/([\w-]+)").unwrap()
+    });
+
+    CLIP_REGEX
+        .captures(url.trim())
+        .and_then(|captures| captures.get(1))
+        .map(|m| m.as_str().to_string())
+}
+
+/// Parent video id and the looped portion of a clip from the `ytInitialData` of its page
+pub(crate) fn get_clip(initial_data: &serde_json::Value, clip_id: &str) -> Option<(String, Clip)> {
+    let video_id = initial_data["currentVideoEndpoint"]["watchEndpoint"]["videoId"]
+        .as_str()
+        .filter(|x| validate_id(x.to_string()))?
+        .to_string();
+
+    let loop_command = find_key(initial_data, "loopCommand")?;
+    let time_ms = |key: &str| {
+        loop_command[key]
+            .as_str()
+            .and_then(|x| x.parse::<u64>().ok())
+            .or_else(|| loop_command[key].as_u64())
+    };
+
+    Some((
+        video_id,
+        Clip {
+            id: clip_id.to_string(),
+            start_time_ms: time_ms("startTimeMs")?,
+            end_time_ms: time_ms("endTimeMs")?,
+        },
+    ))
+}
+
+/// First value of the key in the JSON tree, depth first
+fn find_key<'a>(value: &'a serde_json::Value, key: &str) -> Option<&'a serde_json::Value> {
+    match value {
+        serde_json::Value::Object(map) => map
+            .get(key)
+            .or_else(|| map.values().find_map(|x| find_key(x, key))),
+        serde_json::Value::Array(array) => array.iter().find_map(|x| find_key(x, key)),
+        _ => None,
+    }
+}
+
 #[cfg_attr(feature = "performance_analysis", flamer::flame)]
 pub fn validate_id(id: String) -> bool {
     static ID_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[a-zA-Z0-9-_]{11}$").unwrap());
@@ -597,12 +837,24 @@ pub fn validate_id(id: String) -> bool {
 #[cfg_attr(feature = "performance_analysis", flamer::flame)]
 fn get_url_video_id(url: &str) -> Option<String> {
     static VALID_PATH_DOMAINS: Lazy<Regex> = Lazy::new(|| {
-        Regex::new(r"(?m)(?:^|\W)(?:youtube(?:-nocookie)?\.com/(?:.*[?&]v=|v/|shorts/|e(?:mbed)?/|[^/]+/.+/)|youtu\.be/)([\w-]+)")
+        Regex::new(r"(?mi)(?:^|\W)(?:youtube(?:-nocookie)?\.com/(?:.*[?&]v=|v/|shorts/|live/|e(?:mbed)?/|[^/]+/.+/)|youtu\.be/)([\w-]+)")
         .unwrap()
     });
 
     let parsed = url::Url::parse(url.trim()).ok()?;
 
+    // Short links keep the id as the first path segment, query (`t`, `si`, `list`, ...) is ignored
+    if parsed
+        .host_str()
+        .is_some_and(|host| host == "youtu.be" || host == "www.youtu.be")
+    {
+        return parsed
+            .path_segments()
+            .and_then(|mut segments| segments.find(|x| !x.is_empty()))
+            .map(|id| id.substring(0, 11).to_string())
+            .filter(|id| validate_id(id.clone()));
+    }
+
     if let Some(id) = parsed.query_pairs().find_map(|(key, value)| {
         if key == "v" {
             Some(value.to_string())
@@ -610,19 +862,14 @@ fn get_url_video_id(url: &str) -> Option<String> {
             None
         }
     }) {
-        return Some(id).filter(|id| {
-            let return_id = id.substring(0, 11);
-            validate_id(return_id.into())
-        });
+        // Share links may append extra characters to the id, only the first 11 are the id
+        return Some(id.substring(0, 11).to_string()).filter(|id| validate_id(id.clone()));
     }
 
     if VALID_PATH_DOMAINS.is_match(url.trim()) {
         if let Some(captures) = VALID_PATH_DOMAINS.captures(url.trim()) {
             if let Some(id) = captures.get(1).map(|m| m.as_str().to_string()) {
-                return Some(id).filter(|id| {
-                    let return_id = id.substring(0, 11);
-                    validate_id(return_id.into())
-                });
+                return Some(id.substring(0, 11).to_string()).filter(|id| validate_id(id.clone()));
             }
         }
     }
@@ -648,6 +895,18 @@ pub fn get_text(obj: &serde_json::Value) -> &serde_json::Value {
     }
 }
 
+#[cfg_attr(feature = "performance_analysis", flamer::flame)]
+pub fn has_streaming_formats(player_response: &PlayerResponse) -> bool {
+    player_response
+        .streaming_data
+        .as_ref()
+        .map(|x| {
+            x.formats.as_ref().is_some_and(|x| !x.is_empty())
+                || x.adaptive_formats.as_ref().is_some_and(|x| !x.is_empty())
+        })
+        .unwrap_or(false)
+}
+
 #[cfg_attr(feature = "performance_analysis", flamer::flame)]
 pub fn is_live(player_response: &PlayerResponse) -> bool {
     let video_details = player_response.video_details.as_ref();
@@ -680,7 +939,7 @@ pub fn clean_video_details(
 
         video_url: format!("{BASE_URL}{id}"),
         storyboards: get_storyboards(player_response).unwrap_or_default(),
-        chapters: get_chapters(initial_response).unwrap_or_default(),
+        chapters: get_chapters(initial_response, player_response).unwrap_or_default(),
 
         embed: Embed {
             flash_secure_url: data
@@ -768,6 +1027,8 @@ pub fn clean_video_details(
         category: data
             .as_ref()
             .and_then(|x| x.category.clone())
+            .filter(|x| !x.is_empty())
+            .or_else(|| get_category(initial_response))
             .unwrap_or("".to_string()),
         publish_date: data
             .as_ref()
@@ -785,10 +1046,21 @@ pub fn clean_video_details(
             .as_ref()
             .and_then(|x| x.video_id.clone())
             .unwrap_or("".to_string()),
-        keywords: video_details
-            .as_ref()
-            .and_then(|x| x.keywords.clone())
-            .unwrap_or_default(),
+        keywords: {
+            let mut keywords = video_details
+                .as_ref()
+                .and_then(|x| x.keywords.clone())
+                .unwrap_or_default();
+
+            // Merge the microformat tags without duplicates
+            for tag in get_tags(initial_response) {
+                if !keywords.contains(&tag) {
+                    keywords.push(tag);
+                }
+            }
+
+            keywords
+        },
         channel_id: video_details
             .as_ref()
             .as_ref()
@@ -827,6 +1099,8 @@ pub fn clean_video_details(
                 .unwrap_or_default(),
         ]
         .concat(),
+        // Set by the video if it is created from a clip
+        clip: None,
     }
 }
 
@@ -975,6 +1249,121 @@ pub fn is_player_response_error(
     None
 }
 
+/// Map playability status and reason of the player response to a specific [`VideoError`].
+/// Returns `None` for playable videos and for statuses without a specific error
+#[cfg_attr(feature = "performance_analysis", flamer::flame)]
+pub fn get_playability_error(player_response: &PlayerResponse) -> Option<VideoError> {
+    let playability_status = player_response.playability_status.as_ref()?;
+    let status = playability_status.status.as_deref()?;
+    let reason = playability_status.reason.clone().unwrap_or_default();
+    let reason_lowercase = reason.to_lowercase();
+
+    match status {
+        "LIVE_STREAM_OFFLINE" => {
+            let is_scheduled = playability_status
+                .live_streamability
+                .as_ref()
+                .and_then(|x| {
+                    x.pointer(
+                        "/liveStreamabilityRenderer/offlineSlate/liveStreamOfflineSlateRenderer/scheduledStartTime",
+                    )
+                })
+                .is_some()
+                || reason_lowercase.contains("will begin")
+                || reason_lowercase.contains("premiere");
+
+            if is_scheduled {
+                Some(VideoError::LiveStreamNotStarted(reason))
+            } else {
+                Some(VideoError::LiveStreamOffline)
+            }
+        }
+        "ERROR" | "UNPLAYABLE" | "LOGIN_REQUIRED" | "CONTENT_CHECK_REQUIRED" => {
+            if reason_lowercase.contains("member") {
+                Some(VideoError::MembersOnly)
+            } else if reason_lowercase.contains("country") {
+                let available_countries = player_response
+                    .micro_format
+                    .as_ref()
+                    .and_then(|x| x.player_micro_format_renderer.as_ref())
+                    .and_then(|x| x.available_countries.clone())
+                    .unwrap_or_default();
+
+                Some(VideoError::GeoRestricted {
+                    available_countries,
+                })
+            } else if reason_lowercase.contains("removed")
+                || reason_lowercase.contains("terminated")
+                || reason_lowercase.contains("no longer available")
+            {
+                Some(VideoError::Removed(reason))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Derive the [`Availability`] of the video from the `playabilityStatus` of the player response.
+/// Specific errors come from [`get_playability_error`], the same mapping [`crate::Video::get_info`] uses
+pub fn get_availability(player_response: &PlayerResponse) -> Availability {
+    if let Some(err) = get_playability_error(player_response) {
+        return match err {
+            VideoError::LiveStreamNotStarted(_) => Availability::LiveUpcoming,
+            VideoError::LiveStreamOffline => Availability::LiveOffline,
+            VideoError::MembersOnly => Availability::MembersOnly,
+            VideoError::GeoRestricted {
+                available_countries,
+            } => Availability::GeoRestricted {
+                available_countries,
+            },
+            VideoError::Removed(_) => Availability::Removed,
+            err => Availability::Unavailable(err.to_string()),
+        };
+    }
+
+    let Some(playability_status) = player_response.playability_status.as_ref() else {
+        return Availability::Removed;
+    };
+    let status = playability_status.status.as_deref().unwrap_or("");
+    let reason = playability_status.reason.clone().unwrap_or_default();
+
+    if is_age_gated(player_response)
+        || matches!(
+            status,
+            "AGE_CHECK_REQUIRED" | "AGE_VERIFICATION_REQUIRED" | "CONTENT_CHECK_REQUIRED"
+        )
+    {
+        return Availability::AgeRestricted;
+    }
+
+    // Bot check ("Sign in to confirm you're not a bot") is also `LOGIN_REQUIRED`, the video is not private
+    if reason.to_lowercase().contains("not a bot") {
+        return Availability::Unavailable(reason);
+    }
+
+    if is_private_video(player_response) {
+        return Availability::Private;
+    }
+
+    match status {
+        "OK" => Availability::Available,
+        "ERROR" => Availability::Removed,
+        _ => Availability::Unavailable(reason),
+    }
+}
+
+/// Whether the player response is blocked by the age gate (e.g. "Sign in to confirm your age")
+pub fn is_age_gated(player_response: &PlayerResponse) -> bool {
+    player_response
+        .playability_status
+        .as_ref()
+        .and_then(|x| x.reason.as_ref())
+        .map(|x| x.to_lowercase())
+        .is_some_and(|x| x.contains("confirm your age") || x.contains("inappropriate"))
+}
+
 #[cfg_attr(feature = "performance_analysis", flamer::flame)]
 pub fn is_private_video(player_response: &PlayerResponse) -> bool {
     player_response
@@ -996,35 +1385,83 @@ pub fn get_ytconfig(html: &str) -> Result<YTConfig, VideoError> {
     }
 }
 
+#[cfg_attr(feature = "performance_analysis", flamer::flame)]
+pub fn get_visitor_data(html: &str) -> Option<String> {
+    static PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r#""VISITOR_DATA":"([^"]+)""#).unwrap());
+
+    get_ytconfig(html)
+        .ok()
+        .and_then(|x| x.visitor_data)
+        .or_else(|| {
+            PATTERN
+                .captures(html)
+                .and_then(|c| c.get(1))
+                .map(|m| m.as_str().to_string())
+        })
+}
+
 type CacheFunctions = Lazy<RwLock<Option<(String, Vec<(String, String)>)>>>;
 static FUNCTIONS: CacheFunctions = Lazy::new(|| RwLock::new(None));
 
+/// On-disk cache of the player script functions, see [`crate::RequestOptions::player_cache_path`]
+#[derive(Debug, Serialize, Deserialize)]
+struct PlayerFunctionsCache {
+    player_url: String,
+    functions: Vec<(String, String)>,
+}
+
+/// Extract decipher and n transform functions of the player script.
+/// Functions are cached in memory and, if `cache_path` is given, on disk until the player URL changes
 #[cfg_attr(feature = "performance_analysis", flamer::flame)]
 pub async fn get_functions(
     html5player: impl Into<String>,
     client: &reqwest_middleware::ClientWithMiddleware,
+    cache_path: Option<&Path>,
 ) -> Result<Vec<(String, String)>, VideoError> {
-    let mut url = url::Url::parse(BASE_URL).expect("IMPOSSIBLE");
-    url.set_path(&html5player.into());
-    url.query_pairs_mut().clear();
-
+    let url = get_player_url(&html5player.into());
     let url = url.as_str();
 
-    // println!("html5player url: {}", url);
+    trace_debug!(player_url = url, "Extracting player functions");
 
     {
         // Check if an URL is already cached
         if let Some((cached_url, cached_functions)) = FUNCTIONS.read().await.as_ref() {
             // Check if the cache is the same as the URL
             if cached_url == url {
+                trace_debug!("Player functions found in memory cache");
                 return Ok(cached_functions.clone());
             }
         }
     }
 
-    let response = get_html(client, url, None).await?;
+    // Functions of the same player may be cached on disk by another process
+    let disk_cache = match cache_path {
+        Some(path) => read_player_cache(path).await,
+        None => None,
+    }
+    .filter(|x| x.player_url == url);
+
+    let functions = if let Some(disk_cache) = disk_cache {
+        disk_cache.functions
+    } else {
+        let response = get_html(client, url, None).await?;
+
+        let functions = extract_functions(response);
+
+        // Disk cache is best-effort, a failed write only costs a refetch next time
+        if let Some(path) = cache_path {
+            let cache = PlayerFunctionsCache {
+                player_url: url.to_string(),
+                functions: functions.clone(),
+            };
 
-    let functions = extract_functions(response);
+            if let Err(_err) = write_player_cache(path, &cache).await {
+                trace_debug!(error = %_err, "Player functions cache can not be written");
+            }
+        }
+
+        functions
+    };
 
     // Update the cache
     {
@@ -1034,6 +1471,50 @@ pub async fn get_functions(
     Ok(functions)
 }
 
+/// Read the on-disk player functions cache, the file system is not available on `wasm32`
+async fn read_player_cache(_path: &Path) -> Option<PlayerFunctionsCache> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let cache = tokio::fs::read_to_string(_path).await.ok()?;
+
+        serde_json::from_str(&cache).ok()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    None
+}
+
+/// Write the on-disk player functions cache through a temporary file renamed over the cache,
+/// so other processes never read a partially written file
+async fn write_player_cache(_path: &Path, _cache: &PlayerFunctionsCache) -> std::io::Result<()> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let cache = serde_json::to_string(_cache)?;
+
+        let mut temp_path = _path.as_os_str().to_owned();
+        temp_path.push(format!(".{:x}.tmp", rand::thread_rng().gen::<u64>()));
+        let temp_path = std::path::PathBuf::from(temp_path);
+
+        tokio::fs::write(&temp_path, cache).await?;
+
+        if let Err(err) = tokio::fs::rename(&temp_path, _path).await {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            return Err(err);
+        }
+    }
+
+    Ok(())
+}
+
+/// Absolute URL of the player script path found by [`get_html5player`]
+pub(crate) fn get_player_url(html5player: &str) -> String {
+    let mut url = url::Url::parse(BASE_URL).expect("IMPOSSIBLE");
+    url.set_path(html5player);
+    url.query_pairs_mut().clear();
+
+    url.to_string()
+}
+
 #[cfg_attr(feature = "performance_analysis", flamer::flame)]
 pub fn extract_functions(body: String) -> Vec<(String, String)> {
     let mut functions: Vec<(String, String)> = vec![];
@@ -1063,7 +1544,7 @@ pub fn extract_functions(body: String) -> Vec<(String, String)> {
     #[cfg_attr(feature = "performance_analysis", flamer::flame)]
     fn extract_decipher(body: String, functions: &mut Vec<(String, String)>) {
         let function_name = between(body.as_str(), r#"a.set("alr","yes");c&&(c="#, "(decodeURIC");
-        // println!("decipher function name: {}", function_name);
+        trace_debug!(function_name, "Decipher function name");
         if !function_name.is_empty() {
             let function_start = format!("{function_name}=function(a)");
             let ndx = body.find(function_start.as_str());
@@ -1082,8 +1563,6 @@ pub fn extract_functions(body: String) -> Vec<(String, String)> {
 
                 function_body.retain(|c| c != '\n');
 
-                // println!("decipher function: {}", function_body);
-
                 functions.push((function_name.to_string(), function_body));
             }
         }
@@ -1128,7 +1607,7 @@ pub fn extract_functions(body: String) -> Vec<(String, String)> {
             }
         }
 
-        // println!("ncode function name: {}", function_name);
+        trace_debug!(function_name, "N transform function name");
 
         if !function_name.is_empty() {
             let function_start = format!("{function_name}=function(a)");
@@ -1143,8 +1622,6 @@ pub fn extract_functions(body: String) -> Vec<(String, String)> {
 
                 function_body.retain(|c| c != '\n');
 
-                // println!("ncode function: {}", function_body);
-
                 functions.push((function_name.to_string(), function_body));
             }
         }
@@ -1153,9 +1630,53 @@ pub fn extract_functions(body: String) -> Vec<(String, String)> {
     extract_decipher(body.clone(), &mut functions);
     extract_ncode(body, &mut functions);
 
+    if functions.len() < 2 {
+        trace_warn!(
+            found = functions.len(),
+            "Decipher or n transform function not found in the player script"
+        );
+    }
+
     functions
 }
 
+/// User agent of the innertube client which requested the format URL (`c` query param).
+/// Videoplayback requests with a user agent mismatching the client are a known cause of `403` errors
+pub(crate) fn get_user_agent_of_url(url: &str) -> Option<&'static str> {
+    let url = url::Url::parse(url).ok()?;
+    let (_, client_name) = url.query_pairs().find(|(key, _)| key == "c")?;
+
+    let key = match client_name.as_ref() {
+        "WEB" => "web",
+        "IOS" => "ios",
+        "ANDROID" => "android",
+        "TVHTML5_SIMPLY_EMBEDDED_PLAYER" => "tv_embedded",
+        "WEB_CREATOR" => "web_creator",
+        _ => return None,
+    };
+
+    INNERTUBE_CLIENT.get(key).map(|x| x.3)
+}
+
+/// [`DEFAULT_HEADERS`] with the user agent of the innertube client of the format URL, see [`get_user_agent_of_url`]
+pub(crate) fn get_download_headers(url: &str) -> reqwest::header::HeaderMap {
+    let mut headers = DEFAULT_HEADERS.clone();
+
+    if let Some(user_agent) = get_user_agent_of_url(url) {
+        headers.insert(
+            reqwest::header::USER_AGENT,
+            reqwest::header::HeaderValue::from_static(user_agent),
+        );
+    }
+
+    headers
+}
+
+/// Clamp a download chunk size between [`MIN_DL_CHUNK_SIZE`] and [`MAX_DL_CHUNK_SIZE`]
+pub(crate) fn clamp_chunk_size(dl_chunk_size: u64) -> u64 {
+    dl_chunk_size.clamp(MIN_DL_CHUNK_SIZE, MAX_DL_CHUNK_SIZE)
+}
+
 pub async fn get_html(
     client: &reqwest_middleware::ClientWithMiddleware,
     url: impl Into<String>,
@@ -1181,6 +1702,47 @@ pub async fn get_html(
     Ok(response_first)
 }
 
+/// Delay requested by the `Retry-After` header of a `429 Too Many Requests` or `503 Service Unavailable` response
+pub(crate) fn get_retry_after(response: &reqwest::Response) -> Option<std::time::Duration> {
+    let status = response.status();
+
+    if status != reqwest::StatusCode::TOO_MANY_REQUESTS
+        && status != reqwest::StatusCode::SERVICE_UNAVAILABLE
+    {
+        return None;
+    }
+
+    let value = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let now = std::time::SystemTime::now();
+    #[cfg(target_arch = "wasm32")]
+    let now = std::time::UNIX_EPOCH
+        + wasmtimer::std::SystemTime::now()
+            .duration_since(wasmtimer::std::UNIX_EPOCH)
+            .unwrap_or_default();
+
+    parse_retry_after(value, now)
+}
+
+/// Parse `Retry-After` header value, either delay in seconds or HTTP-date
+fn parse_retry_after(value: &str, now: std::time::SystemTime) -> Option<std::time::Duration> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(std::time::Duration::from_secs(seconds));
+    }
+
+    // Dates in the past mean retry immediately
+    httpdate::parse_http_date(value)
+        .ok()
+        .map(|date| date.duration_since(now).unwrap_or_default())
+}
+
 /// Try to generate IPv6 with custom valid block
 /// # Example
 /// ```ignore
@@ -1227,6 +1789,38 @@ pub fn get_random_v6_ip(ip: impl Into<String>) -> Result<IpAddr, VideoError> {
     Ok(IpAddr::from(random_addr))
 }
 
+/// Try to generate IPv4 with custom valid block (CIDR notation, mask between `8` and `32`)
+/// # Example
+/// ```ignore
+/// let ipv4: std::net::IpAddr = get_random_v4_ip("203.0.113.0/24")?;
+/// ```
+pub fn get_random_v4_ip(ip: impl Into<String>) -> Result<IpAddr, VideoError> {
+    let ipv4_format: String = ip.into();
+
+    let (raw_addr, raw_mask) = ipv4_format
+        .split_once('/')
+        .ok_or(VideoError::InvalidIPv4Format)?;
+
+    let base_addr = raw_addr
+        .parse::<Ipv4Addr>()
+        .map_err(|_x| VideoError::InvalidIPv4Format)?;
+
+    let mask = raw_mask
+        .parse::<u32>()
+        .map_err(|_x| VideoError::InvalidIPv4Subnet)?;
+
+    if !(8..=32).contains(&mask) {
+        return Err(VideoError::InvalidIPv4Subnet);
+    }
+
+    let network_mask = u32::MAX.checked_shl(32 - mask).unwrap_or(0);
+    let random_host: u32 = rand::thread_rng().gen();
+
+    Ok(IpAddr::from(Ipv4Addr::from(
+        (u32::from(base_addr) & network_mask) | (random_host & !network_mask),
+    )))
+}
+
 #[cfg_attr(feature = "performance_analysis", flamer::flame)]
 pub fn normalize_ip(ip: impl Into<String>) -> Vec<u16> {
     let ip: String = ip.into();
@@ -1276,28 +1870,198 @@ pub fn time_to_ms(duration: &str) -> usize {
     ms
 }
 
-#[cfg_attr(feature = "performance_analysis", flamer::flame)]
-pub fn parse_abbreviated_number(time_str: &str) -> usize {
-    let replaced_string = time_str.replace(',', ".").replace(' ', "");
-    static STRING_MATCH_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"([\d,.]+)([MK]?)").unwrap());
+/// Check the live badge or the live thumbnail overlay of a video renderer
+pub(crate) fn is_live_now(renderer: &serde_json::Value) -> bool {
+    let live_badge = renderer["badges"]
+        .as_array()
+        .map(|badges| {
+            badges.iter().any(|x| {
+                x["metadataBadgeRenderer"]["style"].as_str() == Some("BADGE_STYLE_TYPE_LIVE_NOW")
+            })
+        })
+        .unwrap_or(false);
 
-    if let Some(caps) = STRING_MATCH_REGEX.captures(replaced_string.as_str()) {
-        let return_value = if caps.len() > 0 {
-            let mut num;
+    let live_overlay = renderer["thumbnailOverlays"]
+        .as_array()
+        .map(|overlays| {
+            overlays
+                .iter()
+                .any(|x| x["thumbnailOverlayTimeStatusRenderer"]["style"].as_str() == Some("LIVE"))
+        })
+        .unwrap_or(false);
 
-            match caps.get(1) {
-                Some(regex_match) => num = regex_match.as_str().parse::<f64>().unwrap_or(0f64),
-                None => num = 0f64,
-            }
+    live_badge || live_overlay
+}
 
-            let multi = match caps.get(2) {
-                Some(regex_match) => regex_match.as_str(),
-                None => "",
-            };
+/// Check the upcoming event data or the upcoming thumbnail overlay of a video renderer
+pub(crate) fn is_upcoming(renderer: &serde_json::Value) -> bool {
+    !renderer["upcomingEventData"].is_null()
+        || renderer["thumbnailOverlays"]
+            .as_array()
+            .map(|overlays| {
+                overlays.iter().any(|x| {
+                    x["thumbnailOverlayTimeStatusRenderer"]["style"].as_str() == Some("UPCOMING")
+                })
+            })
+            .unwrap_or(false)
+}
 
-            match multi {
-                "M" => num *= 1000000f64,
-                "K" => num *= 1000f64,
+/// Scheduled start time of an upcoming video renderer as unix timestamp in seconds
+pub(crate) fn get_scheduled_start_time(renderer: &serde_json::Value) -> Option<u64> {
+    renderer["upcomingEventData"]["startTime"]
+        .as_str()
+        .and_then(|x| x.parse::<u64>().ok())
+}
+
+/// Parse the suggestion list of the `complete/search` response like `["query", ["suggestion", ...], ...]`
+pub(crate) fn parse_suggestions(body: &str) -> Result<Vec<String>, VideoError> {
+    let serde_value = serde_json::from_str::<serde_json::Value>(body)
+        .map_err(|_| VideoError::BodyCannotParsed)?;
+
+    let suggestions = serde_value
+        .as_array()
+        .and_then(|x| x.get(1))
+        .and_then(|x| x.as_array())
+        .ok_or(VideoError::BodyCannotParsed)?;
+
+    Ok(suggestions
+        .iter()
+        .filter_map(|x| x.as_str().map(|x| x.to_string()))
+        .collect())
+}
+
+/// Index of the first segment to start `offset` back from the end of the segments with `durations`,
+/// and the duration from that segment to the end. Whole segments are kept, so the duration may exceed the offset
+pub(crate) fn get_dvr_start_index(
+    durations: &[std::time::Duration],
+    offset: std::time::Duration,
+) -> (usize, std::time::Duration) {
+    let mut total = std::time::Duration::ZERO;
+    let mut start_index = durations.len();
+
+    for duration in durations.iter().rev() {
+        if total >= offset {
+            break;
+        }
+
+        total += *duration;
+        start_index -= 1;
+    }
+
+    (start_index, total)
+}
+
+/// Parse relative time texts like `2 years ago` or `Streamed 3 days ago` to approximate [`std::time::Duration`]
+pub fn parse_relative_time(text: &str) -> Option<std::time::Duration> {
+    static RELATIVE_TIME_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r"(\d+)\s*(second|minute|hour|day|week|month|year)s?\s+ago").unwrap()
+    });
+
+    let caps = RELATIVE_TIME_REGEX.captures(text)?;
+    let count = caps.get(1)?.as_str().parse::<u64>().ok()?;
+
+    let unit_secs = match caps.get(2)?.as_str() {
+        "second" => 1,
+        "minute" => 60,
+        "hour" => 60 * 60,
+        "day" => 24 * 60 * 60,
+        "week" => 7 * 24 * 60 * 60,
+        "month" => 30 * 24 * 60 * 60,
+        _ => 365 * 24 * 60 * 60,
+    };
+
+    Some(std::time::Duration::from_secs(count * unit_secs))
+}
+
+/// Convert WebVTT captions to SubRip (SRT) format
+#[cfg_attr(feature = "performance_analysis", flamer::flame)]
+pub fn vtt_to_srt(vtt: &str) -> String {
+    static TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]*>").unwrap());
+
+    let vtt = vtt.replace("\r\n", "\n");
+    let mut srt = String::new();
+    let mut index = 0;
+
+    // Header, NOTE and STYLE blocks have no timing line so they are skipped
+    for block in vtt.split("\n\n") {
+        let mut lines = block.lines().skip_while(|line| !line.contains("-->"));
+
+        let Some(timing) = lines.next() else {
+            continue;
+        };
+
+        let mut times = timing.split("-->").map(|time| {
+            // Drop cue settings like `align:start position:0%`
+            let time = time.split_whitespace().next().unwrap_or_default();
+            let mut parts = time.split(':').collect::<Vec<&str>>();
+            if parts.len() < 3 {
+                parts.insert(0, "0");
+            }
+            format!("{:0>2}:{}", parts[0], parts[1..].join(":")).replace('.', ",")
+        });
+
+        let (Some(start), Some(end)) = (times.next(), times.next()) else {
+            continue;
+        };
+
+        let text = lines
+            .map(|line| TAG_REGEX.replace_all(line, "").trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        if text.is_empty() {
+            continue;
+        }
+
+        index += 1;
+        srt.push_str(&format!("{index}\n{start} --> {end}\n{text}\n\n"));
+    }
+
+    srt
+}
+
+#[cfg_attr(feature = "performance_analysis", flamer::flame)]
+pub fn parse_abbreviated_number(time_str: &str) -> usize {
+    // `,` followed by 3 digits is a thousands separator (`12,345`), otherwise a decimal separator (`1,2K`)
+    let chars: Vec<char> = time_str.chars().collect();
+    let replaced_string: String = chars
+        .iter()
+        .enumerate()
+        .filter_map(|(index, &char)| match char {
+            ',' if chars[index + 1..]
+                .iter()
+                .take_while(|x| x.is_ascii_digit())
+                .count()
+                == 3 =>
+            {
+                None
+            }
+            ',' => Some('.'),
+            ' ' => None,
+            _ => Some(char),
+        })
+        .collect();
+    static STRING_MATCH_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"([\d.]+)([MKB]?)").unwrap());
+
+    if let Some(caps) = STRING_MATCH_REGEX.captures(replaced_string.as_str()) {
+        let return_value = if caps.len() > 0 {
+            let mut num;
+
+            match caps.get(1) {
+                Some(regex_match) => num = regex_match.as_str().parse::<f64>().unwrap_or(0f64),
+                None => num = 0f64,
+            }
+
+            let multi = match caps.get(2) {
+                Some(regex_match) => regex_match.as_str(),
+                None => "",
+            };
+
+            match multi {
+                "B" => num *= 1000000000f64,
+                "M" => num *= 1000000f64,
+                "K" => num *= 1000f64,
                 _ => {
                     // Do Nothing
                 }
@@ -1403,6 +2167,39 @@ pub fn cut_after_js(mixed_json: &str) -> Option<&str> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_abbreviated_number() {
+        assert_eq!(parse_abbreviated_number("1.2M subscribers"), 1_200_000);
+        println!("[PASSED] test_million_abbreviation");
+
+        assert_eq!(parse_abbreviated_number("35.4K subscribers"), 35_400);
+        println!("[PASSED] test_thousand_abbreviation");
+
+        assert_eq!(parse_abbreviated_number("1.5B views"), 1_500_000_000);
+        println!("[PASSED] test_billion_abbreviation");
+
+        assert_eq!(parse_abbreviated_number("12,345 subscribers"), 12_345);
+        assert_eq!(parse_abbreviated_number("1,234,567 views"), 1_234_567);
+        println!("[PASSED] test_thousands_separator");
+
+        assert_eq!(parse_abbreviated_number("1,2K"), 1_200);
+        println!("[PASSED] test_decimal_comma");
+
+        assert_eq!(parse_abbreviated_number("No subscribers"), 0);
+        println!("[PASSED] test_no_number");
+    }
+
+    #[test]
+    fn test_vtt_to_srt() {
+        let vtt = "WEBVTT\nKind: captions\nLanguage: en\n\n00:00:01.000 --> 00:00:04.500 align:start position:0%\nHello <c>world</c>\n\n1:02:03.250 --> 1:02:05.000\nSecond line\n";
+
+        assert_eq!(
+            vtt_to_srt(vtt),
+            "1\n00:00:01,000 --> 00:00:04,500\nHello world\n\n2\n01:02:03,250 --> 01:02:05,000\nSecond line\n\n"
+        );
+        println!("[PASSED] test_vtt_to_srt");
+    }
+
     #[test]
     fn test_cut_after_js() {
         assert_eq!(
@@ -1516,4 +2313,866 @@ mod tests {
         assert!(cut_after_js(r#"{"a": 1,{ "b": 1}"#).is_none());
         println!("[PASSED] test_returns_error_when_missing_closing_bracket");
     }
+
+    fn synthetic_format(
+        itag: u64,
+        quality_label: &str,
+        bitrate: u64,
+        content_length: Option<u64>,
+    ) -> VideoFormat {
+        serde_json::from_value(serde_json::json!({
+            "itag": itag,
+            "mimeType": r#"video/mp4; codecs="avc1.4d401f, mp4a.40.2""#,
+            "bitrate": bitrate,
+            "qualityLabel": quality_label,
+            "contentLength": content_length.map(|x| x.to_string()),
+            "approxDurationMs": "100000",
+            "url": "",
+            "hasVideo": true,
+            "hasAudio": true,
+            "isLive": false,
+            "isHLS": false,
+            "isDashMPD": false,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_choose_format_max_file_size() {
+        let formats = vec![
+            synthetic_format(18, "360p", 500_000, Some(6_000_000)),
+            synthetic_format(22, "720p", 2_000_000, Some(25_000_000)),
+            // 4Mbps for 100 seconds => 50MB estimated
+            synthetic_format(37, "1080p", 4_000_000, None),
+        ];
+
+        let choose = |max_size: u64| {
+            choose_format(
+                &formats,
+                &VideoOptions {
+                    quality: VideoQuality::MaxFileSize(max_size),
+                    filter: VideoSearchOptions::VideoAudio,
+                    ..Default::default()
+                },
+            )
+            .map(|x| x.itag)
+        };
+
+        assert_eq!(choose(100_000_000).unwrap(), 37);
+        println!("[PASSED] test_max_file_size_estimates_from_bitrate");
+
+        assert_eq!(choose(25_000_000).unwrap(), 22);
+        println!("[PASSED] test_max_file_size_picks_highest_fitting");
+
+        assert_eq!(choose(10_000_000).unwrap(), 18);
+        assert_eq!(choose(1_000).unwrap(), 18);
+        println!("[PASSED] test_max_file_size_falls_back_to_smallest");
+    }
+
+    #[test]
+    fn test_candidate_formats() {
+        let formats = vec![
+            synthetic_format(18, "360p", 500_000, Some(6_000_000)),
+            synthetic_format(37, "1080p", 4_000_000, None),
+            synthetic_format(22, "720p", 2_000_000, Some(25_000_000)),
+        ];
+
+        let candidates = |quality: VideoQuality| {
+            let options = VideoOptions {
+                quality,
+                filter: VideoSearchOptions::VideoAudio,
+                ..Default::default()
+            };
+
+            let itags = candidate_formats(&formats, &options)
+                .iter()
+                .map(|x| x.itag)
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                itags.first().copied(),
+                choose_format(&formats, &options).ok().map(|x| x.itag)
+            );
+
+            itags
+        };
+
+        // Formats with a known content length are ranked first
+        assert_eq!(candidates(VideoQuality::Highest), vec![22, 18, 37]);
+        assert_eq!(candidates(VideoQuality::Lowest), vec![37, 18, 22]);
+        println!("[PASSED] test_candidate_formats_ordering");
+
+        assert_eq!(candidates(VideoQuality::Resolution(720)), vec![22, 18, 37]);
+        assert_eq!(
+            candidates(VideoQuality::MaxFileSize(10_000_000)),
+            vec![18, 22, 37]
+        );
+        println!("[PASSED] test_candidate_formats_match_choose_format");
+
+        assert_eq!(candidates(VideoQuality::Itag(22)), vec![22]);
+        assert!(candidates(VideoQuality::Itag(140)).is_empty());
+        println!("[PASSED] test_candidate_formats_itag");
+    }
+
+    #[test]
+    fn test_prefer_codec() {
+        let mut vp9 = synthetic_format(248, "1080p", 4_000_000, Some(50_000_000));
+        vp9.mime_type.codecs = vec!["vp9".to_string()];
+
+        let formats = vec![
+            vp9,
+            synthetic_format(18, "360p", 500_000, Some(6_000_000)),
+            synthetic_format(22, "720p", 2_000_000, Some(25_000_000)),
+        ];
+
+        let choose = |prefer_codec: Option<&str>| {
+            choose_format(
+                &formats,
+                &VideoOptions {
+                    quality: VideoQuality::Highest,
+                    filter: VideoSearchOptions::VideoAudio,
+                    prefer_codec: prefer_codec.map(|x| x.to_string()),
+                    ..Default::default()
+                },
+            )
+            .map(|x| x.itag)
+        };
+
+        assert_eq!(choose(None).unwrap(), 248);
+        assert_eq!(choose(Some("AVC1")).unwrap(), 22);
+        println!("[PASSED] test_prefer_codec_over_quality");
+
+        assert_eq!(choose(Some("av01")).unwrap(), 248);
+        println!("[PASSED] test_prefer_codec_falls_back_to_other_codecs");
+    }
+
+    #[test]
+    fn test_mime_type_lenient_parse() {
+        let mime_type: crate::structs::MimeType =
+            serde_json::from_str(r#""video/mp4; codecs=\"avc1.4d401f, mp4a.40.2\"""#).unwrap();
+        assert_eq!(mime_type.container, "mp4");
+        assert_eq!(mime_type.video_codec.as_deref(), Some("avc1.4d401f"));
+        assert_eq!(mime_type.audio_codec.as_deref(), Some("mp4a.40.2"));
+        println!("[PASSED] test_mime_type_parse");
+
+        let mime_type: crate::structs::MimeType =
+            serde_json::from_str(r#""video; codecs=\"av02\"""#).unwrap();
+        assert_eq!(mime_type.mime, mime::APPLICATION_OCTET_STREAM);
+        assert_eq!(mime_type.codecs, vec!["av02".to_string()]);
+        assert_eq!(mime_type.video_codec.as_deref(), Some("av02"));
+        println!("[PASSED] test_mime_type_invalid_falls_back");
+    }
+
+    #[test]
+    fn test_get_user_agent_of_url() {
+        assert_eq!(
+            get_user_agent_of_url(
+                "https://rr1---sn-4g5e6nzz.googlevideo.com/videoplayback?itag=18&c=IOS&n=abc"
+            ),
+            Some("com.google.ios.youtube/19.29.1 (iPhone16,2; U; CPU iOS 17_5_1 like Mac OS X;)")
+        );
+        println!("[PASSED] test_user_agent_of_ios_url");
+
+        assert_eq!(
+            get_user_agent_of_url(
+                "https://rr1---sn-4g5e6nzz.googlevideo.com/videoplayback?itag=18&c=NEW_CLIENT"
+            ),
+            None
+        );
+        assert_eq!(
+            get_user_agent_of_url(
+                "https://rr1---sn-4g5e6nzz.googlevideo.com/videoplayback?itag=18"
+            ),
+            None
+        );
+        println!("[PASSED] test_user_agent_of_unknown_client");
+    }
+
+    #[test]
+    fn test_video_quality_serde() {
+        assert_eq!(
+            serde_json::to_value(VideoQuality::HighestAudio).unwrap(),
+            serde_json::json!("highestAudio")
+        );
+        assert_eq!(
+            serde_json::to_value(VideoQuality::Itag(18)).unwrap(),
+            serde_json::json!({ "itag": 18 })
+        );
+        assert_eq!(
+            serde_json::from_value::<VideoQuality>(serde_json::json!({ "maxFileSize": 1000 }))
+                .unwrap(),
+            VideoQuality::MaxFileSize(1000)
+        );
+        assert_eq!(
+            serde_json::from_value::<VideoSearchOptions>(serde_json::json!("videoAudio")).unwrap(),
+            VideoSearchOptions::VideoAudio
+        );
+        println!("[PASSED] test_video_quality_serde_round_trip");
+
+        assert!(
+            serde_json::to_value(VideoSearchOptions::Custom(std::sync::Arc::new(
+                |_: &VideoFormat| true
+            )))
+            .is_err()
+        );
+        assert!(serde_json::from_value::<VideoQuality>(serde_json::json!("custom")).is_err());
+        println!("[PASSED] test_video_quality_serde_custom_fails");
+    }
+
+    #[test]
+    fn test_get_random_v4_ip() {
+        for _ in 0..100 {
+            let IpAddr::V4(ip) = get_random_v4_ip("203.0.113.0/24").unwrap() else {
+                panic!("Not an IPv4 address");
+            };
+            assert_eq!(ip.octets()[..3], [203, 0, 113]);
+        }
+        println!("[PASSED] test_random_v4_ip_in_block");
+
+        assert_eq!(
+            get_random_v4_ip("198.51.100.7/32").unwrap(),
+            "198.51.100.7".parse::<IpAddr>().unwrap()
+        );
+        println!("[PASSED] test_random_v4_ip_single_address");
+
+        assert!(matches!(
+            get_random_v4_ip("203.0.113.0"),
+            Err(VideoError::InvalidIPv4Format)
+        ));
+        assert!(matches!(
+            get_random_v4_ip("203.0.113/24"),
+            Err(VideoError::InvalidIPv4Format)
+        ));
+        assert!(matches!(
+            get_random_v4_ip("203.0.113.0/33"),
+            Err(VideoError::InvalidIPv4Subnet)
+        ));
+        println!("[PASSED] test_random_v4_ip_invalid_block");
+    }
+
+    #[test]
+    fn test_video_error_is_retryable() {
+        assert!(!VideoError::VideoIsPrivate.is_retryable());
+        assert!(!VideoError::FormatNotFound.is_retryable());
+        assert!(!VideoError::InvalidIPv6Format.is_retryable());
+        assert!(!VideoError::Cancelled.is_retryable());
+        println!("[PASSED] test_permanent_errors_are_not_retryable");
+
+        assert_eq!(VideoError::VideoNotFound.status_code(), None);
+        println!("[PASSED] test_status_code_of_non_reqwest_error");
+
+        let status_error = |status: u16| {
+            reqwest::Response::from(http::Response::builder().status(status).body("").unwrap())
+                .error_for_status()
+                .unwrap_err()
+        };
+
+        let error =
+            VideoError::ReqwestMiddleware(reqwest_middleware::Error::middleware(status_error(429)));
+        assert_eq!(error.status_code(), Some(429));
+        assert!(error.is_retryable());
+        println!("[PASSED] test_error_raised_by_middleware");
+
+        let error = VideoError::ReqwestMiddleware(reqwest_middleware::Error::middleware(
+            reqwest_middleware::Error::Reqwest(status_error(404)),
+        ));
+        assert_eq!(error.status_code(), Some(404));
+        assert!(!error.is_retryable());
+        println!("[PASSED] test_nested_middleware_error");
+    }
+
+    #[test]
+    fn test_dl_chunk_size() {
+        use crate::constants::DEFAULT_DL_CHUNK_SIZE;
+
+        let chunk_size = |dl_chunk_size| {
+            crate::DownloadOptions {
+                dl_chunk_size,
+                ..Default::default()
+            }
+            .chunk_size()
+        };
+
+        assert_eq!(chunk_size(None), DEFAULT_DL_CHUNK_SIZE);
+        assert_eq!(chunk_size(Some(1048576)), 1048576);
+        println!("[PASSED] test_dl_chunk_size_in_range");
+
+        assert_eq!(chunk_size(Some(0)), MIN_DL_CHUNK_SIZE);
+        assert_eq!(chunk_size(Some(1024)), MIN_DL_CHUNK_SIZE);
+        println!("[PASSED] test_dl_chunk_size_clamped_to_minimum");
+
+        assert_eq!(chunk_size(Some(u64::MAX)), MAX_DL_CHUNK_SIZE);
+        assert_eq!(
+            chunk_size(Some(DEFAULT_DL_CHUNK_SIZE + 1)),
+            MAX_DL_CHUNK_SIZE
+        );
+        println!("[PASSED] test_dl_chunk_size_clamped_to_maximum");
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_445_412_480);
+
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(std::time::Duration::from_secs(120))
+        );
+        println!("[PASSED] test_retry_after_seconds");
+
+        // 2015-10-21 07:28:00 GMT is 1445412480
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(std::time::Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now),
+            Some(std::time::Duration::ZERO)
+        );
+        println!("[PASSED] test_retry_after_http_date");
+
+        assert_eq!(parse_retry_after("soon", now), None);
+        println!("[PASSED] test_retry_after_invalid");
+    }
+
+    #[test]
+    fn test_storyboard_tile_at() {
+        let storyboard = crate::structs::StoryBoard {
+            template_url: "https://i.ytimg.com/sb/FZ8BxMU3BYc/storyboard3_L2/M$M.jpg".to_string(),
+            thumbnail_width: 160,
+            thumbnail_height: 90,
+            thumbnail_count: 100,
+            interval: 2000,
+            columns: 5,
+            rows: 5,
+            storyboard_count: 4,
+        };
+
+        let tile = storyboard.tile_at(0, 200_000).unwrap();
+        assert_eq!(
+            tile.url,
+            "https://i.ytimg.com/sb/FZ8BxMU3BYc/storyboard3_L2/M0.jpg"
+        );
+        assert_eq!((tile.x, tile.y, tile.width, tile.height), (0, 0, 160, 90));
+        println!("[PASSED] test_first_storyboard_tile");
+
+        // Thumbnail 31 => second sheet, position 6 => column 1, row 1
+        let tile = storyboard.tile_at(62_500, 200_000).unwrap();
+        assert_eq!(
+            tile.url,
+            "https://i.ytimg.com/sb/FZ8BxMU3BYc/storyboard3_L2/M1.jpg"
+        );
+        assert_eq!((tile.x, tile.y), (160, 90));
+        println!("[PASSED] test_storyboard_tile_on_next_sheet");
+
+        let tile = storyboard.tile_at(10_000_000, 200_000).unwrap();
+        assert_eq!(
+            tile.url,
+            "https://i.ytimg.com/sb/FZ8BxMU3BYc/storyboard3_L2/M3.jpg"
+        );
+        assert_eq!((tile.x, tile.y), (640, 360));
+        println!("[PASSED] test_storyboard_tile_after_end");
+
+        // Thumbnails are spread over the video length without interval
+        let storyboard = crate::structs::StoryBoard {
+            interval: 0,
+            ..storyboard
+        };
+        assert_eq!(storyboard.tile_at(100_000, 200_000).unwrap().x, 0);
+        assert_eq!(storyboard.tile_at(100_000, 200_000).unwrap().y, 0);
+        assert!(storyboard
+            .tile_at(100_000, 200_000)
+            .unwrap()
+            .url
+            .ends_with("M2.jpg"));
+        println!("[PASSED] test_storyboard_tile_without_interval");
+    }
+
+    #[test]
+    fn test_get_description_chapters() {
+        let chapters = |description: &str| {
+            get_chapters(
+                &serde_json::json!({}),
+                &serde_json::from_value::<PlayerResponse>(serde_json::json!({
+                    "videoDetails": { "shortDescription": description, "lengthSeconds": "3700" }
+                }))
+                .unwrap(),
+            )
+            .unwrap()
+            .into_iter()
+            .map(|x| (x.start_time, x.title))
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            chapters("Tracklist:\n0:00 Intro\n1:30 - Verse\n(12:05) Chorus\nOutro 1:01:00\nPart 2: https://youtu.be/x at 2:00:00"),
+            vec![
+                (0, "Intro".to_string()),
+                (90, "Verse".to_string()),
+                (725, "Chorus".to_string()),
+                (3660, "Outro".to_string()),
+            ]
+        );
+        println!("[PASSED] test_description_chapters");
+
+        assert!(chapters("1:00 Intro\n2:00 Verse\n3:00 Chorus").is_empty());
+        assert!(chapters("0:00 Intro\n2:00 Verse").is_empty());
+        println!("[PASSED] test_description_chapters_rules");
+    }
+
+    #[test]
+    fn test_estimated_file_size() {
+        let format = synthetic_format(18, "360p", 500_000, Some(6_000_000));
+        assert_eq!(format.estimated_file_size(None), Some(6_000_000));
+        println!("[PASSED] test_estimated_file_size_uses_content_length");
+
+        // 4Mbps for 100 seconds
+        let mut format = synthetic_format(37, "1080p", 4_000_000, None);
+        assert_eq!(format.estimated_file_size(None), Some(50_000_000));
+        println!("[PASSED] test_estimated_file_size_from_bitrate");
+
+        format.average_bitrate = Some(2_000_000);
+        assert_eq!(format.estimated_file_size(None), Some(25_000_000));
+        println!("[PASSED] test_estimated_file_size_prefers_average_bitrate");
+
+        format.approx_duration_ms = None;
+        assert_eq!(format.estimated_file_size(Some(10)), Some(2_500_000));
+        assert_eq!(format.estimated_file_size(None), None);
+        println!("[PASSED] test_estimated_file_size_from_length_seconds");
+    }
+
+    #[test]
+    #[cfg(feature = "ffmpeg")]
+    fn test_ffmpeg_args_trim() {
+        let args = crate::structs::FFmpegArgs {
+            format: Some("mp3".to_string()),
+            start: Some(std::time::Duration::from_millis(90_500)),
+            end: Some(std::time::Duration::from_secs(120)),
+            ..Default::default()
+        }
+        .build();
+
+        let input_position = args.iter().position(|x| x == "-i").unwrap();
+        let start_position = args.iter().position(|x| x == "-ss").unwrap();
+
+        assert!(start_position > input_position);
+        assert_eq!(args[start_position + 1], "90.500");
+        assert!(args.windows(2).any(|x| x == ["-to", "120.000"]));
+        assert_eq!(args.last().unwrap(), "pipe:1");
+        println!("[PASSED] test_ffmpeg_args_trim");
+    }
+
+    #[test]
+    #[cfg(feature = "ffmpeg")]
+    fn test_ffmpeg_args_presets() {
+        use crate::structs::FFmpegArgs;
+
+        let output_args = |args: FFmpegArgs| {
+            let args = args.build();
+            let input_position = args.iter().position(|x| x == "-i").unwrap();
+
+            assert_eq!(args[input_position + 1], "-");
+            assert_eq!(args.last().unwrap(), "pipe:1");
+
+            args[input_position + 6..args.len() - 1].to_vec()
+        };
+
+        assert_eq!(
+            output_args(FFmpegArgs::to_mp3(192)),
+            ["-f", "mp3", "-vn", "-c:a", "libmp3lame", "-b:a", "192k"]
+        );
+        println!("[PASSED] test_ffmpeg_args_to_mp3");
+
+        assert_eq!(
+            output_args(FFmpegArgs::to_aac(128)),
+            ["-f", "adts", "-vn", "-c:a", "aac", "-b:a", "128k"]
+        );
+        println!("[PASSED] test_ffmpeg_args_to_aac");
+
+        assert_eq!(
+            output_args(FFmpegArgs::extract_audio()),
+            ["-f", "matroska", "-vn", "-c:a", "copy"]
+        );
+        println!("[PASSED] test_ffmpeg_args_extract_audio");
+
+        assert!(FFmpegArgs::default().build().is_empty());
+    }
+
+    #[test]
+    fn test_is_age_gated() {
+        let age_gated = |status: &str, reason: &str| {
+            is_age_gated(
+                &serde_json::from_value::<PlayerResponse>(serde_json::json!({
+                    "playabilityStatus": { "status": status, "reason": reason }
+                }))
+                .unwrap(),
+            )
+        };
+
+        assert!(age_gated("LOGIN_REQUIRED", "Sign in to confirm your age"));
+        assert!(age_gated(
+            "LOGIN_REQUIRED",
+            "This video may be inappropriate for some users."
+        ));
+        println!("[PASSED] test_is_age_gated");
+
+        assert!(!age_gated("LOGIN_REQUIRED", "This video is private"));
+        assert!(!age_gated("OK", ""));
+        println!("[PASSED] test_is_not_age_gated");
+    }
+
+    #[test]
+    fn test_get_availability() {
+        let availability = |status: &str, reason: &str| {
+            get_availability(
+                &serde_json::from_value::<PlayerResponse>(serde_json::json!({
+                    "playabilityStatus": { "status": status, "reason": reason }
+                }))
+                .unwrap(),
+            )
+        };
+
+        assert_eq!(availability("OK", ""), Availability::Available);
+        assert_eq!(
+            availability("LOGIN_REQUIRED", "This video is private"),
+            Availability::Private
+        );
+        assert_eq!(
+            availability("LOGIN_REQUIRED", "Sign in to confirm your age"),
+            Availability::AgeRestricted
+        );
+        assert_eq!(
+            availability(
+                "UNPLAYABLE",
+                "Join this channel to get access to members-only content like this video, and other exclusive perks."
+            ),
+            Availability::MembersOnly
+        );
+        assert_eq!(
+            availability("ERROR", "Video unavailable"),
+            Availability::Removed
+        );
+        assert_eq!(
+            availability("LIVE_STREAM_OFFLINE", "Premieres in 2 hours"),
+            Availability::LiveUpcoming
+        );
+        assert_eq!(
+            availability(
+                "LIVE_STREAM_OFFLINE",
+                "This live stream recording is not available."
+            ),
+            Availability::LiveOffline
+        );
+        assert_eq!(
+            availability(
+                "UNPLAYABLE",
+                "The uploader has not made this video available in your country"
+            ),
+            Availability::GeoRestricted {
+                available_countries: vec![]
+            }
+        );
+        assert_eq!(
+            availability("LOGIN_REQUIRED", "Sign in to confirm you're not a bot"),
+            Availability::Unavailable("Sign in to confirm you're not a bot".to_string())
+        );
+        println!("[PASSED] test_get_availability");
+    }
+
+    #[test]
+    fn test_parse_relative_time() {
+        let day = 24 * 60 * 60;
+
+        assert_eq!(
+            parse_relative_time("2 years ago"),
+            Some(std::time::Duration::from_secs(2 * 365 * day))
+        );
+        assert_eq!(
+            parse_relative_time("Streamed 3 days ago"),
+            Some(std::time::Duration::from_secs(3 * day))
+        );
+        assert_eq!(
+            parse_relative_time("1 month ago"),
+            Some(std::time::Duration::from_secs(30 * day))
+        );
+        assert_eq!(
+            parse_relative_time("45 minutes ago"),
+            Some(std::time::Duration::from_secs(45 * 60))
+        );
+        assert_eq!(parse_relative_time("Premiered"), None);
+        println!("[PASSED] test_parse_relative_time");
+    }
+
+    #[test]
+    fn test_live_and_upcoming_search_video() {
+        let live = serde_json::json!({
+            "videoId": "jfKfPfyJRdk",
+            "badges": [{
+                "metadataBadgeRenderer": { "style": "BADGE_STYLE_TYPE_LIVE_NOW", "label": "LIVE" }
+            }],
+            "thumbnailOverlays": [{
+                "thumbnailOverlayTimeStatusRenderer": { "style": "LIVE" }
+            }]
+        });
+        let upcoming = serde_json::json!({
+            "videoId": "5qap5aO4i9A",
+            "upcomingEventData": { "startTime": "1767225600", "isReminderSet": false },
+            "thumbnailOverlays": [{
+                "thumbnailOverlayTimeStatusRenderer": { "style": "UPCOMING" }
+            }]
+        });
+        let video = serde_json::json!({
+            "videoId": "FZ8BxMU3BYc",
+            "lengthText": { "simpleText": "3:33" },
+            "thumbnailOverlays": [{
+                "thumbnailOverlayTimeStatusRenderer": { "style": "DEFAULT" }
+            }]
+        });
+
+        assert!(is_live_now(&live));
+        assert!(!is_upcoming(&live));
+        assert_eq!(get_scheduled_start_time(&live), None);
+        println!("[PASSED] test_live_search_video");
+
+        assert!(!is_live_now(&upcoming));
+        assert!(is_upcoming(&upcoming));
+        assert_eq!(get_scheduled_start_time(&upcoming), Some(1767225600));
+        println!("[PASSED] test_upcoming_search_video");
+
+        assert!(!is_live_now(&video));
+        assert!(!is_upcoming(&video));
+        println!("[PASSED] test_default_search_video");
+    }
+
+    #[test]
+    fn test_parse_suggestions() {
+        let body = r#"["i know ",["i know your ways","i know places"],[],{"k":1}]"#;
+
+        assert_eq!(
+            parse_suggestions(body).unwrap(),
+            vec!["i know your ways".to_string(), "i know places".to_string()]
+        );
+        println!("[PASSED] test_parse_suggestions");
+
+        assert!(parse_suggestions("").is_err());
+        assert!(parse_suggestions("<html></html>").is_err());
+        assert!(parse_suggestions(r#"{"error":"bad request"}"#).is_err());
+        assert!(parse_suggestions(r#"["i know "]"#).is_err());
+        println!("[PASSED] test_parse_malformed_suggestions");
+    }
+
+    #[test]
+    fn test_category_and_tags() {
+        let details = |initial_response: serde_json::Value, category: &str| {
+            clean_video_details(
+                &initial_response,
+                &serde_json::from_value::<PlayerResponse>(serde_json::json!({
+                    "videoDetails": { "videoId": "FZ8BxMU3BYc", "keywords": ["rick", "astley"] },
+                    "microformat": { "playerMicroformatRenderer": { "category": category } }
+                }))
+                .unwrap(),
+                serde_json::Value::Null,
+                "FZ8BxMU3BYc".to_string(),
+            )
+        };
+        let watch_page = |rows: serde_json::Value| {
+            serde_json::json!({
+                "contents": { "twoColumnWatchNextResults": { "results": { "results": { "contents": [
+                    { "videoPrimaryInfoRenderer": {} },
+                    { "videoSecondaryInfoRenderer": {
+                        "metadataRowContainer": { "metadataRowContainerRenderer": { "rows": rows } }
+                    } }
+                ] } } } },
+                "microformat": { "microformatDataRenderer": { "tags": ["astley", "never gonna"] } }
+            })
+        };
+
+        let music = watch_page(serde_json::json!([
+            { "metadataRowRenderer": {
+                "title": { "simpleText": "Song" },
+                "contents": [{ "runs": [{ "text": "Never Gonna Give You Up" }] }]
+            } },
+            { "metadataRowRenderer": {
+                "title": { "simpleText": "Artist" },
+                "contents": [{ "simpleText": "Rick Astley" }]
+            } }
+        ]));
+        let gaming = watch_page(serde_json::json!([
+            { "richMetadataRowRenderer": { "contents": [
+                { "richMetadataRenderer": {
+                    "style": "RICH_METADATA_RENDERER_STYLE_BOX_ART",
+                    "title": { "simpleText": "Minecraft" }
+                } },
+                { "richMetadataRenderer": {
+                    "style": "RICH_METADATA_RENDERER_STYLE_TOPIC",
+                    "title": { "simpleText": "Gaming" }
+                } }
+            ] } }
+        ]));
+        let category_row = watch_page(serde_json::json!([
+            { "metadataRowRenderer": {
+                "title": { "simpleText": "Category" },
+                "contents": [{ "runs": [{ "text": "Education" }] }]
+            } }
+        ]));
+
+        assert_eq!(details(music.clone(), "").category, "Music");
+        assert_eq!(details(gaming, "").category, "Gaming");
+        assert_eq!(details(category_row, "").category, "Education");
+        assert_eq!(
+            details(music.clone(), "Entertainment").category,
+            "Entertainment"
+        );
+        println!("[PASSED] test_category");
+
+        assert_eq!(
+            details(music, "").keywords,
+            vec!["rick", "astley", "never gonna"]
+        );
+        println!("[PASSED] test_tags");
+    }
+
+    #[test]
+    fn test_get_dvr_start_index() {
+        let durations = vec![std::time::Duration::from_secs(5); 10];
+
+        assert_eq!(
+            get_dvr_start_index(&durations, std::time::Duration::from_secs(20)),
+            (6, std::time::Duration::from_secs(20))
+        );
+        // Segment at the offset is kept whole
+        assert_eq!(
+            get_dvr_start_index(&durations, std::time::Duration::from_secs(12)),
+            (7, std::time::Duration::from_secs(15))
+        );
+        println!("[PASSED] test_get_dvr_start_index");
+
+        // Offset beyond the DVR window starts from the oldest segment
+        assert_eq!(
+            get_dvr_start_index(&durations, std::time::Duration::from_secs(600)),
+            (0, std::time::Duration::from_secs(50))
+        );
+        assert_eq!(
+            get_dvr_start_index(&durations, std::time::Duration::ZERO),
+            (10, std::time::Duration::ZERO)
+        );
+        println!("[PASSED] test_get_dvr_start_index_out_of_window");
+    }
+
+    #[test]
+    fn test_get_clip() {
+        assert_eq!(
+            get_clip_id("https://www.youtube.com/clip/UgkxU2HSeGL_NvmDJ-nQJrlLwllwMDBdGZFs?si=abc"),
+            Some("UgkxU2HSeGL_NvmDJ-nQJrlLwllwMDBdGZFs".to_string())
+        );
+        assert_eq!(
+            get_clip_id("https://www.youtube.com/watch?v=FZ8BxMU3BYc"),
+            None
+        );
+        // Clip URLs do not contain the id of the video
+        assert_eq!(
+            get_video_id("https://www.youtube.com/clip/UgkxU2HSeGL_NvmDJ-nQJrlLwllwMDBdGZFs"),
+            None
+        );
+        println!("[PASSED] test_get_clip_id");
+
+        let initial_data = serde_json::json!({
+            "currentVideoEndpoint": { "watchEndpoint": { "videoId": "FZ8BxMU3BYc" } },
+            "engagementPanels": [{
+                "engagementPanelSectionListRenderer": {
+                    "content": { "clipSectionRenderer": { "contents": [{
+                        "clipAttributionRenderer": { "onScrubExit": { "commandExecutorCommand": {
+                            "commands": [{ "loopCommand": {
+                                "startTimeMs": "61000",
+                                "endTimeMs": "76500",
+                                "postId": "UgkxU2HSeGL_NvmDJ-nQJrlLwllwMDBdGZFs"
+                            } }]
+                        } } }
+                    }] } }
+                }
+            }]
+        });
+
+        let (video_id, clip) = get_clip(&initial_data, "UgkxU2HSeGL_NvmDJ-nQJrlLwllwMDBdGZFs")
+            .expect("Clip is parsed");
+
+        assert_eq!(video_id, "FZ8BxMU3BYc");
+        assert_eq!(clip.start(), std::time::Duration::from_millis(61000));
+        assert_eq!(clip.end(), std::time::Duration::from_millis(76500));
+        assert!(get_clip(&serde_json::json!({}), "").is_none());
+        println!("[PASSED] test_get_clip");
+    }
+
+    #[test]
+    fn test_transcript() {
+        use crate::info_extras::{
+            get_transcript_languages, get_transcript_params, get_transcript_segments,
+        };
+        use crate::structs::TranscriptSegment;
+
+        let initial_data = serde_json::json!({
+            "engagementPanels": [
+                { "engagementPanelSectionListRenderer": { "panelIdentifier": "comment-item-section" } },
+                { "engagementPanelSectionListRenderer": {
+                    "panelIdentifier": "engagement-panel-searchable-transcript",
+                    "content": { "continuationItemRenderer": { "continuationEndpoint": {
+                        "getTranscriptEndpoint": { "params": "CgtGWjhCeE1VM0JZYw%3D%3D" }
+                    } } }
+                } }
+            ]
+        });
+
+        assert_eq!(
+            get_transcript_params(&initial_data).as_deref(),
+            Some("CgtGWjhCeE1VM0JZYw%3D%3D")
+        );
+        println!("[PASSED] test_get_transcript_params");
+
+        let response = serde_json::json!({
+            "actions": [{ "updateEngagementPanelAction": { "content": { "transcriptRenderer": {
+                "content": { "transcriptSearchPanelRenderer": {
+                    "body": { "transcriptSegmentListRenderer": { "initialSegments": [
+                        { "transcriptSectionHeaderRenderer": { "startMs": "0" } },
+                        { "transcriptSegmentRenderer": {
+                            "startMs": "1200",
+                            "endMs": "4000",
+                            "snippet": { "runs": [{ "text": "We're no strangers " }, { "text": "to love" }] }
+                        } }
+                    ] } },
+                    "footer": { "transcriptFooterRenderer": { "languageMenu": { "sortFilterSubMenuRenderer": {
+                        "subMenuItems": [
+                            {
+                                "title": "English",
+                                "selected": true,
+                                "continuation": { "reloadContinuationData": { "continuation": "en" } }
+                            },
+                            {
+                                "title": "German",
+                                "selected": false,
+                                "continuation": { "reloadContinuationData": { "continuation": "de" } }
+                            }
+                        ]
+                    } } } }
+                } }
+            } } } }]
+        });
+
+        assert_eq!(
+            get_transcript_segments(&response),
+            vec![TranscriptSegment {
+                start_ms: 1200,
+                duration_ms: 2800,
+                text: "We're no strangers to love".to_string(),
+            }]
+        );
+        println!("[PASSED] test_get_transcript_segments");
+
+        assert_eq!(
+            get_transcript_languages(&response),
+            vec![
+                ("English".to_string(), "en".to_string(), true),
+                ("German".to_string(), "de".to_string(), false),
+            ]
+        );
+        println!("[PASSED] test_get_transcript_languages");
+    }
 }
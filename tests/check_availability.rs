@@ -0,0 +1,14 @@
+#[tokio::test]
+async fn check_availability() {
+    use rusty_ytdl::{Availability, Video};
+
+    let url = "https://www.youtube.com/watch?v=FZ8BxMU3BYc";
+
+    let video = Video::new(url).unwrap();
+
+    let availability = video.check_availability().await.unwrap();
+
+    assert_eq!(availability, Availability::Available);
+
+    println!("Availability: {:#?}", availability);
+}
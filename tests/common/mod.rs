@@ -0,0 +1,47 @@
+use rusty_ytdl::stream::{NonLiveStream, NonLiveStreamOptions};
+use rusty_ytdl::{choose_format, Video, VideoOptions, VideoQuality, VideoSearchOptions};
+
+/// [`NonLiveStream`] of the lowest audio format of the test video and its content length
+pub async fn lowest_audio_stream() -> (NonLiveStream, u64) {
+    let url = "https://www.youtube.com/watch?v=FZ8BxMU3BYc";
+
+    let video_options = VideoOptions {
+        quality: VideoQuality::Lowest,
+        filter: VideoSearchOptions::Audio,
+        ..Default::default()
+    };
+
+    let video = Video::new_with_options(url, &video_options).unwrap();
+
+    let info = video.get_info().await.unwrap();
+    let format = choose_format(&info.formats, &video_options).unwrap();
+
+    let content_length = format
+        .content_length
+        .as_ref()
+        .and_then(|x| x.parse::<u64>().ok())
+        .unwrap();
+
+    let dl_chunk_size = 1024 * 256;
+
+    let stream = NonLiveStream::new(NonLiveStreamOptions {
+        client: None,
+        link: format.url,
+        content_length,
+        dl_chunk_size,
+        start: 0,
+        end: dl_chunk_size,
+        on_progress: None,
+        refresh_link: None,
+        parallel_connections: None,
+        max_bytes_per_second: None,
+        cancellation: None,
+        verify_length: true,
+        content_type: Some(format.mime_type.mime.essence_str().to_string()),
+        #[cfg(feature = "ffmpeg")]
+        ffmpeg_args: None,
+    })
+    .unwrap();
+
+    (stream, content_length)
+}
@@ -0,0 +1,43 @@
+#[tokio::test]
+async fn download_resume() {
+    use rusty_ytdl::{DownloadOptions, Video, VideoOptions, VideoQuality, VideoSearchOptions};
+
+    let url = "https://www.youtube.com/watch?v=FZ8BxMU3BYc";
+    let dir = std::env::temp_dir();
+    let full_path = dir.join("rusty_ytdl_download_resume_full.webm");
+    let resumed_path = dir.join("rusty_ytdl_download_resume_partial.webm");
+
+    let video_options = VideoOptions {
+        quality: VideoQuality::Lowest,
+        filter: VideoSearchOptions::Audio,
+        ..Default::default()
+    };
+
+    let video = Video::new_with_options(url, &video_options).unwrap();
+    video.download(&full_path).await.unwrap();
+    let full = std::fs::read(&full_path).unwrap();
+
+    // Interrupted download with garbage after the offset, it is overwritten by the resume
+    let start_offset = 100_000;
+    let mut partial = full[..start_offset].to_vec();
+    partial.extend_from_slice(&[0u8; 1000]);
+    std::fs::write(&resumed_path, partial).unwrap();
+
+    let resume_options = VideoOptions {
+        download_options: DownloadOptions {
+            start_offset: Some(start_offset as u64),
+            ..Default::default()
+        },
+        ..video_options.clone()
+    };
+
+    let video = Video::new_with_options(url, resume_options).unwrap();
+    video.download(&resumed_path).await.unwrap();
+    let resumed = std::fs::read(&resumed_path).unwrap();
+
+    assert_eq!(resumed.len(), full.len());
+    assert!(resumed == full);
+
+    let _ = std::fs::remove_file(full_path);
+    let _ = std::fs::remove_file(resumed_path);
+}
@@ -0,0 +1,22 @@
+#[tokio::test]
+async fn download_to_end() {
+    use rusty_ytdl::{Video, VideoOptions, VideoQuality, VideoSearchOptions};
+
+    let url = "https://www.youtube.com/watch?v=FZ8BxMU3BYc";
+
+    let video_options = VideoOptions {
+        quality: VideoQuality::Lowest,
+        filter: VideoSearchOptions::Audio,
+        ..Default::default()
+    };
+
+    let video = Video::new_with_options(url, video_options).unwrap();
+
+    let stream = video.stream().await.unwrap();
+
+    let bytes = stream.download_to_end().await.unwrap();
+
+    assert_eq!(bytes.len(), stream.content_length());
+
+    println!("{} byte downloaded", bytes.len());
+}
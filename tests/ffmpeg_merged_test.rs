@@ -0,0 +1,31 @@
+#[ignore]
+#[tokio::test]
+async fn ffmpeg_merged_test() {
+    #[cfg(feature = "ffmpeg")]
+    {
+        use rusty_ytdl::{Video, VideoOptions, VideoQuality};
+
+        let url = "FZ8BxMU3BYc";
+
+        let video_options = VideoOptions {
+            quality: VideoQuality::Resolution(1080),
+            ..Default::default()
+        };
+
+        let video = Video::new_with_options(url, video_options).unwrap();
+
+        let stream = video
+            .stream_merged(Some(std::time::Duration::from_secs(600)))
+            .await
+            .unwrap();
+
+        assert!(stream.content_length() > 0);
+
+        let mut total = 0;
+        while let Some(chunk) = stream.chunk().await.unwrap() {
+            total += chunk.len();
+        }
+
+        assert!(total > 0);
+    }
+}
@@ -0,0 +1,20 @@
+#[tokio::test]
+async fn get_comments() {
+    use rusty_ytdl::{CommentOptions, CommentSortBy, Video};
+
+    let url = "https://www.youtube.com/watch?v=FZ8BxMU3BYc";
+
+    let video = Video::new(url).unwrap();
+
+    let comments = video
+        .get_comments(CommentOptions {
+            limit: 30,
+            sort_by: CommentSortBy::Newest,
+        })
+        .await
+        .unwrap();
+
+    assert!(comments.len() <= 30);
+
+    println!("Comments: {:#?}", comments);
+}
@@ -0,0 +1,15 @@
+#[tokio::test]
+async fn get_hls_manifest_url() {
+    use rusty_ytdl::Video;
+
+    // 24/7 live stream, exposes HLS manifest
+    let url = "https://www.youtube.com/watch?v=jfKfPfyJRdk";
+
+    let video = Video::new(url).unwrap();
+
+    let hls_manifest_url = video.get_hls_manifest_url().await.unwrap();
+
+    assert!(hls_manifest_url.is_some());
+
+    println!("HLS manifest URL: {:#?}", hls_manifest_url);
+}
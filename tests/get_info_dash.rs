@@ -0,0 +1,23 @@
+#[tokio::test]
+async fn get_info_dash() {
+    use rusty_ytdl::Video;
+
+    // 24/7 live stream, exposes DASH manifest
+    let url = "https://www.youtube.com/watch?v=jfKfPfyJRdk";
+
+    let video = Video::new(url).unwrap();
+
+    let video_info = video.get_info().await.unwrap();
+
+    assert!(video_info.dash_manifest_url.is_some());
+
+    let dash_formats: Vec<_> = video_info
+        .formats
+        .iter()
+        .filter(|format| format.is_dash_mpd)
+        .collect();
+
+    assert!(!dash_formats.is_empty());
+
+    println!("DASH Formats: {:#?}", dash_formats);
+}
@@ -0,0 +1,14 @@
+#[tokio::test]
+async fn get_metadata() {
+    use rusty_ytdl::Video;
+
+    let url = "https://www.youtube.com/watch?v=FZ8BxMU3BYc";
+
+    let video = Video::new(url).unwrap();
+
+    let video_info = video.get_metadata().await.unwrap();
+
+    assert!(!video_info.video_details.title.is_empty());
+
+    println!("Video details: {:#?}", video_info.video_details);
+}
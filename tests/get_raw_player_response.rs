@@ -0,0 +1,17 @@
+#[tokio::test]
+async fn get_raw_player_response() {
+    use rusty_ytdl::Video;
+
+    let url = "https://www.youtube.com/watch?v=FZ8BxMU3BYc";
+
+    let video = Video::new(url).unwrap();
+
+    let player_response = video.get_raw_player_response().await.unwrap();
+
+    assert_eq!(player_response["videoDetails"]["videoId"], "FZ8BxMU3BYc");
+
+    println!(
+        "Playability status: {:#?}",
+        player_response["playabilityStatus"]["status"]
+    );
+}
@@ -0,0 +1,14 @@
+#[tokio::test]
+async fn get_related_videos() {
+    use rusty_ytdl::Video;
+
+    let url = "https://www.youtube.com/watch?v=FZ8BxMU3BYc";
+
+    let video = Video::new(url).unwrap();
+
+    let related_videos = video.get_related_videos().await.unwrap();
+
+    assert!(!related_videos.is_empty());
+
+    println!("Related videos: {:#?}", related_videos);
+}
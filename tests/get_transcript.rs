@@ -0,0 +1,15 @@
+#[tokio::test]
+async fn get_transcript() {
+    use rusty_ytdl::Video;
+
+    let url = "https://www.youtube.com/watch?v=FZ8BxMU3BYc";
+
+    let video = Video::new(url).unwrap();
+
+    let transcript = video.get_transcript(Some("en")).await.unwrap();
+
+    assert!(!transcript.is_empty());
+    assert!(transcript.iter().all(|x| !x.text.is_empty()));
+
+    println!("Transcript: {:#?}", transcript);
+}
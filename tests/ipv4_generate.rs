@@ -0,0 +1,7 @@
+use rusty_ytdl::*;
+
+#[test]
+fn generate_random_v4_ip() {
+    let ipv4_format = "203.0.113.0/24";
+    println!("{:?}", get_random_v4_ip(ipv4_format).unwrap().to_string());
+}
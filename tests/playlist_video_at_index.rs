@@ -0,0 +1,16 @@
+#[tokio::test]
+async fn playlist_video_at_index() {
+    use rusty_ytdl::search::Playlist;
+
+    let video = Playlist::get_video_at_index(
+        "https://www.youtube.com/watch?v=FZ8BxMU3BYc&list=PLwMEL7UNT4o9iMzrvNBXZqXbNPFfT6rVD&index=3",
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(video.playlist_index, Some(3));
+    assert!(!video.id.is_empty());
+
+    println!("{:#?}", video.id);
+}
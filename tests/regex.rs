@@ -23,6 +23,48 @@ async fn is_valid_id_or_link() {
         Some("FZ8BxMU3BYc".to_string()),
         get_video_id("https://www.youtube.com/embed/FZ8BxMU3BYc")
     );
+    assert_eq!(
+        Some("FZ8BxMU3BYc".to_string()),
+        get_video_id("https://www.youtube.com/live/FZ8BxMU3BYc")
+    );
+    assert_eq!(
+        Some("FZ8BxMU3BYc".to_string()),
+        get_video_id("https://www.youtube.com/live/FZ8BxMU3BYc?feature=share")
+    );
+    assert_eq!(
+        Some("FZ8BxMU3BYc".to_string()),
+        get_video_id("https://youtube.com/shorts/FZ8BxMU3BYc?si=0dQe1b2Xq3yZ4w5v")
+    );
+    assert_eq!(
+        Some("FZ8BxMU3BYc".to_string()),
+        get_video_id("https://music.youtube.com/watch?v=FZ8BxMU3BYc&si=0dQe1b2Xq3yZ4w5v")
+    );
+    assert_eq!(
+        Some("FZ8BxMU3BYc".to_string()),
+        get_video_id(
+            "https://music.youtube.com/watch?v=FZ8BxMU3BYc&list=RDAMVMFZ8BxMU3BYc&si=0dQe1b2Xq3yZ4w5v"
+        )
+    );
+    assert_eq!(
+        Some("FZ8BxMU3BYc".to_string()),
+        get_video_id("https://youtu.be/FZ8BxMU3BYc?t=43&list=PLRBp0Fe2GpgmsW46rJyudVFlY6IYjFBIK")
+    );
+    assert_eq!(
+        Some("FZ8BxMU3BYc".to_string()),
+        get_video_id("https://youtu.be/FZ8BxMU3BYc?si=0dQe1b2Xq3yZ4w5v")
+    );
+    assert_eq!(
+        Some("FZ8BxMU3BYc".to_string()),
+        get_video_id("https://youtu.be/FZ8BxMU3BYc/")
+    );
+    assert_eq!(
+        Some("FZ8BxMU3BYc".to_string()),
+        get_video_id("HTTPS://YOUTU.BE/FZ8BxMU3BYc")
+    );
+    assert_eq!(
+        Some("FZ8BxMU3BYc".to_string()),
+        get_video_id("https://www.YouTube.com/watch?v=FZ8BxMU3BYc")
+    );
 
     // Not valid video id
     assert_eq!(None, get_video_id("FZ8BxU3BYc"));
@@ -0,0 +1,20 @@
+#[tokio::test]
+async fn related_videos_continuation() {
+    use rusty_ytdl::Video;
+
+    let url = "https://www.youtube.com/watch?v=FZ8BxMU3BYc";
+
+    let video = Video::new(url).unwrap();
+
+    let mut video_info = video.get_basic_info().await.unwrap();
+    let initial_count = video_info.related_videos.len();
+
+    let related_videos = video.next_related_videos(&mut video_info).await.unwrap();
+
+    assert_eq!(
+        video_info.related_videos.len(),
+        initial_count + related_videos.len()
+    );
+
+    println!("Next related videos: {:#?}", related_videos);
+}
@@ -0,0 +1,22 @@
+#[test]
+fn search_blocking() {
+    #[cfg(feature = "blocking")]
+    {
+        use rusty_ytdl::blocking::search::{SearchOptions, SearchType, YouTube};
+
+        let youtube = YouTube::new().unwrap();
+
+        let res = youtube
+            .search(
+                "i know your ways",
+                Some(&SearchOptions {
+                    limit: 5,
+                    search_type: SearchType::Video,
+                    ..Default::default()
+                }),
+            )
+            .unwrap();
+
+        println!("{res:#?}");
+    }
+}
@@ -0,0 +1,31 @@
+#[tokio::test]
+async fn search_channel() {
+    use rusty_ytdl::search::{Channel, ChannelSearchOptions};
+    use std::collections::HashSet;
+
+    let mut uploads = Channel::get(
+        "https://www.youtube.com/@YouTube",
+        Some(&ChannelSearchOptions {
+            limit: 50,
+            ..Default::default()
+        }),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(uploads.videos.len(), 50);
+
+    let first_ids = uploads
+        .videos
+        .iter()
+        .map(|x| x.id.clone())
+        .collect::<HashSet<String>>();
+    assert_eq!(first_ids.len(), 50);
+
+    let next_videos = uploads.next(Some(10)).await.unwrap();
+
+    assert!(!next_videos.is_empty());
+    assert!(next_videos.len() <= 10);
+    assert!(next_videos.iter().all(|x| !first_ids.contains(&x.id)));
+    assert_eq!(uploads.videos.len(), 50 + next_videos.len());
+}
@@ -0,0 +1,16 @@
+#[tokio::test]
+async fn search_continued() {
+    use rusty_ytdl::search::YouTube;
+
+    let youtube = YouTube::new().unwrap();
+
+    let page = youtube.search_page("i know your ways", None).await.unwrap();
+
+    let continuation = page.continuation.expect("Continuation token not found");
+
+    let next_page = youtube.search_continued(continuation, None).await.unwrap();
+
+    assert!(!next_page.results.is_empty());
+
+    println!("{next_page:#?}");
+}
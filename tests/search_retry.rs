@@ -0,0 +1,49 @@
+#[tokio::test]
+async fn search_retry() {
+    use rusty_ytdl::search::RequestOptions;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let request_count = Arc::new(AtomicUsize::new(0));
+    let server_request_count = request_count.clone();
+
+    // Mock server, first request fails with 503 and the next ones succeed
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buffer = [0u8; 4096];
+            let _ = socket.read(&mut buffer).await.unwrap();
+
+            let response = if server_request_count.fetch_add(1, Ordering::SeqCst) == 0 {
+                "HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+            } else {
+                "HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok"
+            };
+
+            socket.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+
+    let client = RequestOptions {
+        max_retries: Some(2),
+        ..Default::default()
+    }
+    .build_client()
+    .unwrap();
+
+    let response = client
+        .get(format!("http://{address}"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "ok");
+    assert_eq!(request_count.load(Ordering::SeqCst), 2);
+}
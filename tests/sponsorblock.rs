@@ -0,0 +1,15 @@
+#[tokio::test]
+async fn sponsorblock() {
+    #[cfg(feature = "sponsorblock")]
+    {
+        use rusty_ytdl::sponsorblock::{get_skip_segments, SegmentCategory};
+
+        let segments = get_skip_segments("FZ8BxMU3BYc", SegmentCategory::ALL)
+            .await
+            .unwrap();
+
+        assert!(segments.iter().all(|segment| segment.start <= segment.end));
+
+        println!("Segments: {:#?}", segments);
+    }
+}
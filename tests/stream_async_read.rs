@@ -0,0 +1,14 @@
+mod common;
+
+#[tokio::test]
+async fn stream_async_read() {
+    let (stream, content_length) = common::lowest_audio_stream().await;
+
+    let mut reader = stream.into_async_read();
+    let mut bytes: Vec<u8> = vec![];
+
+    let copied = tokio::io::copy(&mut reader, &mut bytes).await.unwrap();
+
+    assert_eq!(copied, content_length);
+    assert_eq!(bytes.len() as u64, content_length);
+}
@@ -0,0 +1,20 @@
+mod common;
+
+#[tokio::test]
+async fn stream_range() {
+    use rusty_ytdl::stream::Stream;
+
+    let (stream, content_length) = common::lowest_audio_stream().await;
+
+    // Spans multiple chunks and stops before the end of the source
+    let range = stream.range(1000, Some(600_999)).await.unwrap();
+    assert_eq!(range.content_length(), 600_000);
+
+    let bytes = range.download_to_end().await.unwrap();
+    assert_eq!(bytes.len(), 600_000);
+
+    let tail = stream.range(content_length - 10, None).await.unwrap();
+    assert_eq!(tail.download_to_end().await.unwrap().len(), 10);
+
+    assert!(stream.range(content_length, None).await.is_err());
+}
@@ -7,4 +7,10 @@ async fn suggestion() {
     let res = youtube.suggestion("i know ", None).await;
 
     println!("{res:#?}");
+
+    // Suggestions are plain strings without the JSON quotes
+    assert!(res
+        .unwrap()
+        .iter()
+        .all(|x| !x.starts_with('"') && !x.ends_with('"')));
 }
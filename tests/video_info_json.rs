@@ -0,0 +1,16 @@
+#[tokio::test]
+async fn video_info_json() {
+    use rusty_ytdl::{Video, VideoInfo};
+
+    let url = "https://www.youtube.com/watch?v=FZ8BxMU3BYc";
+
+    let video = Video::new(url).unwrap();
+
+    let video_info = video.get_info().await.unwrap();
+
+    let json = video_info.to_json().unwrap();
+    let parsed_info = VideoInfo::from_json(&json).unwrap();
+
+    assert_eq!(parsed_info.formats, video_info.formats);
+    assert_eq!(parsed_info.to_json().unwrap(), json);
+}